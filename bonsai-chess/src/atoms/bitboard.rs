@@ -0,0 +1,219 @@
+//! # Bitboards
+//!
+//! This module provides the [`Bitboard`] struct, a set of board squares packed
+//! into a single 64-bit integer. Each bit corresponds to one square, which turns
+//! questions like "which of these squares are occupied?" into single machine
+//! instructions and makes it the workhorse of move generation and attack detection.
+
+use crate::atoms::Coordinate;
+
+/// A set of squares, stored as one bit per square.
+///
+/// # Bit Layout
+///
+/// The bit index of a square is `row * 8 + column`, following the same
+/// orientation as [`Coordinate`]:
+/// * **Bit 0**: a8 (row 0, column 0).
+/// * **Bit 7**: h8 (row 0, column 7).
+/// * **Bit 56**: a1 (row 7, column 0).
+/// * **Bit 63**: h1 (row 7, column 7).
+///
+/// Moving a piece "up" the board (towards Rank 8) therefore *decreases* its bit index by 8.
+///
+/// # Examples
+///
+/// ```rust
+/// use bonsai_chess::prelude::{Bitboard, Coordinate};
+///
+/// let e4 = Coordinate::from_algebraic_notation("e4").unwrap();
+/// let d5 = Coordinate::from_algebraic_notation("d5").unwrap();
+///
+/// let squares = Bitboard::from_coordinate(e4) | Bitboard::from_coordinate(d5);
+/// assert_eq!(squares.count(), 2);
+/// assert!(squares.contains(e4));
+/// assert_eq!(squares.into_iter().collect::<Vec<_>>(), vec![d5, e4]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bitboard(pub u64);
+
+impl Bitboard {
+    /// A bitboard without any square.
+    pub const EMPTY: Self = Self(0);
+
+    /// A bitboard containing all 64 squares.
+    pub const FULL: Self = Self(u64::MAX);
+
+    /// The 32 light squares, a8 and h1 among them.
+    pub const LIGHT_SQUARES: Self = Self(0xAA55_AA55_AA55_AA55);
+
+    /// The 32 dark squares, a1 and h8 among them.
+    pub const DARK_SQUARES: Self = Self(!Self::LIGHT_SQUARES.0);
+
+    /// Creates a bitboard containing a single square.
+    #[must_use]
+    pub const fn from_coordinate(coordinate: Coordinate) -> Self {
+        Self(1 << coordinate.index())
+    }
+
+    /// Returns `true` if the square is part of the set.
+    #[must_use]
+    pub const fn contains(self, coordinate: Coordinate) -> bool {
+        self.0 & (1 << coordinate.index()) != 0
+    }
+
+    /// Adds a square to the set.
+    pub const fn insert(&mut self, coordinate: Coordinate) {
+        self.0 |= 1 << coordinate.index();
+    }
+
+    /// Removes a square from the set.
+    pub const fn remove(&mut self, coordinate: Coordinate) {
+        self.0 &= !(1 << coordinate.index());
+    }
+
+    /// Returns `true` if the set contains no squares.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the number of squares in the set.
+    #[must_use]
+    pub const fn count(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// Returns `true` if the set contains two or more squares.
+    #[must_use]
+    pub const fn has_several(self) -> bool {
+        self.0 & self.0.wrapping_sub(1) != 0
+    }
+
+    /// Returns the square with the lowest bit index, if any.
+    #[must_use]
+    pub const fn first(self) -> Option<Coordinate> {
+        if self.is_empty() {
+            None
+        } else {
+            Coordinate::from_index(self.0.trailing_zeros() as usize)
+        }
+    }
+
+    /// Returns the square with the highest bit index, if any.
+    #[must_use]
+    pub const fn last(self) -> Option<Coordinate> {
+        if self.is_empty() {
+            None
+        } else {
+            Coordinate::from_index(63 - self.0.leading_zeros() as usize)
+        }
+    }
+}
+
+/// Iterates over the squares of a [`Bitboard`], from the lowest bit index (a8) to the highest (h1).
+#[derive(Clone, Debug)]
+pub struct BitboardIter(u64);
+
+impl Iterator for BitboardIter {
+    type Item = Coordinate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let index = self.0.trailing_zeros() as usize;
+
+        // Clear the lowest set bit
+        self.0 &= self.0 - 1;
+
+        Coordinate::from_index(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.0.count_ones() as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for BitboardIter {}
+
+impl IntoIterator for Bitboard {
+    type Item = Coordinate;
+    type IntoIter = BitboardIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        BitboardIter(self.0)
+    }
+}
+
+impl std::ops::BitAnd for Bitboard {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl std::ops::BitOr for Bitboard {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitXor for Bitboard {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl std::ops::Not for Bitboard {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Self(!self.0)
+    }
+}
+
+impl std::ops::BitAndAssign for Bitboard {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl std::ops::BitOrAssign for Bitboard {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl std::ops::BitXorAssign for Bitboard {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        self.0 ^= rhs.0;
+    }
+}
+
+/// Formats the bitboard as an 8x8 grid of `1`s and `.`s, Rank 8 on top.
+impl std::fmt::Display for Bitboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..8 {
+            for column in 0..8 {
+                let symbol = if self.0 & (1 << (row * 8 + column)) == 0 {
+                    '.'
+                } else {
+                    '1'
+                };
+                write!(f, "{symbol}")?;
+            }
+            if row < 7 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
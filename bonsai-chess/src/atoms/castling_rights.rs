@@ -4,6 +4,12 @@
 //! tracking the availability of castling for both White and Black on both the
 //! King-side and Queen-side. It is a critical component of the board's state
 //! and is required for accurate move generation and FEN parsing.
+//!
+//! Each right remembers the file of the Rook it castles with, so that the same
+//! type describes both standard chess (Rooks on the a- and h-files) and Chess960,
+//! where the Rooks may start on any file on either side of the King.
+
+use crate::{atoms::Side, moves::CastlingSide};
 
 /// Represents the castling permissions for both players.
 ///
@@ -13,12 +19,12 @@
 /// 3. The Rook is captured (losing rights only on that side).
 ///
 /// This struct tracks these rights independently of the board state.
-/// It corresponds to the "`KQkq`" portion of a FEN string.
+/// It corresponds to the "`KQkq`" portion of a FEN string (or "`HAha`" in Shredder-FEN).
 ///
 /// # Examples
 ///
 /// ```rust
-/// use bonsai_chess::prelude::CastlingRights;
+/// use bonsai_chess::prelude::{CastlingRights, CastlingSide, Side};
 ///
 /// // Create a default game state where everyone can castle
 /// let mut rights = CastlingRights::new();
@@ -28,14 +34,16 @@
 /// rights.disable_white_king_side();
 /// rights.disable_white_queen_side();
 /// assert!(!rights.white_king_side());
+///
+/// // In standard chess, Black castles long with the a8 Rook
+/// assert_eq!(rights.rook_file(Side::Black, CastlingSide::Long), Some(0));
 /// ```
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-#[allow(clippy::struct_excessive_bools)]
 pub struct CastlingRights {
-    white_king_side: bool,
-    white_queen_side: bool,
-    black_king_side: bool,
-    black_queen_side: bool,
+    /// The file of the Rook each right castles with, or `None` once the right is lost.
+    ///
+    /// White short, White long, Black short and Black long, in that order.
+    rook_files: [Option<usize>; 4],
 }
 
 impl Default for CastlingRights {
@@ -46,9 +54,16 @@ impl Default for CastlingRights {
 }
 
 impl CastlingRights {
+    /// File of the Rook castling short in standard chess (the h-file).
+    const STANDARD_SHORT_ROOK_FILE: usize = 7;
+
+    /// File of the Rook castling long in standard chess (the a-file).
+    const STANDARD_LONG_ROOK_FILE: usize = 0;
+
     /// Creates a new `CastlingRights` instance with all rights enabled.
     ///
-    /// This is the standard state for the start of a new chess game.
+    /// This is the standard state for the start of a new chess game, with
+    /// the Rooks on the a- and h-files.
     ///
     /// # Examples
     ///
@@ -60,12 +75,7 @@ impl CastlingRights {
     /// ```
     #[must_use]
     pub const fn new() -> Self {
-        Self {
-            white_king_side: true,
-            white_queen_side: true,
-            black_king_side: true,
-            black_queen_side: true,
-        }
+        Self::from(true, true, true, true)
     }
 
     /// Creates an instance with absolutely no castling rights.
@@ -84,17 +94,15 @@ impl CastlingRights {
     #[must_use]
     pub const fn no_rights() -> Self {
         Self {
-            white_king_side: false,
-            white_queen_side: false,
-            black_king_side: false,
-            black_queen_side: false,
+            rook_files: [None; 4],
         }
     }
 
     /// Creates a custom set of castling rights from explicit boolean values.
     ///
     /// This constructor is particularly useful when parsing FEN strings or
-    /// restoring a previous game state.
+    /// restoring a previous game state. The Rooks are assumed to stand on the
+    /// a- and h-files, as in standard chess.
     ///
     /// # Arguments
     ///
@@ -111,82 +119,173 @@ impl CastlingRights {
         black_queen_side: bool,
     ) -> Self {
         Self {
-            white_king_side,
-            white_queen_side,
-            black_king_side,
-            black_queen_side,
+            rook_files: [
+                Self::rook_file_if(white_king_side, Self::STANDARD_SHORT_ROOK_FILE),
+                Self::rook_file_if(white_queen_side, Self::STANDARD_LONG_ROOK_FILE),
+                Self::rook_file_if(black_king_side, Self::STANDARD_SHORT_ROOK_FILE),
+                Self::rook_file_if(black_queen_side, Self::STANDARD_LONG_ROOK_FILE),
+            ],
+        }
+    }
+
+    /// Returns `Some(rook_file)` if the right is `available`.
+    const fn rook_file_if(available: bool, rook_file: usize) -> Option<usize> {
+        if available { Some(rook_file) } else { None }
+    }
+
+    /// Index of a right in `rook_files`.
+    const fn slot(side: Side, castling_side: CastlingSide) -> usize {
+        let side = match side {
+            Side::White => 0,
+            Side::Black => 2,
+        };
+        match castling_side {
+            CastlingSide::Short => side,
+            CastlingSide::Long => side + 1,
+        }
+    }
+
+    /// Returns the file (0-7 for a-h) of the Rook `side` castles with on `castling_side`,
+    /// or `None` if that right has been lost.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::{CastlingRights, CastlingSide, Side};
+    ///
+    /// let mut rights = CastlingRights::no_rights();
+    /// rights.enable(Side::White, CastlingSide::Short, 6);
+    ///
+    /// assert_eq!(rights.rook_file(Side::White, CastlingSide::Short), Some(6));
+    /// assert!(rights.white_king_side());
+    /// ```
+    #[must_use]
+    pub const fn rook_file(self, side: Side, castling_side: CastlingSide) -> Option<usize> {
+        self.rook_files[Self::slot(side, castling_side)]
+    }
+
+    /// Returns `true` if `side` still has the right to castle on `castling_side`.
+    #[must_use]
+    pub const fn has(self, side: Side, castling_side: CastlingSide) -> bool {
+        self.rook_file(side, castling_side).is_some()
+    }
+
+    /// Returns `true` if every right still available castles with a Rook on the
+    /// a- or h-file, as in standard chess.
+    #[must_use]
+    pub const fn is_standard(self) -> bool {
+        let mut slot = 0;
+        while slot < self.rook_files.len() {
+            let expected = if slot % 2 == 0 {
+                Self::STANDARD_SHORT_ROOK_FILE
+            } else {
+                Self::STANDARD_LONG_ROOK_FILE
+            };
+            if let Some(file) = self.rook_files[slot]
+                && file != expected
+            {
+                return false;
+            }
+            slot += 1;
         }
+        true
+    }
+
+    /// Grants `side` the right to castle on `castling_side` with the Rook on `rook_file`.
+    pub const fn enable(&mut self, side: Side, castling_side: CastlingSide, rook_file: usize) {
+        self.rook_files[Self::slot(side, castling_side)] = Some(rook_file);
+    }
+
+    /// Revokes the right of `side` to castle on `castling_side`.
+    pub const fn disable(&mut self, side: Side, castling_side: CastlingSide) {
+        self.rook_files[Self::slot(side, castling_side)] = None;
     }
 
     /// Returns `true` if White still has the right to castle King-side.
     #[must_use]
     pub const fn white_king_side(self) -> bool {
-        self.white_king_side
+        self.has(Side::White, CastlingSide::Short)
     }
 
     /// Returns `true` if White still has the right to castle Queen-side.
     #[must_use]
     pub const fn white_queen_side(self) -> bool {
-        self.white_queen_side
+        self.has(Side::White, CastlingSide::Long)
     }
 
     /// Returns `true` if Black still has the right to castle King-side.
     #[must_use]
     pub const fn black_king_side(self) -> bool {
-        self.black_king_side
+        self.has(Side::Black, CastlingSide::Short)
     }
 
     /// Returns `true` if Black still has the right to castle Queen-side.
     #[must_use]
     pub const fn black_queen_side(self) -> bool {
-        self.black_queen_side
+        self.has(Side::Black, CastlingSide::Long)
     }
 
-    /// Grants White the right to castle King-side.
+    /// Grants White the right to castle King-side with the h1 Rook.
     pub const fn enable_white_king_side(&mut self) {
-        self.white_king_side = true;
+        self.enable(
+            Side::White,
+            CastlingSide::Short,
+            Self::STANDARD_SHORT_ROOK_FILE,
+        );
     }
 
-    /// Grants White the right to castle Queen-side.
+    /// Grants White the right to castle Queen-side with the a1 Rook.
     pub const fn enable_white_queen_side(&mut self) {
-        self.white_queen_side = true;
+        self.enable(
+            Side::White,
+            CastlingSide::Long,
+            Self::STANDARD_LONG_ROOK_FILE,
+        );
     }
 
-    /// Grants Black the right to castle King-side.
+    /// Grants Black the right to castle King-side with the h8 Rook.
     pub const fn enable_black_king_side(&mut self) {
-        self.black_king_side = true;
+        self.enable(
+            Side::Black,
+            CastlingSide::Short,
+            Self::STANDARD_SHORT_ROOK_FILE,
+        );
     }
 
-    /// Grants Black the right to castle Queen-side.
+    /// Grants Black the right to castle Queen-side with the a8 Rook.
     pub const fn enable_black_queen_side(&mut self) {
-        self.black_queen_side = true;
+        self.enable(
+            Side::Black,
+            CastlingSide::Long,
+            Self::STANDARD_LONG_ROOK_FILE,
+        );
     }
 
     /// Revokes White's right to castle King-side.
     ///
-    /// Typically called when White's King or h1 Rook moves, or when the h1 Rook is captured.
+    /// Typically called when White's King or King-side Rook moves, or when that Rook is captured.
     pub const fn disable_white_king_side(&mut self) {
-        self.white_king_side = false;
+        self.disable(Side::White, CastlingSide::Short);
     }
 
     /// Revokes White's right to castle Queen-side.
     ///
-    /// Typically called when White's King or a1 Rook moves, or when the a1 Rook is captured.
+    /// Typically called when White's King or Queen-side Rook moves, or when that Rook is captured.
     pub const fn disable_white_queen_side(&mut self) {
-        self.white_queen_side = false;
+        self.disable(Side::White, CastlingSide::Long);
     }
 
     /// Revokes Black's right to castle King-side.
     ///
-    /// Typically called when Black's King or h8 Rook moves, or when the h8 Rook is captured.
+    /// Typically called when Black's King or King-side Rook moves, or when that Rook is captured.
     pub const fn disable_black_king_side(&mut self) {
-        self.black_king_side = false;
+        self.disable(Side::Black, CastlingSide::Short);
     }
 
     /// Revokes Black's right to castle Queen-side.
     ///
-    /// Typically called when Black's King or a8 Rook moves, or when the a8 Rook is captured.
+    /// Typically called when Black's King or Queen-side Rook moves, or when that Rook is captured.
     pub const fn disable_black_queen_side(&mut self) {
-        self.black_queen_side = false;
+        self.disable(Side::Black, CastlingSide::Long);
     }
 }
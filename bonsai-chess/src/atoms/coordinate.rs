@@ -5,7 +5,7 @@
 //! is within the valid bounds of the board, eliminating the need for bounds
 //! checking on every board access.
 
-use crate::{BOARD_COLUMNS_RANGE, BOARD_ROWS_RANGE};
+use crate::{BOARD_COLUMNS, BOARD_COLUMNS_RANGE, BOARD_ROWS, BOARD_ROWS_RANGE};
 
 /// Represents a validated coordinate on the chess board.
 ///
@@ -19,10 +19,13 @@ use crate::{BOARD_COLUMNS_RANGE, BOARD_ROWS_RANGE};
 /// * **Column**: 0-indexed, corresponding to array indices.
 ///     * Column 0 = File A
 ///     * Column 7 = File H
+///
+/// Both indices are stored in a byte, so that a coordinate, and every [`Ply`](crate::moves::Ply)
+/// holding some, stays small enough to be kept by the hundreds on the stack.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Coordinate {
-    row: usize,
-    column: usize,
+    row: u8,
+    column: u8,
 }
 
 impl Coordinate {
@@ -63,14 +66,49 @@ impl Coordinate {
 
         if BOARD_ROWS_RANGE.contains(&row_usize) && BOARD_COLUMNS_RANGE.contains(&column_usize) {
             Some(Self {
-                row: row_usize,
-                column: column_usize,
+                row: u8::try_from(row_usize).ok()?,
+                column: u8::try_from(column_usize).ok()?,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Creates a `Coordinate` from its square index (`row * 8 + column`).
+    ///
+    /// Square indices are used to address bits in a [`super::Bitboard`]:
+    /// index 0 is a8 and index 63 is h1.
+    ///
+    /// Returns `None` if the index is 64 or greater.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::Coordinate;
+    ///
+    /// let e4 = Coordinate::from_index(36).unwrap();
+    /// assert_eq!(e4.to_algebraic_notation(), "e4");
+    /// assert!(Coordinate::from_index(64).is_none());
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)] // Both quotients are below 8
+    pub const fn from_index(index: usize) -> Option<Self> {
+        if index < BOARD_ROWS * BOARD_COLUMNS {
+            Some(Self {
+                row: (index / BOARD_COLUMNS) as u8,
+                column: (index % BOARD_COLUMNS) as u8,
             })
         } else {
             None
         }
     }
 
+    /// Returns the square index (`row * 8 + column`), from 0 (a8) to 63 (h1).
+    #[must_use]
+    pub const fn index(&self) -> usize {
+        self.row() * BOARD_COLUMNS + self.column()
+    }
+
     /// Calculates a new coordinate by applying a directional offset and a scalar distance.
     ///
     /// This is particularly useful for move generation, such as calculating ray attacks
@@ -102,8 +140,8 @@ impl Coordinate {
     #[must_use]
     pub fn with_offset(&self, direction: (isize, isize), distance: isize) -> Option<Self> {
         Self::new(
-            self.row.cast_signed() + direction.0 * distance,
-            self.column.cast_signed() + direction.1 * distance,
+            self.row().cast_signed() + direction.0 * distance,
+            self.column().cast_signed() + direction.1 * distance,
         )
     }
 
@@ -234,20 +272,18 @@ impl Coordinate {
     /// Returns the internal row index (0-7).
     #[must_use]
     pub const fn row(&self) -> usize {
-        self.row
+        self.row as usize
     }
 
     /// Returns the internal column index (0-7).
     #[must_use]
     pub const fn column(&self) -> usize {
-        self.column
+        self.column as usize
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{BOARD_COLUMNS, BOARD_ROWS};
-
     use super::*;
 
     #[test]
@@ -333,6 +369,20 @@ mod tests {
         assert_eq!(c.to_algebraic_notation(), "e4");
     }
 
+    #[test]
+    fn test_index_round_trip() {
+        for index in 0..BOARD_ROWS * BOARD_COLUMNS {
+            let coordinate = Coordinate::from_index(index).unwrap();
+            assert_eq!(coordinate.index(), index);
+            assert_eq!(
+                Coordinate::new(coordinate.row(), coordinate.column()),
+                Some(coordinate)
+            );
+        }
+
+        assert!(Coordinate::from_index(BOARD_ROWS * BOARD_COLUMNS).is_none());
+    }
+
     #[test]
     fn test_algebraic_round_trip() {
         // Iterate over every valid square on the board
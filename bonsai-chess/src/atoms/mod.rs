@@ -6,10 +6,15 @@
 //!
 //! # Components
 //!
+//! * [`Bitboard`]: A set of squares packed into a single 64-bit integer.
 //! * [`Team`]: Represents the two sides playing the game (White and Black).
 //! * [`Coordinates`]: Represents a validated, strongly-typed location on the board (Ranks = Rows, Files = Columns).
 //! * [`CastlingRights`]: Tracks the availability of castling for both sides.
 //! * [`MoveCounter`]: Tracks turn history for rules like the 50-move rule and draw claims.
+//! * [`Pocket`]: The captured pieces a player holds in hand, ready to be dropped (Crazyhouse).
+
+/// Represents a set of squares as a 64-bit integer, one bit per square.
+mod bitboard;
 
 /// Tracks castling permissions for both players on the King-side and Queen-side.
 mod castling_rights;
@@ -20,10 +25,15 @@ mod coordinate;
 /// Tracks half-moves and full-moves for game rule enforcement (e.g., the 50-move rule).
 mod move_counter;
 
+/// Counts the pieces a player holds in hand, for variants with drops.
+mod pocket;
+
 /// Represents the two opposing sides in a game of chess (White and Black).
 mod side;
 
+pub use bitboard::{Bitboard, BitboardIter};
 pub use castling_rights::CastlingRights;
 pub use coordinate::Coordinate;
 pub use move_counter::MoveCounter;
+pub use pocket::Pocket;
 pub use side::Side;
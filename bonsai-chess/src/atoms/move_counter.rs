@@ -31,6 +31,7 @@
 /// assert_eq!(counter.fifty_move_rule_counter(), 0); // Resets to 0
 /// ```
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MoveCounter {
     /// A stack tracking the reversible 50-move rule counter.
     ///
@@ -166,6 +167,12 @@ impl MoveCounter {
     /// counter.untick();
     /// assert_eq!(counter.halfmove(), 0);
     /// assert_eq!(counter.fifty_move_rule_counter(), 0);
+    ///
+    /// // Undoing Black's move goes back to the previous fullmove
+    /// counter.tick(false);
+    /// counter.tick(false);
+    /// counter.untick();
+    /// assert_eq!(counter.fullmove(), 1);
     /// ```
     pub fn untick(&mut self) {
         if let Some(count) = self.fifty_move_rule_counter.last_mut() {
@@ -176,10 +183,11 @@ impl MoveCounter {
             }
         }
 
-        self.halfmove = self.halfmove.saturating_sub(1);
+        // The fullmove number went up on the tick that reached an even halfmove count
         if self.halfmove.is_multiple_of(2) {
             self.fullmove = self.fullmove.saturating_sub(1);
         }
+        self.halfmove = self.halfmove.saturating_sub(1);
     }
 
     /// Returns the current value of the fifty-move rule counter.
@@ -0,0 +1,89 @@
+//! # Pocket
+//!
+//! This module provides the [`Pocket`] struct, which holds the pieces a player
+//! has captured and may drop back onto the board, as in Crazyhouse.
+
+use crate::pieces::Kind;
+
+/// The pieces held in hand by one player, counted by kind.
+///
+/// Kings are never captured, so a pocket only ever holds Queens, Rooks, Bishops,
+/// Knights and Pawns.
+///
+/// # Examples
+///
+/// ```rust
+/// use bonsai_chess::prelude::{Kind, Pocket};
+///
+/// let mut pocket = Pocket::default();
+/// pocket.add(Kind::Knight);
+/// pocket.add(Kind::Pawn);
+/// pocket.add(Kind::Pawn);
+///
+/// assert_eq!(pocket.count(Kind::Pawn), 2);
+/// assert_eq!(pocket.kinds().collect::<Vec<_>>(), vec![Kind::Knight, Kind::Pawn]);
+///
+/// pocket.remove(Kind::Knight);
+/// assert_eq!(pocket.count(Kind::Knight), 0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Pocket {
+    /// Number of pieces of each kind, indexed by [`Kind::index`].
+    counts: [u8; 6],
+}
+
+impl Pocket {
+    /// The kinds a pocket can hold, in the order they are written in a FEN.
+    pub const KINDS: [Kind; 5] = [
+        Kind::Queen,
+        Kind::Rook,
+        Kind::Bishop,
+        Kind::Knight,
+        Kind::Pawn,
+    ];
+
+    /// Returns the most pieces of `kind` a pocket can hold in a real game.
+    ///
+    /// Promoted pieces go back to being pawns when captured, so a pocket never holds more
+    /// pieces of a kind than both armies start with, and never a King.
+    #[must_use]
+    pub const fn max_count(kind: Kind) -> u8 {
+        match kind {
+            Kind::King => 0,
+            Kind::Queen => 2,
+            Kind::Rook | Kind::Bishop | Kind::Knight => 4,
+            Kind::Pawn => 16,
+        }
+    }
+
+    /// Returns the number of pieces of `kind` in the pocket.
+    #[must_use]
+    pub const fn count(self, kind: Kind) -> u8 {
+        self.counts[kind.index()]
+    }
+
+    /// Returns `true` if the pocket holds no piece at all.
+    #[must_use]
+    pub fn is_empty(self) -> bool {
+        self.counts.iter().all(|count| *count == 0)
+    }
+
+    /// Puts one piece of `kind` in the pocket.
+    ///
+    /// The count stops at 255, far beyond what a game can put in hand.
+    pub const fn add(&mut self, kind: Kind) {
+        self.counts[kind.index()] = self.counts[kind.index()].saturating_add(1);
+    }
+
+    /// Takes one piece of `kind` out of the pocket, if there is any.
+    pub const fn remove(&mut self, kind: Kind) {
+        self.counts[kind.index()] = self.counts[kind.index()].saturating_sub(1);
+    }
+
+    /// Returns the kinds of which the pocket holds at least one piece, in [`Pocket::KINDS`] order.
+    pub fn kinds(self) -> impl Iterator<Item = Kind> {
+        Self::KINDS
+            .into_iter()
+            .filter(move |kind| self.count(*kind) > 0)
+    }
+}
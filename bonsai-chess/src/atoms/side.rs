@@ -19,6 +19,7 @@
 /// assert_eq!(player_color, Team::White);
 /// ```
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Side {
     /// The White pieces, which traditionally move first.
     White,
@@ -50,4 +51,15 @@ impl Side {
             Self::Black => Self::White,
         }
     }
+
+    /// Returns a dense index for the side (White = 0, Black = 1).
+    ///
+    /// Useful for addressing per-side lookup tables.
+    #[must_use]
+    pub const fn index(self) -> usize {
+        match self {
+            Self::White => 0,
+            Self::Black => 1,
+        }
+    }
 }
@@ -0,0 +1,135 @@
+//! Parsing of the command-line arguments of the Perft binary.
+
+/// The text printed by `perft help`, and after an invalid command.
+pub const USAGE: &str = "\
+Usage:
+  perft                     Verify the move generator against every known result
+  perft run <depth> [fen]   Count the leaf nodes of a position (default: the starting position)
+  perft divide <depth> [fen]
+                            Count the leaf nodes below each root move, one `uci: nodes` per line
+  perft hashed [--threads <n>] [--hash <megabytes>] <depth> [fen]
+                            Count the leaf nodes with a shared hash table (256 MB by default)
+                            and a pool of threads (one per core by default)
+  perft suite               Verify the well-known tricky positions (Kiwipete, positions 3 to 6)
+  perft file <path>         Verify every `fen;D1 n;D2 n` line of a file
+  perft help                Print this message
+
+The FEN may be given as a single quoted argument, or as its separate fields.";
+
+/// The size of the hash table of `perft hashed`, in megabytes, unless `--hash` is given.
+pub const DEFAULT_HASH_MEGABYTES: usize = 256;
+
+/// What the binary was asked to do.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Runs every built-in verification, as the binary did before it took arguments.
+    Verify,
+
+    /// Counts the leaf nodes of a position.
+    Run { depth: usize, fen: Option<String> },
+
+    /// Counts the leaf nodes below each legal move of a position.
+    Divide { depth: usize, fen: Option<String> },
+
+    /// Counts the leaf nodes of a position with a hash table and a pool of workers.
+    Hashed {
+        depth: usize,
+        fen: Option<String>,
+        /// The number of workers, or `None` for one per core.
+        threads: Option<usize>,
+        /// The size of the hash table, in megabytes.
+        hash_megabytes: usize,
+    },
+
+    /// Runs the built-in suite of tricky positions.
+    Suite,
+
+    /// Runs the suite found in a file.
+    File { path: String },
+
+    /// Prints the usage.
+    Help,
+}
+
+impl Command {
+    /// Reads the command from the arguments, without the name of the binary.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing the first invalid argument.
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let Some(command) = args.next() else {
+            return Ok(Self::Verify);
+        };
+
+        let command = match command.as_str() {
+            "run" | "divide" => {
+                let (depth, fen) = parse_position(&command, args)?;
+                return Ok(if command == "run" {
+                    Self::Run { depth, fen }
+                } else {
+                    Self::Divide { depth, fen }
+                });
+            }
+            "hashed" => {
+                let mut threads = None;
+                let mut hash_megabytes = DEFAULT_HASH_MEGABYTES;
+                let mut args = args.peekable();
+
+                while let Some(option) = args.next_if(|arg| arg.starts_with("--")) {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("`{option}` needs a value"))?;
+                    let value = value
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|&value| value > 0)
+                        .ok_or_else(|| format!("`{value}` is not a valid value for `{option}`"))?;
+
+                    match option.as_str() {
+                        "--threads" => threads = Some(value),
+                        "--hash" => hash_megabytes = value,
+                        _ => return Err(format!("unknown option `{option}`")),
+                    }
+                }
+
+                let (depth, fen) = parse_position(&command, args)?;
+                return Ok(Self::Hashed {
+                    depth,
+                    fen,
+                    threads,
+                    hash_megabytes,
+                });
+            }
+            "suite" => Self::Suite,
+            "file" => Self::File {
+                path: args.next().ok_or("`file` needs a path")?,
+            },
+            "help" | "-h" | "--help" => Self::Help,
+            _ => return Err(format!("unknown command `{command}`")),
+        };
+
+        args.next().map_or(Ok(command), |extra| {
+            Err(format!("unexpected argument `{extra}`"))
+        })
+    }
+}
+
+/// Reads the `<depth> [fen]` arguments that end the commands walking a position.
+fn parse_position(
+    command: &str,
+    mut args: impl Iterator<Item = String>,
+) -> Result<(usize, Option<String>), String> {
+    let depth = args
+        .next()
+        .ok_or_else(|| format!("`{command}` needs a depth"))?;
+    let depth = depth
+        .parse()
+        .map_err(|_| format!("`{depth}` is not a valid depth"))?;
+
+    // The fields of the FEN may have been split by the shell
+    let fen = args.collect::<Vec<_>>().join(" ");
+    let fen = (!fen.is_empty()).then_some(fen);
+
+    Ok((depth, fen))
+}
@@ -82,3 +82,79 @@ pub const PERFT_EXPECTED: [PerftResults; 9] = [
         promotions: 0,
     },
 ];
+
+/// The known correct node counts, from depth 1 to 5, of positions from Chess960 games.
+///
+/// Castling rights are given in Shredder-FEN, and the positions exercise castling with
+/// the King or Rook already on its destination, and with Rooks on unusual files.
+///
+/// Source: [CPW - Chess960 Perft Results](https://www.chessprogramming.org/Chess960_Perft_Results)
+pub const CHESS960_PERFT_EXPECTED: [(&str, [usize; 5]); 8] = [
+    (
+        "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+        [21, 528, 12_189, 326_672, 8_146_062],
+    ),
+    (
+        "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+        [21, 807, 18_002, 667_366, 16_253_601],
+    ),
+    (
+        "b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9",
+        [20, 479, 10_471, 273_318, 6_417_013],
+    ),
+    (
+        "qbbnnrkr/2pp2pp/p7/1p2pp2/8/P3PP2/1PPP1KPP/QBBNNR1R w hf - 0 9",
+        [22, 593, 13_440, 382_958, 9_183_776],
+    ),
+    (
+        "1nbbnrkr/p1p1ppp1/3p4/1p3P1p/3Pq2P/8/PPP1P1P1/QNBBNRKR w HFhf - 0 9",
+        [28, 1_120, 31_058, 1_171_749, 34_030_312],
+    ),
+    (
+        "qnbnr1kr/ppp1b1pp/4p3/3p1p2/8/2NPP3/PPP1BPPP/QNB1R1KR w HEhe - 1 9",
+        [29, 899, 26_578, 824_055, 24_851_983],
+    ),
+    (
+        "q1bnrkr1/ppppp2p/2n2p2/4b1p1/2NP4/8/PPP1PPPP/QNB1RRKB w ge - 1 9",
+        [30, 860, 24_566, 732_757, 21_093_346],
+    ),
+    (
+        "qbn1brkr/ppp1p1p1/2n4p/3p1p2/P7/6PP/QPPPPP2/1BNNBRKR w HFhf - 0 9",
+        [25, 635, 17_054, 465_806, 13_203_304],
+    ),
+];
+
+/// The known correct node counts, from depth 1, of the well-known positions that exercise
+/// the corners of move generation: castling through and out of check, promotions with
+/// and without capture, en passant captures that expose the King, and discovered checks.
+///
+/// The depths are kept shallow enough for the whole suite to run in seconds.
+///
+/// Source: [CPW - Perft Results](https://www.chessprogramming.org/Perft_Results)
+pub const TRICKY_PERFT_EXPECTED: [(&str, &str, &[usize]); 5] = [
+    (
+        "Kiwipete",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        &[48, 2_039, 97_862, 4_085_603],
+    ),
+    (
+        "Position 3",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        &[14, 191, 2_812, 43_238, 674_624, 11_030_083],
+    ),
+    (
+        "Position 4",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        &[6, 264, 9_467, 422_333, 15_833_292],
+    ),
+    (
+        "Position 5",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        &[44, 1_486, 62_379, 2_103_487],
+    ),
+    (
+        "Position 6",
+        "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        &[46, 2_079, 89_890, 3_894_594],
+    ),
+];
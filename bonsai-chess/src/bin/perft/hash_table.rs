@@ -0,0 +1,76 @@
+//! A hash table of subtree results, shared by the threads of a hashed Perft run.
+
+use std::sync::Mutex;
+
+use crate::perft_results::PerftResults;
+
+/// The results of the subtree of a position, walked to some depth.
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    /// The Zobrist hash of the position, see `Game::hash`.
+    hash: u64,
+    depth: usize,
+    results: PerftResults,
+}
+
+/// A fixed-size table of subtree results, keyed on the position and the depth.
+///
+/// Positions reached through different move orders (transpositions) have the same
+/// subtree, so walking it once is enough. The table can be shared between threads: each
+/// slot has its own lock, so threads only wait for each other when they touch the same
+/// slot at the same time.
+///
+/// A new result always replaces the one in its slot. As the whole key is compared on
+/// lookup, a replaced result is simply walked again, and can never be returned for
+/// another position (barring a collision of the 64-bit hashes).
+pub struct PerftTable {
+    slots: Vec<Mutex<Option<Entry>>>,
+}
+
+impl PerftTable {
+    /// Creates an empty table using at most `megabytes` of memory.
+    ///
+    /// The number of slots is rounded down to a power of two, and is at least 1.
+    pub fn new(megabytes: usize) -> Self {
+        let slot_size = size_of::<Mutex<Option<Entry>>>();
+        let capacity = (megabytes * 1024 * 1024 / slot_size).max(1);
+        // Rounding down to a power of two lets the index be a mask of the key
+        let capacity = 1 << capacity.ilog2();
+
+        Self {
+            slots: (0..capacity).map(|_| Mutex::new(None)).collect(),
+        }
+    }
+
+    /// Returns the results of the subtree of the position `hash`, walked to `depth`, if
+    /// they are in the table.
+    pub fn probe(&self, hash: u64, depth: usize) -> Option<PerftResults> {
+        let slot = self.slots[self.index(hash, depth)].lock().ok()?;
+
+        slot.filter(|entry| entry.hash == hash && entry.depth == depth)
+            .map(|entry| entry.results)
+    }
+
+    /// Stores the results of the subtree of the position `hash`, walked to `depth`.
+    pub fn store(&self, hash: u64, depth: usize, results: PerftResults) {
+        // A poisoned slot is left alone: storing is only ever an optimization
+        if let Ok(mut slot) = self.slots[self.index(hash, depth)].lock() {
+            *slot = Some(Entry {
+                hash,
+                depth,
+                results,
+            });
+        }
+    }
+
+    /// Returns the slot of a key, mixing in the depth so that the results of the same
+    /// position at different depths do not fight over one slot.
+    fn index(&self, hash: u64, depth: usize) -> usize {
+        let depth = u64::try_from(depth).unwrap_or(u64::MAX);
+        let key = hash ^ depth.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+
+        // The length is a power of two, so this keeps the low bits of the key
+        let mask = u64::try_from(self.slots.len() - 1).unwrap_or(u64::MAX);
+        usize::try_from(key & mask).unwrap_or_default()
+    }
+}
@@ -1,20 +1,88 @@
 //! The entry point for the Perft (Performance Test) binary.
 //!
 //! This program verifies the correctness and performance of the move generator
-//! by walking the move tree of the starting position up to a certain depth.
-//! It compares the results against known correct values defined in [`expected::PERFT_EXPECTED`].
+//! by walking the move tree of positions up to a certain depth, and comparing the
+//! node counts against known correct values.
+//!
+//! Without arguments, it runs every built-in verification: positions from Chess960
+//! games ([`expected::CHESS960_PERFT_EXPECTED`]), the well-known tricky positions
+//! ([`expected::TRICKY_PERFT_EXPECTED`]), and finally the starting position, whose
+//! results are defined in [`expected::PERFT_EXPECTED`].
+//!
+//! Arguments select a single task instead, such as walking an arbitrary FEN or dividing
+//! its count by root move: see [`cli::USAGE`].
 
-use std::time::Instant;
+use std::{
+    process::ExitCode,
+    time::{Duration, Instant},
+};
 
 use bonsai_chess::prelude::*;
 
+mod cli;
 mod expected;
+mod hash_table;
 mod perft;
 mod perft_results;
+mod pool;
+mod suite;
+
+use crate::{
+    cli::{Command, USAGE},
+    expected::{CHESS960_PERFT_EXPECTED, PERFT_EXPECTED, TRICKY_PERFT_EXPECTED},
+    hash_table::PerftTable,
+    perft::{divide, root_level_perft},
+    perft_results::PerftResults,
+    pool::pooled_perft,
+    suite::{SuiteEntry, verify_suite},
+};
+
+fn main() -> ExitCode {
+    let command = match Command::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(error) => {
+            eprintln!("error: {error}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let success = match command {
+        Command::Verify => verify_all(),
+        Command::Run { depth, fen } => run(depth, fen.as_deref()),
+        Command::Divide { depth, fen } => run_divide(depth, fen.as_deref()),
+        Command::Hashed {
+            depth,
+            fen,
+            threads,
+            hash_megabytes,
+        } => run_hashed(depth, fen.as_deref(), threads, hash_megabytes),
+        Command::Suite => verify_tricky_positions(),
+        Command::File { path } => verify_file(&path),
+        Command::Help => {
+            println!("{USAGE}");
+            true
+        }
+    };
+
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Runs every built-in verification, stopping at the first suite that fails.
+fn verify_all() -> bool {
+    // The Chess960 positions only go to depth 5, so they run first
+    let chess960 = CHESS960_PERFT_EXPECTED
+        .iter()
+        .map(|(fen, counts)| (Some("Chess960"), SuiteEntry::from_counts(fen, counts)));
 
-use crate::{expected::PERFT_EXPECTED, perft::root_level_perft};
+    if !verify_suite(chess960) || !verify_tricky_positions() {
+        return false;
+    }
+    println!();
 
-fn main() {
     // Iterate through each depth level defined in our expected results.
     for (depth, &expected) in PERFT_EXPECTED.iter().enumerate() {
         let mut game = Game::from_starting_position();
@@ -30,6 +98,140 @@ fn main() {
         println!("Took: {} seconds\n", end.as_secs_f64());
 
         // Validate the result
-        assert_eq!(result, expected);
+        if result != expected {
+            println!("MISMATCH: expected {expected:?}");
+            return false;
+        }
     }
+
+    true
+}
+
+/// Runs the built-in suite of tricky positions.
+fn verify_tricky_positions() -> bool {
+    let entries = TRICKY_PERFT_EXPECTED
+        .iter()
+        .map(|(name, fen, counts)| (Some(*name), SuiteEntry::from_counts(fen, counts)));
+
+    verify_suite(entries)
+}
+
+/// Reads the position to walk, which is the starting position if no FEN is given.
+fn read_position(fen: Option<&str>) -> Option<Game> {
+    let Some(fen) = fen else {
+        return Some(Game::from_starting_position());
+    };
+
+    match Game::try_from_fen(fen) {
+        Ok(game) => Some(game),
+        Err(error) => {
+            eprintln!("error: invalid FEN: {error}");
+            None
+        }
+    }
+}
+
+/// Walks a single position, and prints its results.
+fn run(depth: usize, fen: Option<&str>) -> bool {
+    let Some(mut game) = read_position(fen) else {
+        return false;
+    };
+
+    let start = Instant::now();
+    let result = root_level_perft(&mut game, depth);
+    let end = start.elapsed();
+
+    print_timed_results(&result, end);
+
+    true
+}
+
+/// Walks a single position with a shared hash table and a pool of workers, and prints
+/// its results.
+fn run_hashed(
+    depth: usize,
+    fen: Option<&str>,
+    threads: Option<usize>,
+    hash_megabytes: usize,
+) -> bool {
+    let Some(game) = read_position(fen) else {
+        return false;
+    };
+    let threads = threads.unwrap_or_else(|| {
+        std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+    });
+
+    println!("Threads: {threads}, hash: {hash_megabytes} MB");
+    let table = PerftTable::new(hash_megabytes);
+
+    let start = Instant::now();
+    let result = pooled_perft(&game, depth, threads, &table);
+    let end = start.elapsed();
+
+    print_timed_results(&result, end);
+
+    true
+}
+
+/// Prints the results of a run, how long it took, and the speed it walked the tree at.
+fn print_timed_results(result: &PerftResults, elapsed: Duration) {
+    let seconds = elapsed.as_secs_f64();
+    #[allow(clippy::cast_precision_loss)] // Only counts beyond 2^53 nodes lose precision
+    let nodes_per_second = result.nodes as f64 / seconds.max(f64::EPSILON);
+
+    println!("{result:?}");
+    println!("Took: {seconds} seconds ({nodes_per_second:.0} nodes per second)");
+}
+
+/// Prints the node count below each root move, in the format of most other engines.
+fn run_divide(depth: usize, fen: Option<&str>) -> bool {
+    if depth == 0 {
+        eprintln!("error: divide needs a depth of at least 1");
+        return false;
+    }
+    let Some(game) = read_position(fen) else {
+        return false;
+    };
+
+    let results = divide(&game, depth);
+    for (m, result) in &results {
+        println!("{}: {}", m.to_uci(), result.nodes);
+    }
+
+    let total: usize = results.iter().map(|(_, result)| result.nodes).sum();
+    println!("\nMoves: {}", results.len());
+    println!("Nodes searched: {total}");
+
+    true
+}
+
+/// Runs the suite of a file with one `fen;D1 n;D2 n` line per position.
+///
+/// Blank lines, and lines starting with `#`, are skipped.
+fn verify_file(path: &str) -> bool {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) => {
+            eprintln!("error: cannot read `{path}`: {error}");
+            return false;
+        }
+    };
+
+    let mut entries = Vec::new();
+    for (number, line) in (1..).zip(contents.lines()) {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match SuiteEntry::parse(line) {
+            Ok(entry) => entries.push((None, entry)),
+            Err(error) => {
+                eprintln!("error: line {number}: {error}");
+                return false;
+            }
+        }
+    }
+
+    verify_suite(entries)
 }
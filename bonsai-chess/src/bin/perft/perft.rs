@@ -1,6 +1,6 @@
 use bonsai_chess::prelude::*;
 
-use crate::perft_results::PerftResults;
+use crate::{hash_table::PerftTable, perft_results::PerftResults};
 
 /// Executes a Perft run starting from the current board state, using multiple threads.
 ///
@@ -39,7 +39,7 @@ pub fn root_level_perft(game: &mut Game, depth: usize) -> PerftResults {
         let mut board_clone = game.clone();
 
         let handle = std::thread::spawn(move || {
-            board_clone.make_move(&m);
+            board_clone.make_move_unchecked(&m);
             perft(&mut board_clone, depth - 1)
         });
 
@@ -58,6 +58,39 @@ pub fn root_level_perft(game: &mut Game, depth: usize) -> PerftResults {
     total_results
 }
 
+/// Counts the leaf nodes below each legal move of the position, using multiple threads.
+///
+/// This is the "divide" of other move generators: comparing its output with theirs
+/// points at the root move whose subtree is wrong, which can then be divided in turn.
+/// The moves are sorted by their UCI notation.
+///
+/// # Panics
+///
+/// Panics if `depth` is 0, as there is no move to divide by.
+pub fn divide(game: &Game, depth: usize) -> Vec<(Ply, PerftResults)> {
+    assert!(depth > 0, "divide needs at least one ply");
+
+    let mut handles = Vec::new();
+
+    // Spawn a thread for each legal move available at the root.
+    for m in game.get_legal_moves() {
+        let mut board_clone = game.clone();
+
+        handles.push(std::thread::spawn(move || {
+            board_clone.make_move_unchecked(&m);
+            (m, perft(&mut board_clone, depth - 1))
+        }));
+    }
+
+    let mut results: Vec<_> = handles
+        .into_iter()
+        .filter_map(|handle| handle.join().ok())
+        .collect();
+    results.sort_by_key(|(m, _)| m.to_uci());
+
+    results
+}
+
 /// Recursively calculates the number of leaf nodes at a specific depth.
 ///
 /// This is the core single-threaded worker function.
@@ -79,36 +112,80 @@ pub fn perft(game: &mut Game, depth: usize) -> PerftResults {
     // If we are at depth 1, the number of nodes is simply the number of legal moves.
     // We can count other features (captures, etc.) by inspecting the move objects
     // without actually executing them on the board.
+    let mut moves = MoveList::new();
+    game.generate_moves_into(&mut moves);
+
     if depth == 1 {
-        let moves = game.get_legal_moves();
-        results.nodes = moves.len();
+        return count_moves(&moves);
+    }
 
-        for m in moves {
-            if m.piece_captured().is_some() {
-                results.captures += 1;
-            }
+    // We must make moves to traverse deeper into the tree.
+    for m in &moves {
+        game.make_move_unchecked(m);
 
-            if let Some(sp) = m.special_move() {
-                match sp {
-                    SpecialMove::Castle(_) => results.castles += 1,
-                    SpecialMove::EnPassant(_) => results.en_passant += 1,
-                    SpecialMove::Promotion(_) => results.promotions += 1,
-                }
-            }
-        }
+        // Accumulate results from the leaves
+        results += perft(game, depth - 1);
+
+        game.unmake_move();
+    }
+
+    results
+}
 
+/// Recursively calculates the number of leaf nodes at a specific depth, like [`perft`],
+/// but looks the subtree of each position up in `table` before walking it.
+///
+/// Subtrees of depth 1 are not stored: counting them in bulk is cheaper than a lookup.
+pub fn hashed_perft(game: &mut Game, depth: usize, table: &PerftTable) -> PerftResults {
+    if depth == 0 {
+        let mut results = PerftResults::new();
+        results.nodes = 1;
         return results;
     }
 
-    // We must make moves to traverse deeper into the tree.
-    let moves = game.get_legal_moves();
-    for m in moves {
-        game.make_move(&m);
+    let hash = game.hash();
+    if let Some(results) = table.probe(hash, depth) {
+        return results;
+    }
 
-        // Accumulate results from the leaves
-        results += perft(game, depth - 1);
+    let mut moves = MoveList::new();
+    game.generate_moves_into(&mut moves);
+
+    if depth == 1 {
+        return count_moves(&moves);
+    }
 
-        game.undo_last_move();
+    let mut results = PerftResults::new();
+    for m in &moves {
+        game.make_move_unchecked(m);
+        results += hashed_perft(game, depth - 1, table);
+        game.unmake_move();
+    }
+
+    table.store(hash, depth, results);
+    results
+}
+
+/// Counts the moves at the leaves of the tree, and the features (captures, etc.) they have,
+/// by inspecting the move objects without executing them on the board.
+fn count_moves(moves: &MoveList) -> PerftResults {
+    let mut results = PerftResults::new();
+    results.nodes = moves.len();
+
+    for m in moves {
+        if m.piece_captured().is_some() {
+            results.captures += 1;
+        }
+
+        if let Some(sp) = m.special_move() {
+            match sp {
+                SpecialMove::Castle(..) => results.castles += 1,
+                SpecialMove::EnPassant(_) => results.en_passant += 1,
+                SpecialMove::Promotion(_) => results.promotions += 1,
+                // Drops only exist in Crazyhouse, which is not counted here
+                SpecialMove::Drop => {}
+            }
+        }
     }
 
     results
@@ -0,0 +1,160 @@
+//! A bounded pool of worker threads for hashed Perft runs, balanced by work stealing.
+//!
+//! The tree is split into subtrees small enough to be walked by a single thread. Each
+//! worker keeps its own queue of subtrees: it takes work from the back of its queue, and
+//! when the queue is empty, steals from the front of the queue of another worker. Deep
+//! subtrees are split again by whichever worker picks them up, so a worker that finishes
+//! early always finds something left to steal, however lopsided the tree is.
+
+use std::{
+    collections::VecDeque,
+    sync::{
+        Mutex, MutexGuard, PoisonError,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use bonsai_chess::prelude::*;
+
+use crate::{hash_table::PerftTable, perft::hashed_perft, perft_results::PerftResults};
+
+/// Subtrees deeper than this are split into the subtrees of their moves, instead of being
+/// walked by a single thread.
+///
+/// At this depth, subtrees hold thousands to millions of nodes: enough for the cost of
+/// queueing them to vanish, and few enough for the workers to stay balanced.
+const SPLIT_DEPTH: usize = 4;
+
+/// A subtree waiting to be walked: the position at its root, and the depth left.
+type Task = (Game, usize);
+
+/// Executes a hashed Perft run on a pool of `threads` workers (at least 1).
+///
+/// The workers share `table`, so a transposition found by one of them is not walked
+/// again by the others. Subtrees that are split between workers are looked up in the
+/// table, but not stored, as no single worker knows their total.
+pub fn pooled_perft(game: &Game, depth: usize, threads: usize, table: &PerftTable) -> PerftResults {
+    let queues: Vec<_> = (0..threads.max(1))
+        .map(|_| Mutex::new(VecDeque::new()))
+        .collect();
+    // The number of tasks queued or being walked: the run is over when it drops to 0
+    let pending = AtomicUsize::new(1);
+
+    lock(&queues[0]).push_back((game.clone(), depth));
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..queues.len())
+            .map(|id| {
+                let (queues, pending) = (&queues, &pending);
+                scope.spawn(move || work(id, queues, pending, table))
+            })
+            .collect();
+
+        let mut total_results = PerftResults::new();
+        for handle in handles {
+            if let Ok(results) = handle.join() {
+                total_results += results;
+            }
+        }
+
+        total_results
+    })
+}
+
+/// The loop of the worker `id`: walks or splits tasks until none are left.
+///
+/// Returns the sum of the results of the subtrees it walked.
+fn work(
+    id: usize,
+    queues: &[Mutex<VecDeque<Task>>],
+    pending: &AtomicUsize,
+    table: &PerftTable,
+) -> PerftResults {
+    let mut results = PerftResults::new();
+
+    loop {
+        let Some((mut game, depth)) = next_task(id, queues) else {
+            if pending.load(Ordering::Acquire) == 0 {
+                return results;
+            }
+            // Another worker is still splitting a subtree, which may give us work
+            std::thread::yield_now();
+            continue;
+        };
+
+        if depth <= SPLIT_DEPTH {
+            results += hashed_perft(&mut game, depth, table);
+        } else if let Some(hit) = table.probe(game.hash(), depth) {
+            results += hit;
+        } else {
+            let mut moves = MoveList::new();
+            game.generate_moves_into(&mut moves);
+
+            // The children are counted before their parent is done, so that `pending`
+            // cannot drop to 0 while there is work left
+            pending.fetch_add(moves.len(), Ordering::AcqRel);
+            let mut queue = lock(&queues[id]);
+            for m in &moves {
+                let mut child = game.clone();
+                child.make_move_unchecked(m);
+                queue.push_back((child, depth - 1));
+            }
+        }
+
+        pending.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Takes the most recent task of the worker `id`, or else steals the oldest task of
+/// another worker, which is the root of the largest subtree it has left.
+fn next_task(id: usize, queues: &[Mutex<VecDeque<Task>>]) -> Option<Task> {
+    let own_task = lock(&queues[id]).pop_back();
+
+    own_task.or_else(|| {
+        (1..queues.len())
+            .map(|offset| (id + offset) % queues.len())
+            .find_map(|victim| lock(&queues[victim]).pop_front())
+    })
+}
+
+/// Locks a queue, even if a worker panicked while holding it: the queue itself is never
+/// left half-updated, and dropping its tasks would leave the other workers waiting forever.
+fn lock(queue: &Mutex<VecDeque<Task>>) -> MutexGuard<'_, VecDeque<Task>> {
+    queue.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use bonsai_chess::prelude::*;
+
+    use super::pooled_perft;
+    use crate::{hash_table::PerftTable, perft::perft};
+
+    #[test]
+    fn test_pooled_perft_matches_perft() {
+        // Kiwipete: castling, promotions and en passant all happen within a few plies
+        let mut game = Game::try_from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        )
+        .unwrap();
+
+        // A tiny table, so that results are replaced all the time
+        let table = PerftTable::new(1);
+        for depth in 0..=3 {
+            let expected = perft(&mut game, depth);
+            assert_eq!(pooled_perft(&game, depth, 3, &table), expected);
+            // The second run is mostly answered by the table
+            assert_eq!(pooled_perft(&game, depth, 3, &table), expected);
+        }
+    }
+
+    #[test]
+    fn test_pooled_perft_splits_deep_subtrees() {
+        // Underpromotions on both sides, deep enough for the root to be split
+        let game = Game::try_from_fen("n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1").unwrap();
+
+        let table = PerftTable::new(16);
+        assert_eq!(pooled_perft(&game, 5, 4, &table).nodes, 3_605_103);
+        assert_eq!(pooled_perft(&game, 5, 1, &table).nodes, 3_605_103);
+    }
+}
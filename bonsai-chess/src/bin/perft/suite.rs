@@ -0,0 +1,148 @@
+//! Verification of positions against their known node counts.
+//!
+//! Suites are read from the built-in tables of [`crate::expected`], or from files in the
+//! format of the usual `perftsuite.epd`: one position per line, followed by its expected
+//! node counts, such as `fen;D1 20;D2 400`.
+
+use std::time::Instant;
+
+use bonsai_chess::prelude::*;
+
+use crate::perft::root_level_perft;
+
+/// A position, and the expected number of leaf nodes at some depths.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SuiteEntry {
+    /// The position, in FEN (or Shredder-FEN, for Chess960 positions).
+    pub fen: String,
+
+    /// The expected node counts, as `(depth, nodes)` pairs.
+    pub expected: Vec<(usize, usize)>,
+}
+
+impl SuiteEntry {
+    /// Creates an entry from node counts listed from depth 1.
+    pub fn from_counts(fen: &str, counts: &[usize]) -> Self {
+        Self {
+            fen: fen.to_string(),
+            expected: (1..).zip(counts.iter().copied()).collect(),
+        }
+    }
+
+    /// Reads a line such as `fen;D1 20;D2 400`.
+    ///
+    /// Spaces around the `;` are allowed, and the depths may come in any order.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing the first malformed count.
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut fields = line.split(';');
+        let fen = fields.next().unwrap_or_default().trim().to_string();
+
+        let expected = fields
+            .map(str::trim)
+            .filter(|field| !field.is_empty())
+            .map(|field| {
+                let (depth, nodes) = field
+                    .strip_prefix('D')
+                    .and_then(|count| count.split_once(char::is_whitespace))
+                    .ok_or_else(|| format!("`{field}` is not a `D<depth> <nodes>` count"))?;
+
+                let depth = depth
+                    .parse()
+                    .map_err(|_| format!("invalid depth in `{field}`"))?;
+                let nodes = nodes
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("invalid node count in `{field}`"))?;
+
+                Ok((depth, nodes))
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Self { fen, expected })
+    }
+
+    /// Walks the position to each depth, printing the node counts as they are found.
+    ///
+    /// Stops at the first depth whose count is wrong.
+    ///
+    /// # Returns
+    ///
+    /// `true` if every count matched.
+    pub fn verify(&self) -> bool {
+        let mut game = match Game::try_from_fen(&self.fen) {
+            Ok(game) => game,
+            Err(error) => {
+                println!("Invalid FEN: {error}");
+                return false;
+            }
+        };
+
+        for &(depth, expected_nodes) in &self.expected {
+            let start = Instant::now();
+            let result = root_level_perft(&mut game, depth);
+            let end = start.elapsed();
+
+            println!(
+                "Depth {depth}: {} nodes ({} seconds)",
+                result.nodes,
+                end.as_secs_f64()
+            );
+
+            if result.nodes != expected_nodes {
+                println!("MISMATCH: expected {expected_nodes} nodes");
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Verifies every entry of a suite, printing a summary at the end.
+///
+/// Each entry is printed with its name, or as a `Position` if it has none.
+///
+/// # Returns
+///
+/// `true` if every entry matched.
+pub fn verify_suite<'a>(entries: impl IntoIterator<Item = (Option<&'a str>, SuiteEntry)>) -> bool {
+    let mut failed = Vec::new();
+    let mut total = 0;
+
+    for (name, entry) in entries {
+        total += 1;
+        println!("--- {}: {} ---", name.unwrap_or("Position"), entry.fen);
+
+        if !entry.verify() {
+            failed.push(entry.fen);
+        }
+        println!();
+    }
+
+    println!("{} of {total} positions passed", total - failed.len());
+    for fen in &failed {
+        println!("FAILED: {fen}");
+    }
+
+    failed.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SuiteEntry;
+
+    #[test]
+    fn test_parse() {
+        let entry =
+            SuiteEntry::parse("4k3/8/8/8/8/8/8/4K2R w K - 0 1 ;D1 15 ;D2 66;D3 1197").unwrap();
+
+        assert_eq!(entry.fen, "4k3/8/8/8/8/8/8/4K2R w K - 0 1");
+        assert_eq!(entry.expected, [(1, 15), (2, 66), (3, 1197)]);
+
+        assert!(SuiteEntry::parse("4k3/8/8/8/8/8/8/4K2R w K - 0 1;D1").is_err());
+        assert!(SuiteEntry::parse("4k3/8/8/8/8/8/8/4K2R w K - 0 1;X1 15").is_err());
+    }
+}
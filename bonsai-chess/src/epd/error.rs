@@ -0,0 +1,79 @@
+//! # EPD Errors
+//!
+//! Every error found while reading EPD carries the line it was found on, counted
+//! from the start of the input.
+
+use crate::state::{FenParsingError, SanError};
+
+/// An error found while reading EPD, and the line it was found on.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EpdError {
+    line: usize,
+    kind: EpdErrorKind,
+}
+
+/// The reasons why EPD can fail to be read.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EpdErrorKind {
+    /// The underlying reader failed, with the message of its error.
+    Io(String),
+    /// The four position fields do not describe a valid position.
+    InvalidPosition(FenParsingError),
+    /// An opcode that does not start with a letter, or holds other characters than
+    /// letters, digits and underscores.
+    InvalidOpcode(String),
+    /// A quoted operand is not closed before the end of the line.
+    UnterminatedString,
+    /// An operand that does not fit its opcode, such as a non-numeric `acd`.
+    InvalidOperand {
+        /// The opcode of the operation.
+        opcode: String,
+        /// The operand that could not be read.
+        operand: String,
+    },
+    /// A move of a `bm`, `am` or `pv` operation that cannot be played in the position.
+    InvalidMove(SanError),
+}
+
+impl EpdError {
+    /// Creates an error found on `line`.
+    #[must_use]
+    pub const fn new(line: usize, kind: EpdErrorKind) -> Self {
+        Self { line, kind }
+    }
+
+    /// Returns the line the error was found on, starting at 1.
+    #[must_use]
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the reason of the error.
+    #[must_use]
+    pub const fn kind(&self) -> &EpdErrorKind {
+        &self.kind
+    }
+}
+
+impl std::fmt::Display for EpdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl std::fmt::Display for EpdErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "I/O error: {error}"),
+            Self::InvalidPosition(error) => write!(f, "Invalid position: {error}"),
+            Self::InvalidOpcode(opcode) => write!(f, "Invalid opcode {opcode}"),
+            Self::UnterminatedString => write!(f, "Unterminated string"),
+            Self::InvalidOperand { opcode, operand } => {
+                write!(f, "Invalid operand {operand} for opcode {opcode}")
+            }
+            Self::InvalidMove(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for EpdError {}
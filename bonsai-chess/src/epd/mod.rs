@@ -0,0 +1,31 @@
+//! # Extended Position Description
+//!
+//! This module reads and writes positions in Extended Position Description (EPD),
+//! the format engine test suites (WAC, STS, Bratko-Kopec, ...) are distributed in.
+//!
+//! An EPD record is the first four fields of a FEN followed by operations, such as
+//! `bm Qg6;` (the best move) or `id "WAC.001";` (the name of the position). Moves
+//! are written in SAN, and are resolved against the position when it is read.
+//!
+//! Files holding many records are read lazily with an [`EpdReader`], and written
+//! with an [`EpdWriter`].
+
+/// The errors that can occur while reading EPD.
+mod error;
+
+/// Reads a single EPD record.
+mod parser;
+
+/// Iterates over the records of an EPD file.
+mod reader;
+
+/// The data of an EPD record: a position and its operations.
+mod record;
+
+/// Writes EPD records.
+mod writer;
+
+pub use error::{EpdError, EpdErrorKind};
+pub use reader::EpdReader;
+pub use record::Epd;
+pub use writer::EpdWriter;
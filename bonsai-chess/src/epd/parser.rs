@@ -0,0 +1,223 @@
+//! # EPD Parser
+//!
+//! Reads a single EPD line: the four position fields, then the operations, each
+//! made of an opcode, its operands and a closing `;`. Operands are either bare
+//! words or strings in double quotes, which may contain spaces and semicolons.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    epd::{Epd, EpdError, EpdErrorKind},
+    state::Game,
+};
+
+/// The opcodes whose single operand is an integer.
+const NUMERIC_OPCODES: [&str; 5] = ["acd", "ce", "dm", "fmvn", "hmvc"];
+
+impl Epd {
+    /// Reads a single line of Extended Position Description.
+    ///
+    /// The moves of the `bm`, `am` and `pv` operations are resolved through SAN, and the
+    /// operands of `acd`, `ce`, `dm`, `hmvc` and `fmvn` must be integers. Other opcodes
+    /// are kept as they are, whatever their operands.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`EpdError`] on line 1 if the position is invalid, an operation is
+    /// malformed, or one of the moves cannot be played.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// let epd = Epd::parse(
+    ///     "6k1/5ppp/8/8/8/8/8/R5K1 w - - dm 1; c0 \"Back rank; Ra8#\"; acd 4;",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(epd.direct_mate(), Some(1));
+    /// assert_eq!(epd.comment(0), Some("Back rank; Ra8#"));
+    /// assert_eq!(epd.analysis_depth(), Some(4));
+    ///
+    /// let error = Epd::parse("6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Rb8;").unwrap_err();
+    /// assert!(matches!(error.kind(), EpdErrorKind::InvalidMove(_)));
+    /// ```
+    pub fn parse(epd: &str) -> Result<Self, EpdError> {
+        Self::parse_from_line(epd, 1)
+    }
+
+    /// Reads a single EPD record found on line `line` of the input it comes from.
+    pub(super) fn parse_from_line(epd: &str, line: usize) -> Result<Self, EpdError> {
+        let error = |kind| EpdError::new(line, kind);
+
+        let mut rest = epd.trim();
+        let mut position = Vec::with_capacity(4);
+        for _ in 0..4 {
+            let (field, tail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            position.push(field);
+            rest = tail.trim_start();
+        }
+
+        let operations = parse_operations(rest).map_err(error)?;
+        for opcode in NUMERIC_OPCODES {
+            let Some(operands) = operations.get(opcode) else {
+                continue;
+            };
+            if operands.len() != 1 || operands[0].parse::<i64>().is_err() {
+                return Err(error(EpdErrorKind::InvalidOperand {
+                    opcode: opcode.to_string(),
+                    operand: operands.join(" "),
+                }));
+            }
+        }
+
+        // EPD has no clocks, unless they are given as operations
+        let clock = |opcode: &str, default: &'static str| {
+            operations
+                .get(opcode)
+                .and_then(|operands| operands.first())
+                .map_or(default, String::as_str)
+        };
+        let fen = format!(
+            "{} {} {}",
+            position.join(" "),
+            clock("hmvc", "0"),
+            clock("fmvn", "1")
+        );
+        let position = Game::try_from_fen(&fen)
+            .map_err(|fen_error| error(EpdErrorKind::InvalidPosition(fen_error)))?;
+
+        let epd = Self {
+            position,
+            operations,
+        };
+        let moves = [
+            epd.best_moves(),
+            epd.avoid_moves(),
+            epd.principal_variation(),
+        ];
+        if let Some(Err(san_error)) = moves.into_iter().find(Result::is_err) {
+            return Err(error(EpdErrorKind::InvalidMove(san_error)));
+        }
+
+        Ok(epd)
+    }
+}
+
+/// Reads the operations following the position fields, by opcode.
+///
+/// The last operation of the line may leave out its `;`.
+fn parse_operations(text: &str) -> Result<BTreeMap<String, Vec<String>>, EpdErrorKind> {
+    let mut operations = BTreeMap::new();
+    let mut chars = text.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace() || *c == ';').is_some() {}
+        let Some(&first) = chars.peek() else {
+            return Ok(operations);
+        };
+
+        let mut opcode = String::new();
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != ';') {
+            opcode.push(c);
+        }
+        let is_valid = first.is_ascii_alphabetic()
+            && opcode
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_valid {
+            return Err(EpdErrorKind::InvalidOpcode(opcode));
+        }
+
+        let mut operands = Vec::new();
+        loop {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            match chars.next() {
+                None | Some(';') => break,
+                Some('"') => operands.push(read_string(&mut chars)?),
+                Some(c) => {
+                    let mut operand = String::from(c);
+                    while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != ';') {
+                        operand.push(c);
+                    }
+                    operands.push(operand);
+                }
+            }
+        }
+
+        operations.insert(opcode, operands);
+    }
+}
+
+/// Reads a quoted operand, whose opening quote was already read.
+///
+/// As in PGN strings, a backslash escapes a quote or another backslash.
+fn read_string(chars: &mut impl Iterator<Item = char>) -> Result<String, EpdErrorKind> {
+    let mut string = String::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Ok(string),
+            '\\' => string.push(chars.next().ok_or(EpdErrorKind::UnterminatedString)?),
+            _ => string.push(c),
+        }
+    }
+
+    Err(EpdErrorKind::UnterminatedString)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::epd::{Epd, EpdErrorKind};
+
+    #[test]
+    fn test_operations() {
+        let epd = Epd::parse(
+            "r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - \
+             bm Qxf7#; am Qxe5+ Qh3;  id \"Scholar's \\\"mate\\\"\" ; acd 3;ce 32767; \
+             pv Qxf7#; hmvc 4; fmvn 4; xyz_1 a b c",
+        )
+        .unwrap();
+
+        assert_eq!(epd.best_moves().unwrap()[0].to_uci(), "h5f7");
+        assert_eq!(epd.avoid_moves().unwrap().len(), 2);
+        assert_eq!(epd.id(), Some("Scholar's \"mate\""));
+        assert_eq!(epd.analysis_depth(), Some(3));
+        assert_eq!(epd.centipawn_evaluation(), Some(32767));
+        assert_eq!(epd.principal_variation().unwrap().len(), 1);
+        assert_eq!(epd.operation("xyz_1").unwrap(), ["a", "b", "c"]);
+        assert_eq!(epd.position().move_counter().fullmove(), 4);
+    }
+
+    #[test]
+    fn test_malformed_records() {
+        let position = "4k3/8/8/8/8/8/8/4K2R w K -";
+        let kind = |operations: &str| {
+            Epd::parse(&format!("{position} {operations}"))
+                .unwrap_err()
+                .kind()
+                .clone()
+        };
+
+        assert_eq!(
+            kind("1bm Rh8;"),
+            EpdErrorKind::InvalidOpcode(String::from("1bm"))
+        );
+        assert_eq!(kind("id \"open;"), EpdErrorKind::UnterminatedString);
+        assert_eq!(
+            kind("acd deep;"),
+            EpdErrorKind::InvalidOperand {
+                opcode: String::from("acd"),
+                operand: String::from("deep"),
+            }
+        );
+        assert!(matches!(
+            kind("pv Rh8+ Kd8 Rh8;"),
+            EpdErrorKind::InvalidMove(_)
+        ));
+        assert!(matches!(
+            Epd::parse("4k3/8/8/8/8/8/8/4K2R w").unwrap_err().kind(),
+            EpdErrorKind::InvalidPosition(_)
+        ));
+    }
+}
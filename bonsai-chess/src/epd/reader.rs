@@ -0,0 +1,84 @@
+//! # Streaming EPD Reader
+//!
+//! Reads the records of an EPD file one line at a time from any [`BufRead`] source,
+//! so that test suites of any size can be processed without loading them whole.
+
+use std::io::BufRead;
+
+use crate::epd::{Epd, EpdError, EpdErrorKind};
+
+/// An iterator over the records of an EPD file.
+///
+/// Each non-blank line is a record, and each item is either that record or the error
+/// that made it unreadable. Errors in a record do not stop the iteration: skipping an
+/// `Err` moves on to the next line. Only an I/O error of the underlying reader ends it,
+/// after being yielded once.
+///
+/// # Examples
+///
+/// ```rust
+/// use bonsai_chess::prelude::*;
+///
+/// let suite = "6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Ra8#; id \"mate\";\n\
+///              \n\
+///              6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Rb8; id \"broken\";\n\
+///              4k3/8/8/8/8/8/8/4K3 b - - id \"bare kings\";\n";
+///
+/// let records: Vec<_> = EpdReader::new(suite.as_bytes()).collect();
+/// assert_eq!(records.len(), 3);
+/// assert_eq!(records[0].as_ref().unwrap().id(), Some("mate"));
+/// assert_eq!(records[1].as_ref().unwrap_err().line(), 3);
+/// assert_eq!(records[2].as_ref().unwrap().position().turn(), Side::Black);
+/// ```
+#[derive(Debug)]
+pub struct EpdReader<R> {
+    reader: R,
+    /// The number of lines read so far.
+    line: usize,
+    /// Set after an I/O error, which ends the iteration.
+    failed: bool,
+}
+
+impl<R: BufRead> EpdReader<R> {
+    /// Creates a reader over the records of `reader`.
+    #[must_use]
+    pub const fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: 0,
+            failed: false,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for EpdReader<R> {
+    type Item = Result<Epd, EpdError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(_) => self.line += 1,
+                Err(error) => {
+                    self.failed = true;
+                    return Some(Err(EpdError::new(
+                        self.line + 1,
+                        EpdErrorKind::Io(error.to_string()),
+                    )));
+                }
+            }
+
+            // Byte order marks are common at the start of files saved on Windows
+            let record = line.trim_start_matches('\u{feff}').trim();
+            if !record.is_empty() {
+                return Some(Epd::parse_from_line(record, self.line));
+            }
+        }
+    }
+}
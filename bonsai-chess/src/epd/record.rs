@@ -0,0 +1,198 @@
+//! # EPD Record
+//!
+//! The data of a single EPD line: a position, and the operations that describe it
+//! (the best move of a test suite, the evaluation found by an engine, comments, ...).
+
+use std::collections::BTreeMap;
+
+use crate::{moves::Ply, state::Game, state::SanError};
+
+/// A position in Extended Position Description (EPD), with its operations.
+///
+/// The position holds the four first fields of a FEN: the piece placement, the side
+/// to move, the castling rights and the en passant target. The clocks, which EPD
+/// leaves out, are read from the `hmvc` and `fmvn` operations when they are present.
+///
+/// Each operation is an opcode followed by any number of operands. Operations are kept
+/// sorted by opcode, which is the order the PGN standard writes them in, and the
+/// common ones have dedicated accessors:
+/// * **`bm`** / **`am`**: The best moves, or the moves to avoid, in SAN.
+/// * **`id`**: The name of the position in its test suite.
+/// * **`c0`** to **`c9`**: Comments.
+/// * **`acd`**: The depth of the analysis, in plies.
+/// * **`ce`**: The evaluation found by the analysis, in centipawns.
+/// * **`pv`**: The principal variation found by the analysis, in SAN.
+/// * **`dm`**: The number of moves to a forced mate.
+///
+/// # Examples
+///
+/// ```rust
+/// use bonsai_chess::prelude::*;
+///
+/// let epd = Epd::parse(
+///     "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id \"WAC.001\";",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(epd.id(), Some("WAC.001"));
+/// let best_moves = epd.best_moves().unwrap();
+/// assert_eq!(best_moves.len(), 1);
+/// assert_eq!(best_moves[0].to_uci(), "g3g6");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Epd {
+    pub(crate) position: Game,
+    pub(crate) operations: BTreeMap<String, Vec<String>>,
+}
+
+impl Epd {
+    /// Creates a record of the current position of `game`, without operations.
+    ///
+    /// The clocks of the game are not kept: add them as `hmvc` and `fmvn` operations
+    /// with [`set_operation`](Self::set_operation) if they matter. Neither are the moves
+    /// that led to the position, so the record is the same as the one read from its EPD.
+    #[must_use]
+    pub fn from_game(game: &Game) -> Self {
+        // A game always writes a valid FEN, so reading its fields back cannot fail
+        let position = Game::from_fen(&format!("{} 0 1", position_fields(game)));
+
+        Self {
+            position,
+            operations: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the position, as a game with no move played.
+    #[must_use]
+    pub const fn position(&self) -> &Game {
+        &self.position
+    }
+
+    /// Returns the four position fields, as they are written in EPD.
+    #[must_use]
+    pub fn fen(&self) -> String {
+        position_fields(&self.position)
+    }
+
+    /// Returns the operands of the operation `opcode`, or `None` if the record does not
+    /// have it.
+    #[must_use]
+    pub fn operation(&self, opcode: &str) -> Option<&[String]> {
+        self.operations.get(opcode).map(Vec::as_slice)
+    }
+
+    /// Returns every operation of the record, sorted by opcode.
+    pub fn operations(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.operations
+            .iter()
+            .map(|(opcode, operands)| (opcode.as_str(), operands.as_slice()))
+    }
+
+    /// Sets the operands of the operation `opcode`, replacing the previous ones if any.
+    ///
+    /// The operands are stored as given: moves must be in SAN, and numbers in decimal.
+    pub fn set_operation(&mut self, opcode: &str, operands: Vec<String>) {
+        self.operations.insert(opcode.to_string(), operands);
+    }
+
+    /// Removes the operation `opcode`, and returns its operands.
+    pub fn remove_operation(&mut self, opcode: &str) -> Option<Vec<String>> {
+        self.operations.remove(opcode)
+    }
+
+    /// Returns the best moves of the position (`bm`), or an empty list if there are none.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`SanError`] of the first move that cannot be played in the position,
+    /// which can only happen if the operation was changed after the record was read.
+    pub fn best_moves(&self) -> Result<Vec<Ply>, SanError> {
+        self.moves("bm")
+    }
+
+    /// Returns the moves to avoid in the position (`am`), or an empty list if there are none.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`SanError`] of the first move that cannot be played in the position.
+    pub fn avoid_moves(&self) -> Result<Vec<Ply>, SanError> {
+        self.moves("am")
+    }
+
+    /// Returns the principal variation (`pv`): moves played one after the other from the
+    /// position, or an empty list if there is none.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`SanError`] of the first move that cannot be played where it stands
+    /// in the variation.
+    pub fn principal_variation(&self) -> Result<Vec<Ply>, SanError> {
+        let mut game = self.position.clone();
+        self.operation("pv")
+            .unwrap_or_default()
+            .iter()
+            .map(|san| {
+                let ply = game.parse_san(san)?;
+                game.make_move(&ply);
+                Ok(ply)
+            })
+            .collect()
+    }
+
+    /// Sets the best moves of the position (`bm`), written in SAN.
+    pub fn set_best_moves(&mut self, plies: &[Ply]) {
+        let sans = plies.iter().map(|ply| self.position.to_san(ply)).collect();
+        self.set_operation("bm", sans);
+    }
+
+    /// Returns the name of the position (`id`).
+    #[must_use]
+    pub fn id(&self) -> Option<&str> {
+        self.single_operand("id")
+    }
+
+    /// Returns the comment number `index` (`c0` to `c9`).
+    #[must_use]
+    pub fn comment(&self, index: usize) -> Option<&str> {
+        self.single_operand(&format!("c{index}"))
+    }
+
+    /// Returns the depth of the analysis in plies (`acd`).
+    #[must_use]
+    pub fn analysis_depth(&self) -> Option<u32> {
+        self.single_operand("acd")?.parse().ok()
+    }
+
+    /// Returns the evaluation found by the analysis in centipawns, from the point of view
+    /// of the side to move (`ce`).
+    #[must_use]
+    pub fn centipawn_evaluation(&self) -> Option<i32> {
+        self.single_operand("ce")?.parse().ok()
+    }
+
+    /// Returns the number of moves to a forced mate (`dm`).
+    #[must_use]
+    pub fn direct_mate(&self) -> Option<u32> {
+        self.single_operand("dm")?.parse().ok()
+    }
+
+    /// Returns the first operand of the operation `opcode`.
+    fn single_operand(&self, opcode: &str) -> Option<&str> {
+        self.operation(opcode)?.first().map(String::as_str)
+    }
+
+    /// Resolves every operand of the operation `opcode` as a move of the position.
+    fn moves(&self, opcode: &str) -> Result<Vec<Ply>, SanError> {
+        self.operation(opcode)
+            .unwrap_or_default()
+            .iter()
+            .map(|san| self.position.parse_san(san))
+            .collect()
+    }
+}
+
+/// Returns the four first fields of the FEN of `game`, which are the position fields of EPD.
+fn position_fields(game: &Game) -> String {
+    let fen = game.to_fen();
+    fen.split(' ').take(4).collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,145 @@
+//! # EPD Writer
+//!
+//! Writes positions in Extended Position Description, one record per line: the
+//! four position fields, then every operation followed by a `;`, sorted by opcode.
+
+use std::io::Write;
+
+use crate::epd::Epd;
+
+/// The opcodes whose operands are strings, which are always quoted.
+const STRING_OPCODES: [&str; 11] = [
+    "id", "c0", "c1", "c2", "c3", "c4", "c5", "c6", "c7", "c8", "c9",
+];
+
+/// Writes records one per line to a [`Write`] sink, such as a file opened for appending.
+///
+/// The output can be read back by an [`EpdReader`](crate::epd::EpdReader).
+///
+/// # Examples
+///
+/// ```rust
+/// use bonsai_chess::prelude::*;
+///
+/// let mut epd = Epd::from_game(&Game::from_starting_position());
+/// epd.set_operation("id", vec![String::from("start")]);
+///
+/// let mut writer = EpdWriter::new(Vec::new());
+/// writer.write_epd(&epd).unwrap();
+///
+/// let text = String::from_utf8(writer.into_inner()).unwrap();
+/// assert_eq!(
+///     text,
+///     "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - id \"start\";\n"
+/// );
+/// ```
+#[derive(Debug)]
+pub struct EpdWriter<W> {
+    writer: W,
+}
+
+impl<W: Write> EpdWriter<W> {
+    /// Creates a writer that appends records to `writer`.
+    #[must_use]
+    pub const fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Writes a record, followed by a newline.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error of the underlying writer.
+    pub fn write_epd(&mut self, epd: &Epd) -> std::io::Result<()> {
+        writeln!(self.writer, "{epd}")
+    }
+
+    /// Flushes the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error of the underlying writer.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Formats the record as a single EPD line, without a trailing newline.
+///
+/// Operands are quoted when they are strings (`id`, `c0` to `c9`), or when they could
+/// not be read back otherwise.
+impl std::fmt::Display for Epd {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.fen())?;
+
+        for (opcode, operands) in self.operations() {
+            write!(f, " {opcode}")?;
+            for operand in operands {
+                let needs_quotes = STRING_OPCODES.contains(&opcode)
+                    || operand.is_empty()
+                    || operand.contains(|c: char| c.is_whitespace() || matches!(c, ';' | '"'));
+
+                if needs_quotes {
+                    let escaped = operand.replace('\\', "\\\\").replace('"', "\\\"");
+                    write!(f, " \"{escaped}\"")?;
+                } else {
+                    write!(f, " {operand}")?;
+                }
+            }
+            write!(f, ";")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{epd::Epd, state::Game};
+
+    #[test]
+    fn test_round_trip() {
+        let lines = [
+            "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id \"WAC.001\";",
+            "r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - acd 3; \
+             am Nc3 Qh3; bm Qxf7#; c0 \"a \\\"quoted\\\" word; and more\"; ce 32767; \
+             fmvn 4; hmvc 4; pv Qxf7#;",
+            "4k3/8/8/3pP3/8/8/8/4K3 w - d6",
+        ];
+
+        for line in lines {
+            let epd = Epd::parse(line).unwrap();
+            assert_eq!(epd.to_string(), line);
+            assert_eq!(Epd::parse(&epd.to_string()), Ok(epd));
+        }
+    }
+
+    #[test]
+    fn test_records_of_played_games_round_trip() {
+        let mut game = Game::from_starting_position();
+        for san in ["e4", "c5", "Nf3"] {
+            let ply = game.parse_san(san).unwrap();
+            game.make_move(&ply);
+        }
+
+        let epd = Epd::from_game(&game);
+        assert!(epd.position().get_move_log().is_empty());
+        assert_eq!(epd.position().move_counter().fullmove(), 1);
+        assert_eq!(Epd::parse(&epd.to_string()), Ok(epd));
+    }
+
+    #[test]
+    fn test_best_moves_are_written_in_san() {
+        let mut epd = Epd::parse("6k1/5ppp/8/8/8/8/8/R5K1 w - -").unwrap();
+        let mate = epd.position().parse_san("Ra8").unwrap();
+        epd.set_best_moves(&[mate]);
+
+        assert_eq!(epd.operation("bm").unwrap(), ["Ra8#"]);
+        assert_eq!(epd.best_moves(), Ok(vec![mate]));
+    }
+}
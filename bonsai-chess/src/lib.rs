@@ -8,7 +8,15 @@
 //!
 //! * **State Representation**: Storing the board, pieces, and specific locations.
 //! * **Game Rules**: Enforcing the laws of chess, including turn cycles, castling rights, and checkmate detection.
+//! * **Variants**: Playing by other rules, such as King of the Hill, Three-Check, Crazyhouse or Racing Kings.
 //! * **Move Generation**: Calculating legal moves for a given position.
+//! * **Notation**: Reading and writing moves (SAN, UCI), games (PGN) and positions (FEN, EPD).
+//!
+//! ## Features
+//!
+//! * **`serde`**: Implements `Serialize` and `Deserialize` for the public types. Squares
+//!   are written in algebraic notation, moves in UCI notation, and games as their
+//!   starting FEN followed by their moves, which are checked again when read.
 //!
 //! ## Usage
 //!
@@ -37,6 +45,15 @@ mod pieces;
 /// Defines game outcomes (Checkmate, Draw, Win).
 mod rules;
 
+/// Reads and writes games in Portable Game Notation (PGN).
+mod pgn;
+
+/// Reads and writes positions in Extended Position Description (EPD), the format of test suites.
+mod epd;
+
+/// Stores games with all their variations as a tree of moves.
+mod tree;
+
 /// The number of rows (ranks) on a standard chess board.
 pub const BOARD_ROWS: usize = 8;
 
@@ -65,9 +82,12 @@ pub const BOARD_COLUMNS_RANGE: std::ops::Range<usize> = 0..BOARD_COLUMNS;
 /// ```
 pub mod prelude {
     pub use crate::atoms::*;
+    pub use crate::epd::*;
     pub use crate::moves::*;
+    pub use crate::pgn::*;
     pub use crate::pieces::*;
     pub use crate::rules::*;
     pub use crate::state::*;
+    pub use crate::tree::*;
     pub use crate::{BOARD_COLUMNS, BOARD_COLUMNS_RANGE, BOARD_ROWS, BOARD_ROWS_RANGE};
 }
@@ -0,0 +1,259 @@
+//! # Attack Tables
+//!
+//! This module precomputes, at compile time, the squares attacked by every piece
+//! type from every square of the board, and exposes fast lookups built on them.
+//!
+//! * **Leapers** (Knights, Kings, Pawns) have fixed attack patterns, so a single
+//!   table lookup returns all of their targets.
+//! * **Sliders** (Bishops, Rooks, Queens) use the "classical" approach: for each of
+//!   the 8 directions, a precomputed ray is cut short at the first blocker found in
+//!   the occupancy. This needs no magic numbers nor PEXT instructions.
+//!
+//! The tables also provide the squares [`between`] two aligned squares and the full
+//! [`line`] through them, which the legality checks use to resolve pins and checks.
+
+use crate::{
+    atoms::{Bitboard, Coordinate, Side},
+    moves::directions::{
+        DIAGONALLY_DOWN_LEFT, DIAGONALLY_DOWN_RIGHT, DIAGONALLY_UP_LEFT, DIAGONALLY_UP_RIGHT, DOWN,
+        KING_DIRECTIONS, KNIGHT_DIRECTIONS, LEFT, RIGHT, UP,
+    },
+};
+
+/// Number of squares on the board.
+const SQUARES: usize = 64;
+
+/// The 8 ray directions, orthogonal ones first. The order defines the ray table indices.
+///
+/// Opposite directions sit next to each other, so flipping the lowest bit of an
+/// index (`direction ^ 1`) yields the index of the opposite direction.
+const RAY_DIRECTIONS: [(isize, isize); 8] = [
+    UP,
+    DOWN,
+    LEFT,
+    RIGHT,
+    DIAGONALLY_UP_LEFT,
+    DIAGONALLY_DOWN_RIGHT,
+    DIAGONALLY_UP_RIGHT,
+    DIAGONALLY_DOWN_LEFT,
+];
+
+/// Indices into [`RAY_DIRECTIONS`] of the directions moving along ranks and files.
+const ORTHOGONAL_RAYS: [usize; 4] = [0, 1, 2, 3];
+
+/// Indices into [`RAY_DIRECTIONS`] of the diagonal directions.
+const DIAGONAL_RAYS: [usize; 4] = [4, 5, 6, 7];
+
+/// Squares attacked by a Knight standing on each square.
+static KNIGHT_ATTACKS: [Bitboard; SQUARES] = leaper_attacks(&KNIGHT_DIRECTIONS);
+
+/// Squares attacked by a King standing on each square.
+static KING_ATTACKS: [Bitboard; SQUARES] = leaper_attacks(&KING_DIRECTIONS);
+
+/// Squares attacked by a Pawn standing on each square, indexed by [`Side::index`].
+static PAWN_ATTACKS: [[Bitboard; SQUARES]; 2] = [
+    leaper_attacks(&[DIAGONALLY_UP_LEFT, DIAGONALLY_UP_RIGHT]),
+    leaper_attacks(&[DIAGONALLY_DOWN_LEFT, DIAGONALLY_DOWN_RIGHT]),
+];
+
+/// The squares reached from each square in each direction on an empty board.
+static RAYS: [[Bitboard; SQUARES]; 8] = ray_table();
+
+/// The squares strictly between two aligned squares.
+static BETWEEN: [[Bitboard; SQUARES]; SQUARES] = between_table();
+
+/// The full line (rank, file or diagonal) through two aligned squares.
+static LINE: [[Bitboard; SQUARES]; SQUARES] = line_table();
+
+/// Returns `true` if moving along `direction` increases the square index.
+const fn is_increasing(direction: (isize, isize)) -> bool {
+    direction.0 * 8 + direction.1 > 0
+}
+
+/// Returns the square index reached from `square` after `steps` steps, if still on the board.
+const fn offset(square: usize, direction: (isize, isize), steps: isize) -> Option<usize> {
+    let row = (square / 8).cast_signed() + direction.0 * steps;
+    let column = (square % 8).cast_signed() + direction.1 * steps;
+
+    if row >= 0 && row < 8 && column >= 0 && column < 8 {
+        Some((row * 8 + column).cast_unsigned())
+    } else {
+        None
+    }
+}
+
+const fn leaper_attacks(directions: &[(isize, isize)]) -> [Bitboard; SQUARES] {
+    let mut table = [Bitboard::EMPTY; SQUARES];
+
+    let mut square = 0;
+    while square < SQUARES {
+        let mut i = 0;
+        while i < directions.len() {
+            if let Some(target) = offset(square, directions[i], 1) {
+                table[square].0 |= 1 << target;
+            }
+            i += 1;
+        }
+        square += 1;
+    }
+
+    table
+}
+
+const fn ray_table() -> [[Bitboard; SQUARES]; 8] {
+    let mut table = [[Bitboard::EMPTY; SQUARES]; 8];
+
+    let mut direction = 0;
+    while direction < RAY_DIRECTIONS.len() {
+        let mut square = 0;
+        while square < SQUARES {
+            let mut steps = 1;
+            while let Some(target) = offset(square, RAY_DIRECTIONS[direction], steps) {
+                table[direction][square].0 |= 1 << target;
+                steps += 1;
+            }
+            square += 1;
+        }
+        direction += 1;
+    }
+
+    table
+}
+
+// Only ever evaluated at compile time to initialize a static, never on the stack.
+#[allow(clippy::large_stack_arrays)]
+const fn between_table() -> [[Bitboard; SQUARES]; SQUARES] {
+    let mut table = [[Bitboard::EMPTY; SQUARES]; SQUARES];
+
+    let mut from = 0;
+    while from < SQUARES {
+        let mut direction = 0;
+        while direction < RAY_DIRECTIONS.len() {
+            let mut path = 0;
+            let mut steps = 1;
+            while let Some(to) = offset(from, RAY_DIRECTIONS[direction], steps) {
+                table[from][to].0 = path;
+                path |= 1 << to;
+                steps += 1;
+            }
+            direction += 1;
+        }
+        from += 1;
+    }
+
+    table
+}
+
+// Only ever evaluated at compile time to initialize a static, never on the stack.
+#[allow(clippy::large_stack_arrays)]
+const fn line_table() -> [[Bitboard; SQUARES]; SQUARES] {
+    let rays = ray_table();
+    let mut table = [[Bitboard::EMPTY; SQUARES]; SQUARES];
+
+    let mut from = 0;
+    while from < SQUARES {
+        let mut direction = 0;
+        while direction < RAY_DIRECTIONS.len() {
+            let opposite = direction ^ 1;
+            let full_line = rays[direction][from].0 | rays[opposite][from].0 | (1 << from);
+
+            let mut steps = 1;
+            while let Some(to) = offset(from, RAY_DIRECTIONS[direction], steps) {
+                table[from][to].0 = full_line;
+                steps += 1;
+            }
+            direction += 1;
+        }
+        from += 1;
+    }
+
+    table
+}
+
+/// Cuts the rays leaving `square` in the given directions at their first blocker.
+#[inline]
+fn slider_attacks(square: usize, occupied: Bitboard, directions: [usize; 4]) -> Bitboard {
+    let mut attacks = Bitboard::EMPTY;
+
+    for direction in directions {
+        let ray = RAYS[direction][square];
+        let blockers = ray & occupied;
+
+        if blockers.is_empty() {
+            attacks |= ray;
+            continue;
+        }
+
+        // The nearest blocker is the lowest bit on increasing rays, the highest on the others
+        let nearest = if is_increasing(RAY_DIRECTIONS[direction]) {
+            blockers.0.trailing_zeros() as usize
+        } else {
+            63 - blockers.0.leading_zeros() as usize
+        };
+
+        // Keep the blocker itself (it may be captured), drop everything behind it
+        attacks |= ray ^ RAYS[direction][nearest];
+    }
+
+    attacks
+}
+
+/// Returns the squares a Knight on `square` attacks.
+#[must_use]
+#[inline]
+pub fn knight_attacks(square: Coordinate) -> Bitboard {
+    KNIGHT_ATTACKS[square.index()]
+}
+
+/// Returns the squares a King on `square` attacks.
+#[must_use]
+#[inline]
+pub fn king_attacks(square: Coordinate) -> Bitboard {
+    KING_ATTACKS[square.index()]
+}
+
+/// Returns the squares a Pawn of `side` standing on `square` attacks (diagonally forward).
+#[must_use]
+#[inline]
+pub fn pawn_attacks(side: Side, square: Coordinate) -> Bitboard {
+    PAWN_ATTACKS[side.index()][square.index()]
+}
+
+/// Returns the squares a Bishop on `square` attacks, given the board `occupied` squares.
+#[must_use]
+#[inline]
+pub fn bishop_attacks(square: Coordinate, occupied: Bitboard) -> Bitboard {
+    slider_attacks(square.index(), occupied, DIAGONAL_RAYS)
+}
+
+/// Returns the squares a Rook on `square` attacks, given the board `occupied` squares.
+#[must_use]
+#[inline]
+pub fn rook_attacks(square: Coordinate, occupied: Bitboard) -> Bitboard {
+    slider_attacks(square.index(), occupied, ORTHOGONAL_RAYS)
+}
+
+/// Returns the squares a Queen on `square` attacks, given the board `occupied` squares.
+#[must_use]
+#[inline]
+pub fn queen_attacks(square: Coordinate, occupied: Bitboard) -> Bitboard {
+    bishop_attacks(square, occupied) | rook_attacks(square, occupied)
+}
+
+/// Returns the squares strictly between `from` and `to`.
+///
+/// The result is empty if both squares do not share a rank, file or diagonal.
+#[must_use]
+#[inline]
+pub fn between(from: Coordinate, to: Coordinate) -> Bitboard {
+    BETWEEN[from.index()][to.index()]
+}
+
+/// Returns the whole rank, file or diagonal going through `from` and `to`, edge to edge.
+///
+/// The result is empty if both squares do not share a rank, file or diagonal.
+#[must_use]
+#[inline]
+pub fn line(from: Coordinate, to: Coordinate) -> Bitboard {
+    LINE[from.index()][to.index()]
+}
@@ -7,7 +7,7 @@
 //! is permanently restricted to squares of the same color as its starting square.
 
 use crate::{
-    moves::{LegalityContext, Ply, directions, generator::sliding::slide},
+    moves::{LegalityContext, MoveList, attacks, generator::push_plies},
     pieces::LocatedPiece,
     state::Board,
 };
@@ -17,9 +17,8 @@ use crate::{
 /// The Bishop moves any number of squares along the diagonals, stopping at the first
 /// obstacle. It remains bound to squares of the color it started on.
 ///
-/// This function relies on the shared `slide` utility, passing it the
-/// 4 diagonal directions and a maximum slide distance of 7 squares. It respects
-/// all board boundaries, piece blocking, pins, and checks.
+/// This function looks up the diagonal attacks from the Bishop's square given the
+/// current occupancy, then keeps only the targets allowed by pins and checks.
 ///
 /// # Movement Logic
 /// * **Directions**: Four diagonals (Up-Left, Up-Right, Down-Left, Down-Right).
@@ -30,19 +29,17 @@ use crate::{
 /// * `what_to_move` - The Bishop being moved and its starting location.
 /// * `backend` - The board state used to check for occupancy and captures.
 /// * `context` - The pre-calculated legality constraints (pins, checks, and danger squares).
-/// * `buffer` - A mutable vector where the generated [`Ply`] instances will be appended.
+/// * `buffer` - The list where the generated [`Ply`](crate::moves::Ply) instances will be appended.
 pub fn legal_moves(
     what_to_move: LocatedPiece,
     backend: &Board,
     context: &LegalityContext,
-    buffer: &mut Vec<Ply>,
+    buffer: &mut MoveList,
 ) {
-    slide(
-        what_to_move,
-        7,
-        &directions::DIAGONAL_DIRECTIONS,
-        backend,
-        context,
-        buffer,
-    );
+    let targets = attacks::bishop_attacks(what_to_move.position(), backend.occupied())
+        & !backend.side(what_to_move.piece().team())
+        & context.check_mask()
+        & context.pin_ray(what_to_move.position());
+
+    push_plies(what_to_move, targets, backend, buffer);
 }
@@ -0,0 +1,85 @@
+//! # Check Context
+//!
+//! This module provides the [`CheckContext`] struct, the counterpart of the
+//! [`LegalityContext`](crate::moves::LegalityContext) for the side *giving*
+//! check. Where the legality context describes the threats against the King of
+//! the side to move, the check context describes the threats that side can make
+//! against the enemy King.
+//!
+//! It is calculated once per position, after which telling whether a move gives
+//! check takes a couple of lookups instead of playing the move.
+
+use crate::{
+    atoms::{Bitboard, Coordinate},
+    moves::attacks,
+    pieces::Kind,
+};
+
+/// Provides the squares from which each piece kind would check the enemy King.
+///
+/// A move gives check in one of two ways:
+/// 1. **Direct check**: The piece moved lands on one of the checking squares of its kind.
+/// 2. **Discovered check**: The piece moved stood alone between the enemy King and one of
+///    its own sliders, and leaves the line joining them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CheckContext {
+    /// The square of the King that would be checked.
+    king_position: Coordinate,
+    /// The squares from which each piece kind attacks the King, indexed by [`Kind::index`].
+    checking_squares: [Bitboard; 6],
+    /// Friendly pieces blocking the line between one of their sliders and the enemy King.
+    discovered_check_candidates: Bitboard,
+}
+
+impl CheckContext {
+    /// Creates a new `CheckContext` from pre-calculated board constraints.
+    ///
+    /// # Arguments
+    ///
+    /// * `king_position` - The square of the King that would be checked.
+    /// * `checking_squares` - The squares from which each kind attacks the King, indexed by
+    ///   [`Kind::index`].
+    /// * `discovered_check_candidates` - The friendly pieces that uncover a slider by moving.
+    #[must_use]
+    pub const fn from(
+        king_position: Coordinate,
+        checking_squares: [Bitboard; 6],
+        discovered_check_candidates: Bitboard,
+    ) -> Self {
+        Self {
+            king_position,
+            checking_squares,
+            discovered_check_candidates,
+        }
+    }
+
+    /// Returns the square of the King that would be checked.
+    #[must_use]
+    pub const fn king_position(&self) -> Coordinate {
+        self.king_position
+    }
+
+    /// Returns the squares from which a piece of `kind` attacks the King.
+    ///
+    /// A King never checks another King, so its checking squares are always empty.
+    #[must_use]
+    pub const fn checking_squares(&self, kind: Kind) -> Bitboard {
+        self.checking_squares[kind.index()]
+    }
+
+    /// Returns the squares of the friendly pieces that give a discovered check by leaving
+    /// the line between the King and the slider behind them.
+    #[must_use]
+    pub const fn discovered_check_candidates(&self) -> Bitboard {
+        self.discovered_check_candidates
+    }
+
+    /// Returns `true` if a piece moving from `from` to `to` uncovers a slider on the King.
+    ///
+    /// Moving along the line joining the King and the slider keeps it blocked.
+    #[must_use]
+    pub fn discovers_check(&self, from: Coordinate, to: Coordinate) -> bool {
+        self.discovered_check_candidates.contains(from)
+            && !attacks::line(self.king_position, from).contains(to)
+    }
+}
@@ -0,0 +1,51 @@
+//! # Drop Move Generator
+//!
+//! This module contains the generation logic for drops, the Crazyhouse moves that
+//! put a captured piece from the player's pocket back onto the board.
+
+use crate::{
+    atoms::{Bitboard, Pocket, Side},
+    moves::{LegalityContext, MoveList, Ply},
+    pieces::{Kind, Piece},
+    state::Board,
+};
+
+/// The first and eighth ranks, where no pawn may be dropped.
+const BACK_RANKS: Bitboard = Bitboard(0xFF00_0000_0000_00FF);
+
+/// Generates strictly legal drops for every kind of piece in a pocket.
+///
+/// # Rules
+/// * **Empty Squares**: A piece can only be dropped on an empty square.
+/// * **Pawns**: A pawn can never be dropped on the first or eighth rank.
+/// * **Checks**: Dropping a piece never exposes the King, but while in check only
+///   drops blocking the attack ray are legal, and none answer a double check.
+///   Both constraints are given by [`LegalityContext::check_mask`].
+///
+/// # Arguments
+///
+/// * `side` - The player dropping a piece.
+/// * `pocket` - The pieces that player holds in hand.
+/// * `backend` - The board state used to find the empty squares.
+/// * `context` - The pre-calculated legality constraints (pins, checks, and danger squares).
+/// * `buffer` - The list where the generated [`Ply`] instances will be appended.
+pub fn legal_drops(
+    side: Side,
+    pocket: Pocket,
+    backend: &Board,
+    context: &LegalityContext,
+    buffer: &mut MoveList,
+) {
+    let targets = !backend.occupied() & context.check_mask();
+
+    for kind in pocket.kinds() {
+        let kind_targets = match kind {
+            Kind::Pawn => targets & !BACK_RANKS,
+            _ => targets,
+        };
+
+        for target in kind_targets {
+            buffer.push(Ply::new_drop(target, Piece::new(side, kind)));
+        }
+    }
+}
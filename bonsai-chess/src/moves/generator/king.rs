@@ -9,7 +9,7 @@
 use crate::{
     atoms::{CastlingRights, Coordinate, Side},
     moves::{
-        CastlingSide, LegalityContext, Ply, SpecialMove, directions, generator::sliding::slide,
+        CastlingSide, LegalityContext, MoveList, Ply, SpecialMove, attacks, generator::push_plies,
     },
     pieces::{Kind, LocatedPiece},
     state::Board,
@@ -21,8 +21,8 @@ use crate::{
 /// squares controlled by enemy pieces (danger squares). Additionally, if
 /// conditions are met, it can perform a special "Castling" move.
 ///
-/// This function relies on the shared `slide` utility with a maximum distance
-/// of 1, and delegates castling checks to a specialized helper.
+/// This function looks up the King's precomputed attack pattern, removes the
+/// danger squares from it, and delegates castling checks to a specialized helper.
 ///
 /// # Arguments
 ///
@@ -30,22 +30,19 @@ use crate::{
 /// * `backend` - The board state used to check for occupancy and path clearance.
 /// * `castling_rights` - The current castling permissions to determine if castling is a candidate.
 /// * `context` - The pre-calculated legality constraints (danger squares and current checks).
-/// * `buffer` - A mutable vector where the generated [`Ply`] instances will be appended.
+/// * `buffer` - The list where the generated [`Ply`] instances will be appended.
 pub fn legal_moves(
     what_to_move: LocatedPiece,
     backend: &Board,
     castling_rights: CastlingRights,
     context: &LegalityContext,
-    buffer: &mut Vec<Ply>,
+    buffer: &mut MoveList,
 ) {
-    slide(
-        what_to_move,
-        1,
-        &directions::KING_DIRECTIONS,
-        backend,
-        context,
-        buffer,
-    );
+    let targets = attacks::king_attacks(what_to_move.position())
+        & !backend.side(what_to_move.piece().team())
+        & !context.danger_squares();
+
+    push_plies(what_to_move, targets, backend, buffer);
 
     if castling_rights != CastlingRights::no_rights() {
         get_castling_moves(what_to_move, backend, castling_rights, context, buffer);
@@ -60,8 +57,13 @@ pub fn legal_moves(
 /// 3. All squares between the King and the Rook are unoccupied.
 /// 4. The King does not pass through, nor land on, a square attacked by an enemy piece.
 ///
-/// *Note: For Queenside castling, the b-file square adjacent to the Rook must be empty,
-/// but it is permitted to be under attack, as the King does not pass through it.*
+/// The same rules cover Chess960, where the King and Rook may start on any file: the King
+/// always lands on the g-file (short) or c-file (long) and the Rook right beside it. Every
+/// square either piece travels over must be empty, apart from the King and the castling
+/// Rook themselves.
+///
+/// *Note: For Queenside castling in standard chess, the b-file square adjacent to the Rook
+/// must be empty, but it is permitted to be under attack, as the King does not pass through it.*
 ///
 /// # Arguments
 ///
@@ -69,29 +71,20 @@ pub fn legal_moves(
 /// * `backend` - The board state used to verify empty paths and safe squares.
 /// * `castling_rights` - The specific short/long rights for the current player.
 /// * `context` - Used to quickly determine if the King is currently in check.
-/// * `buffer` - The vector to append the castling `Ply` to if valid.
+/// * `buffer` - The list to append the castling `Ply` to if valid.
 fn get_castling_moves(
     what_to_move: LocatedPiece,
     backend: &Board,
     castling_rights: CastlingRights,
     context: &LegalityContext,
-    buffer: &mut Vec<Ply>,
+    buffer: &mut MoveList,
 ) {
-    // File Indices (0-7 for A-H)
-    const FILE_B: usize = 1; // Occupancy check on long castle
-    const FILE_C: usize = 2; // Long Castle Destination
-    const FILE_D: usize = 3; // Long Castle Cross / Rook Destination
-    const FILE_F: usize = 5; // Short Castle Cross
-    const FILE_G: usize = 6; // Short Castle Destination
-
-    const FILE_A: usize = 0; // where long side rook is
-    const FILE_H: usize = 7; // where short side rook is
-
     let ally = what_to_move.piece().team();
     let enemy = ally.opposite();
+    let king_square = what_to_move.position();
 
     // 1. Cannot castle if currently in check
-    if !context.checkers().is_empty() {
+    if context.in_check() {
         return;
     }
 
@@ -100,82 +93,143 @@ fn get_castling_moves(
         Side::Black => 0,
     };
 
+    if king_square.row() != castling_row {
+        return;
+    }
+
     // Helper to get Coordinate safely
     let to_coordinates =
         |column: usize| -> Coordinate { Coordinate::new(castling_row, column).unwrap() };
 
-    // --- Kingside (Short) ---
-    // Checks: Rights + Path Empty (F, G) + Path Safe (F, G)
-    let can_castle_short = match ally {
-        Side::White => castling_rights.white_king_side(),
-        Side::Black => castling_rights.black_king_side(),
-    };
+    // Inclusive span of columns between two files, in either order
+    let span = |from: usize, to: usize| from.min(to)..=from.max(to);
 
-    if can_castle_short {
-        let f_square = to_coordinates(FILE_F);
-        let g_square = to_coordinates(FILE_G);
+    for castling_side in [CastlingSide::Short, CastlingSide::Long] {
+        let Some(rook_column) = castling_rights.rook_file(ally, castling_side) else {
+            continue;
+        };
+        let rook_square = to_coordinates(rook_column);
 
-        // You cannot go through other pieces
-        let is_path_clear = backend.get(f_square).is_none() && backend.get(g_square).is_none();
+        let is_rook_in_place = backend.get(rook_square).is_some_and(|potential_rook| {
+            potential_rook.kind() == Kind::Rook && potential_rook.team() == ally
+        });
+        if !is_rook_in_place {
+            continue;
+        }
 
-        // FIDE Rule: You cannot castle *through* check.
-        let is_path_safe = !backend.is_square_under_attack(f_square, enemy)
-            && !backend.is_square_under_attack(g_square, enemy);
+        let king_destination = castling_side.king_destination_column();
+        let rook_destination = castling_side.rook_destination_column();
 
-        let is_rook_in_place = backend
-            .get(Coordinate::new(castling_row, FILE_H).unwrap())
-            .is_some_and(|potential_rook| {
-                potential_rook.kind() == Kind::Rook && potential_rook.team() == ally
+        // You cannot go through other pieces: only the King and the castling Rook may
+        // stand on the squares either of them crosses.
+        let is_path_clear = span(king_square.column(), king_destination)
+            .chain(span(rook_column, rook_destination))
+            .map(to_coordinates)
+            .all(|square| {
+                square == king_square || square == rook_square || backend.get(square).is_none()
             });
+        if !is_path_clear {
+            continue;
+        }
 
-        if is_path_clear && is_path_safe && is_rook_in_place {
+        // FIDE Rule: You cannot castle *through* check. The King and Rook are lifted off
+        // the board first, so that a Rook shielding the King (possible in Chess960) does
+        // not hide an attack on the King's destination.
+        let mut lifted = *backend;
+        lifted.unset(king_square);
+        lifted.unset(rook_square);
+        let is_path_safe = span(king_square.column(), king_destination)
+            .map(to_coordinates)
+            .all(|square| !lifted.is_square_under_attack(square, enemy));
+
+        if is_path_safe {
             buffer.push(Ply::new(
-                what_to_move.position(),
-                g_square,
+                king_square,
+                to_coordinates(king_destination),
                 what_to_move.piece(),
                 None,
-                Some(SpecialMove::Castle(CastlingSide::Short)),
+                Some(SpecialMove::Castle(castling_side, rook_square)),
             ));
         }
     }
+}
 
-    // --- Queenside (Long) ---
-    // Checks: Rights + Path Empty (B, C, D) + Path Safe (C, D)
-    // Note: B-file must be empty, but does NOT need to be safe from attack.
-    let can_castle_long = match ally {
-        Side::White => castling_rights.white_queen_side(),
-        Side::Black => castling_rights.black_queen_side(),
-    };
+#[cfg(test)]
+mod tests {
+    use crate::{moves::SpecialMove, state::Game};
 
-    if can_castle_long {
-        let b_square = to_coordinates(FILE_B);
-        let c_square = to_coordinates(FILE_C);
-        let d_square = to_coordinates(FILE_D);
-
-        // B, C, and D must be empty
-        let is_path_clear = backend.get(b_square).is_none()
-            && backend.get(c_square).is_none()
-            && backend.get(d_square).is_none();
-
-        // Only C and D (where King moves) must be safe
-        // The Rook passes through B, but the King does not, so B need not be safe.
-        let is_path_safe = !backend.is_square_under_attack(c_square, enemy)
-            && !backend.is_square_under_attack(d_square, enemy);
-
-        let is_rook_in_place = backend
-            .get(Coordinate::new(castling_row, FILE_A).unwrap())
-            .is_some_and(|potential_rook| {
-                potential_rook.kind() == Kind::Rook && potential_rook.team() == ally
-            });
+    fn perft(game: &mut Game, depth: usize) -> usize {
+        let moves = game.get_legal_moves();
+        if depth == 1 {
+            return moves.len();
+        }
 
-        if is_path_clear && is_path_safe && is_rook_in_place {
-            buffer.push(Ply::new(
-                what_to_move.position(),
-                c_square,
-                what_to_move.piece(),
-                None,
-                Some(SpecialMove::Castle(CastlingSide::Long)),
-            ));
+        moves
+            .iter()
+            .map(|ply| {
+                game.make_move(ply);
+                let nodes = perft(game, depth - 1);
+                game.undo_last_move();
+                nodes
+            })
+            .sum()
+    }
+
+    fn castles(game: &Game) -> Vec<String> {
+        game.get_legal_moves()
+            .into_iter()
+            .filter(|ply| matches!(ply.special_move(), Some(SpecialMove::Castle(..))))
+            .map(|ply| ply.to_uci())
+            .collect()
+    }
+
+    #[test]
+    fn test_chess960_perft() {
+        // Source: https://www.chessprogramming.org/Chess960_Perft_Results
+        for (fen, expected) in [
+            (
+                "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+                [21, 528, 12_189],
+            ),
+            (
+                "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+                [21, 807, 18_002],
+            ),
+            (
+                "qnbnr1kr/ppp1b1pp/4p3/3p1p2/8/2NPP3/PPP1BPPP/QNB1R1KR w HEhe - 1 9",
+                [29, 899, 26_578],
+            ),
+        ] {
+            let mut game = Game::try_from_fen(fen).unwrap();
+            for (depth, nodes) in (1..).zip(expected) {
+                assert_eq!(perft(&mut game, depth), nodes, "{fen} at depth {depth}");
+            }
         }
     }
+
+    #[test]
+    fn test_chess960_castling() {
+        // The King already stands on g1, only the Rook moves
+        let mut game = Game::from_fen("4k3/8/8/8/8/8/8/6KR w H - 0 1");
+        assert_eq!(castles(&game), ["g1h1"]);
+        let castle = game.parse_uci_move("g1h1").unwrap();
+        game.make_move(&castle);
+        assert_eq!(game.to_fen(), "4k3/8/8/8/8/8/8/5RK1 b - - 1 1");
+        game.undo_last_move();
+        assert_eq!(game.to_fen(), "4k3/8/8/8/8/8/8/6KR w K - 0 1");
+
+        // King and Rook swap squares
+        let mut game = Game::from_fen("4k3/8/8/8/8/8/8/5KR1 w G - 0 1");
+        let castle = game.parse_uci_move("f1g1").unwrap();
+        game.make_move(&castle);
+        assert_eq!(game.to_fen(), "4k3/8/8/8/8/8/8/5RK1 b - - 1 1");
+
+        // The b1 Rook shields the King from the a1 Rook, so castling long would expose it
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/rRK5 w B - 0 1");
+        assert!(castles(&game).is_empty());
+
+        // The b1 Knight blocks the Rook, even though the King does not cross b1
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/RN2K3 w Q - 0 1");
+        assert!(castles(&game).is_empty());
+    }
 }
@@ -5,20 +5,20 @@
 //! only pieces capable of jumping over other pieces to reach their destination.
 
 use crate::{
-    moves::{LegalityContext, Ply, directions, generator::sliding::slide},
+    moves::{LegalityContext, MoveList, attacks, generator::push_plies},
     pieces::LocatedPiece,
     state::Board,
 };
 
-/// Generates strictly legal moves for a Knight.    
+/// Generates strictly legal moves for a Knight.
 ///
 /// The Knight moves in an "L" shape: two squares in a cardinal direction, then one
 /// square perpendicular to that.
 ///
 /// # Unique Properties
 /// * **Jumping**: The Knight is the only piece that can jump over other pieces.
-///   The `slide` function handles this naturally when the `distance` is set to 1,
-///   as it only checks the *destination* square for occupancy, ignoring the path.
+///   Its precomputed attack pattern only depends on its square, never on the occupancy.
+/// * **Pins**: A pinned Knight can never move, as no L-shape stays on the pin ray.
 ///
 /// # Movement Logic
 /// * **Directions**: 8 possible L-shapes.
@@ -29,19 +29,17 @@ use crate::{
 /// * `what_to_move` - The Knight being moved and its starting location.
 /// * `backend` - The board state used to check for occupancy and captures at the destination.
 /// * `context` - The pre-calculated legality constraints (pins, checks, and danger squares).
-/// * `buffer` - A mutable vector where the generated [`Ply`] instances will be appended.
+/// * `buffer` - The list where the generated [`Ply`](crate::moves::Ply) instances will be appended.
 pub fn legal_moves(
     what_to_move: LocatedPiece,
     backend: &Board,
     context: &LegalityContext,
-    buffer: &mut Vec<Ply>,
+    buffer: &mut MoveList,
 ) {
-    slide(
-        what_to_move,
-        1,
-        &directions::KNIGHT_DIRECTIONS,
-        backend,
-        context,
-        buffer,
-    );
+    let targets = attacks::knight_attacks(what_to_move.position())
+        & !backend.side(what_to_move.piece().team())
+        & context.check_mask()
+        & context.pin_ray(what_to_move.position());
+
+    push_plies(what_to_move, targets, backend, buffer);
 }
@@ -9,21 +9,26 @@
 //! Move generators for individual pieces then use this context to only yield
 //! strictly legal moves, significantly improving performance.
 
-use crate::atoms::Coordinate;
+use crate::{
+    atoms::{Bitboard, Coordinate},
+    moves::attacks,
+};
 
 /// Provides the legality constraints for the current position before move generation.
 ///
-/// This structure holds pre-calculated masks and lists that dictate whether a
+/// This structure holds pre-calculated [`Bitboard`] masks that dictate whether a
 /// theoretically valid piece movement is actually legal under the rules of chess
 /// (e.g., preventing a pinned piece from moving off its pin ray).
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct LegalityContext {
+    /// The square of the King whose safety is being evaluated.
+    king_position: Coordinate,
     /// Squares containing enemy pieces currently checking our King.
-    checkers: Vec<Coordinate>,
-    /// Friendly pieces pinned to the King, and the `(delta_row, delta_column)` direction ray of the pin.
-    pinned_pieces: Vec<(Coordinate, (isize, isize))>,
+    checkers: Bitboard,
+    /// Friendly pieces pinned to the King.
+    pinned_pieces: Bitboard,
     /// Squares adjacent to the King that are controlled by the enemy.
-    danger_squares: Vec<Coordinate>,
+    danger_squares: Bitboard,
 }
 
 impl LegalityContext {
@@ -31,47 +36,57 @@ impl LegalityContext {
     ///
     /// # Arguments
     ///
-    /// * `checkers` - A list of coordinates containing enemy pieces attacking the King.
-    /// * `pinned_pieces` - A list of tuples containing the coordinate of a friendly pinned piece and the `(d_row, d_col)` ray of the pin.
-    /// * `danger_squares` - A list of coordinates around the King that cannot be stepped onto.
+    /// * `king_position` - The square of the King being protected.
+    /// * `checkers` - The squares of the enemy pieces attacking the King.
+    /// * `pinned_pieces` - The squares of the friendly pieces pinned to the King.
+    /// * `danger_squares` - The squares around the King that cannot be stepped onto.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use bonsai_chess::prelude::{Coordinates, LegalityContext};
+    /// use bonsai_chess::prelude::{Bitboard, Coordinate, LegalityContext};
     ///
-    /// let context = LegalityContext::from(vec![], vec![], vec![]);
+    /// let e1 = Coordinate::from_algebraic_notation("e1").unwrap();
+    /// let context = LegalityContext::from(e1, Bitboard::EMPTY, Bitboard::EMPTY, Bitboard::EMPTY);
     /// assert!(!context.in_check());
     /// ```
     #[must_use]
     pub const fn from(
-        checkers: Vec<Coordinate>,
-        pinned_pieces: Vec<(Coordinate, (isize, isize))>,
-        danger_squares: Vec<Coordinate>,
+        king_position: Coordinate,
+        checkers: Bitboard,
+        pinned_pieces: Bitboard,
+        danger_squares: Bitboard,
     ) -> Self {
         Self {
+            king_position,
             checkers,
             pinned_pieces,
             danger_squares,
         }
     }
 
-    /// Returns a slice of coordinates representing the position of enemy pieces delivering check.
+    /// Returns the square of the King this context protects.
+    #[must_use]
+    pub const fn king_position(&self) -> Coordinate {
+        self.king_position
+    }
+
+    /// Returns the squares of the enemy pieces delivering check.
     #[must_use]
-    pub fn checkers(&self) -> &[Coordinate] {
-        &self.checkers
+    pub const fn checkers(&self) -> Bitboard {
+        self.checkers
     }
 
-    /// Returns a slice of tuples representing pinned pieces and their pin direction vectors.
+    /// Returns the squares of the friendly pieces pinned to the King.
     #[must_use]
-    pub fn pinned_pieces(&self) -> &[(Coordinate, (isize, isize))] {
-        &self.pinned_pieces
+    pub const fn pinned_pieces(&self) -> Bitboard {
+        self.pinned_pieces
     }
 
-    /// Returns a slice of coordinates representing squares the King cannot move to.
+    /// Returns the squares the King cannot move to.
     #[must_use]
-    pub fn danger_squares(&self) -> &[Coordinate] {
-        &self.danger_squares
+    pub const fn danger_squares(&self) -> Bitboard {
+        self.danger_squares
     }
 
     /// Returns `true` if the King is currently in check (attacked by 1 or more pieces).
@@ -86,7 +101,7 @@ impl LegalityContext {
     /// block the attack ray.
     #[must_use]
     pub const fn in_single_check(&self) -> bool {
-        self.checkers.len() == 1
+        self.in_check() && !self.in_double_check()
     }
 
     /// Returns `true` if the King is attacked by two or more pieces.
@@ -95,102 +110,43 @@ impl LegalityContext {
     /// simultaneously. Therefore, the only legal response is for the King to move.
     #[must_use]
     pub const fn in_double_check(&self) -> bool {
-        self.checkers.len() > 1
+        self.checkers.has_several()
     }
 
-    /// Determines if a pinned piece is allowed to move along a specific directional ray.
+    /// Returns the squares a pinned piece may still move to.
     ///
     /// A pinned piece can only move along the ray of its pin (e.g., a piece pinned
     /// horizontally can still move horizontally to capture the pinning piece, but
-    /// cannot move vertically).
+    /// cannot move vertically). That ray is the line through the King and the piece.
     ///
-    /// This function uses the cross product of the intended movement vector and
-    /// the pin vector to quickly determine collinearity.
+    /// Returns [`Bitboard::FULL`] for pieces that are not pinned.
     ///
     /// # Arguments
     ///
     /// * `location_of_piece_to_move` - The coordinate of the piece attempting to move.
-    /// * `row_delta` - The row direction of the intended move.
-    /// * `column_delta` - The column direction of the intended move.
     #[must_use]
-    pub fn is_legal_pin_direction(
-        &self,
-        location_of_piece_to_move: Coordinate,
-        row_delta: isize,
-        column_delta: isize,
-    ) -> bool {
-        for &(pinned_square, (pin_row_delta, pin_column_delta)) in &self.pinned_pieces {
-            if pinned_square == location_of_piece_to_move {
-                // Direction vectors must be parallel. Cross product == 0.
-                return (row_delta * pin_column_delta) - (column_delta * pin_row_delta) == 0;
-            }
+    pub fn pin_ray(&self, location_of_piece_to_move: Coordinate) -> Bitboard {
+        if self.pinned_pieces.contains(location_of_piece_to_move) {
+            attacks::line(self.king_position, location_of_piece_to_move)
+        } else {
+            Bitboard::FULL
         }
-        true // Not pinned
     }
 
-    /// Checks if a proposed target square resolves an ongoing single check.
+    /// Returns the squares where a non-king piece can land while the King is in check.
     ///
-    /// A non-king piece can resolve a check in one of two ways:
+    /// A non-king piece can resolve a single check in one of two ways:
     /// 1. Capturing the piece delivering the check.
     /// 2. Moving to a square that blocks the attack ray between the King and the checker.
     ///
-    /// # Arguments
-    ///
-    /// * `target` - The coordinate the friendly piece intends to move to.
-    /// * `king_position` - The coordinate of the friendly King.
-    /// * `captured_ep_pawn` - The coordinate of a pawn that would be captured via en passant, if applicable.
-    ///
-    /// # Returns
-    ///
-    /// `true` if the move resolves the check or if there is no single check. `false` otherwise.
+    /// Returns [`Bitboard::FULL`] if there is no check, and [`Bitboard::EMPTY`] on a
+    /// double check, which only the King can answer.
     #[must_use]
-    pub fn resolves_single_check(
-        &self,
-        target: Coordinate,
-        king_position: Coordinate,
-        captured_ep_pawn: Option<Coordinate>,
-    ) -> bool {
-        if !self.in_single_check() {
-            return true;
-        }
-
-        let checker = self.checkers[0];
-
-        // 1. Capture the checker
-        if target == checker {
-            return true;
-        }
-
-        // Handle En Passant resolving a check from a pawn
-        if let Some(ep_sq) = captured_ep_pawn
-            && ep_sq == checker
-        {
-            return true;
+    pub fn check_mask(&self) -> Bitboard {
+        match self.checkers.first() {
+            None => Bitboard::FULL,
+            Some(_) if self.in_double_check() => Bitboard::EMPTY,
+            Some(checker) => self.checkers | attacks::between(self.king_position, checker),
         }
-
-        // 2. Block the checker (target must lie exactly between King and Checker)
-        let ray_row_delta = checker.row().cast_signed() - king_position.row().cast_signed();
-        let ray_column_delta =
-            checker.column().cast_signed() - king_position.column().cast_signed();
-
-        let target_row_delta = target.row().cast_signed() - king_position.row().cast_signed();
-        let target_column_delta =
-            target.column().cast_signed() - king_position.column().cast_signed();
-
-        // Cross product must be 0 (must be collinear)
-        if ray_row_delta * target_column_delta - ray_column_delta * target_row_delta != 0 {
-            return false;
-        }
-
-        // Must be in the same direction (dot product > 0)
-        if ray_row_delta * target_row_delta + ray_column_delta * target_column_delta <= 0 {
-            return false;
-        }
-
-        // Must be closer to the king than the checker is (Chebyshev distance)
-        let checker_dist = ray_row_delta.abs().max(ray_column_delta.abs());
-        let target_dist = target_row_delta.abs().max(target_column_delta.abs());
-
-        target_dist < checker_dist
     }
 }
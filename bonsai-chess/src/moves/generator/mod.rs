@@ -5,9 +5,18 @@
 //! sliding pieces (Queens, Rooks, Bishops), leaping pieces (Knights),
 //! step pieces (Kings), and the complex rules governing pawns.
 
+/// Precomputed attack tables and lookups for every piece type.
+pub mod attacks;
+
 /// Bishop move generation logic (diagonal sliding).
 mod bishop;
 
+/// Contextual information used to tell whether a move gives check without playing it (e.g., checking squares).
+mod check_context;
+
+/// Drop generation logic, putting pieces from the pocket back onto the board (Crazyhouse).
+mod drop;
+
 /// Directional vectors (offsets) used for calculating piece movements.
 pub mod directions;
 
@@ -29,16 +38,15 @@ mod queen;
 /// Rook move generation logic (straight sliding).
 mod rook;
 
-/// Shared logic for generating ray attacks for sliding pieces.
-mod sliding;
-
 use crate::{
-    atoms::{CastlingRights, Coordinate},
-    moves::Ply,
+    atoms::{Bitboard, CastlingRights, Coordinate},
+    moves::{MoveList, Ply},
     pieces::{Kind, LocatedPiece},
     state::Board,
 };
 
+pub use check_context::CheckContext;
+pub use drop::legal_drops as generate_drops;
 pub use legality_context::LegalityContext;
 
 /// Calculates all valid moves for a specific piece and pushes them into a buffer.
@@ -54,7 +62,7 @@ pub use legality_context::LegalityContext;
 /// * `en_passant_target`: The coordinate of a pawn that can be captured en passant (if any).
 /// * `castling_rights`: The current castling permissions for both players.
 /// * `context`: The pre-calculated data regarding checks and pins to filter illegal moves.
-/// * `buffer`: A mutable vector where the generated [`Ply`] instances will be appended.
+/// * `buffer`: The list where the generated [`Ply`] instances will be appended.
 ///
 /// # Examples
 ///
@@ -83,7 +91,7 @@ pub fn generate_legal_moves(
     en_passant_target: Option<Coordinate>,
     castling_rights: CastlingRights,
     context: &LegalityContext,
-    buffer: &mut Vec<Ply>,
+    buffer: &mut MoveList,
 ) {
     if context.in_double_check() && what_to_move.piece().kind() != Kind::King {
         // Double check: Only the king can move.
@@ -99,3 +107,32 @@ pub fn generate_legal_moves(
         Kind::Pawn => pawn::legal_moves(what_to_move, backend, en_passant_target, context, buffer),
     }
 }
+
+/// Appends a move from the piece's square to every square in `targets`.
+///
+/// Shared by every piece whose moves are fully described by a set of destination
+/// squares (everything but castling, pawn pushes, promotions and en passant).
+/// Whatever stands on a target square is recorded as the captured piece.
+///
+/// # Arguments
+///
+/// * `what_to_move` - The piece being moved and its starting location.
+/// * `targets` - The strictly legal destination squares.
+/// * `backend` - The board state used to look up the captured pieces.
+/// * `buffer` - The list where the generated [`Ply`] instances will be appended.
+fn push_plies(
+    what_to_move: LocatedPiece,
+    targets: Bitboard,
+    backend: &Board,
+    buffer: &mut MoveList,
+) {
+    for target in targets {
+        buffer.push(Ply::new(
+            what_to_move.position(),
+            target,
+            what_to_move.piece(),
+            backend.get(target),
+            None,
+        ));
+    }
+}
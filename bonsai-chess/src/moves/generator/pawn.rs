@@ -8,7 +8,7 @@
 
 use crate::{
     atoms::{Coordinate, Side},
-    moves::{LegalityContext, Ply, SpecialMove},
+    moves::{LegalityContext, MoveList, Ply, SpecialMove, attacks},
     pieces::{Kind, LocatedPiece, Piece, Promotion},
     state::Board,
 };
@@ -51,13 +51,13 @@ const PROMOTIONS: &[SpecialMove] = &[
 /// * `backend` - The current state of the chess board, used to check for occupancy.
 /// * `en_passant_target` - The coordinate of the En Passant target square, if available.
 /// * `context` - The pre-calculated legality constraints (pins, checks, and danger squares).
-/// * `buffer` - A mutable vector where the generated [`Ply`] instances will be appended.
+/// * `buffer` - The list where the generated [`Ply`] instances will be appended.
 pub fn legal_moves(
     what_to_move: LocatedPiece,
     backend: &Board,
     en_passant_target: Option<Coordinate>,
     context: &LegalityContext,
-    buffer: &mut Vec<Ply>,
+    buffer: &mut MoveList,
 ) {
     generate_pushes(what_to_move, backend, context, buffer);
     generate_captures(what_to_move, backend, en_passant_target, context, buffer);
@@ -67,71 +67,49 @@ fn generate_pushes(
     what_to_move: LocatedPiece,
     backend: &Board,
     context: &LegalityContext,
-    buffer: &mut Vec<Ply>,
+    buffer: &mut MoveList,
 ) {
-    let team = what_to_move.piece().team();
     let current_position = what_to_move.position();
-    let king_position = match team {
-        Side::White => backend.get_white_king(),
-        Side::Black => backend.get_black_king(),
-    };
-
-    let (direction, starting_row, promotion_row) = match team {
+    let (direction, starting_row, promotion_row) = match what_to_move.piece().team() {
         Side::White => (-1isize, WHITE_PAWN_STARTING_ROW, WHITE_PAWN_PROMOTION_ROW),
         Side::Black => (1isize, BLACK_PAWN_STARTING_ROW, BLACK_PAWN_PROMOTION_ROW),
     };
 
-    #[allow(clippy::cast_possible_wrap)]
-    let forward_row = current_position.row() as isize + direction;
+    // A push must stay on the pin ray and, if in check, block it.
+    let allowed = context.check_mask() & context.pin_ray(current_position);
+    let occupied = backend.occupied();
+
+    // Path must be empty for both single and double pushes
+    let Some(one_forward_coords) = current_position.with_offset((direction, 0), 1) else {
+        return;
+    };
+    if occupied.contains(one_forward_coords) {
+        return;
+    }
+
+    // Evaluate Single Push legality
+    if allowed.contains(one_forward_coords) {
+        let special_moves: &[SpecialMove] = if one_forward_coords.row() == promotion_row {
+            PROMOTIONS
+        } else {
+            &[]
+        };
+        push_pawn_move(
+            what_to_move,
+            one_forward_coords,
+            None,
+            special_moves,
+            buffer,
+        );
+    }
 
-    if context.is_legal_pin_direction(current_position, direction, 0)
-        && let Some(one_forward_coords) = Coordinate::new(forward_row, current_position.column())
+    // Evaluate Double Forward Push
+    if current_position.row() == starting_row
+        && let Some(two_forward_coords) = current_position.with_offset((direction, 0), 2)
+        && !occupied.contains(two_forward_coords)
+        && allowed.contains(two_forward_coords)
     {
-        // Path must be empty for both single and double pushes
-        if backend.get(one_forward_coords).is_none() {
-            // Evaluate Single Push legality
-            if context.resolves_single_check(one_forward_coords, king_position, None) {
-                if one_forward_coords.row() == promotion_row {
-                    for promotion in PROMOTIONS {
-                        buffer.push(Ply::new(
-                            current_position,
-                            one_forward_coords,
-                            what_to_move.piece(),
-                            None,
-                            Some(*promotion),
-                        ));
-                    }
-                } else {
-                    buffer.push(Ply::new(
-                        current_position,
-                        one_forward_coords,
-                        what_to_move.piece(),
-                        None,
-                        None,
-                    ));
-                }
-            }
-
-            // Evaluate Double Forward Push
-            if current_position.row() == starting_row {
-                #[allow(clippy::cast_possible_wrap)]
-                let two_forward_row = current_position.row() as isize + 2 * direction;
-
-                if let Some(two_forward_coords) =
-                    Coordinate::new(two_forward_row, current_position.column())
-                    && backend.get(two_forward_coords).is_none()
-                    && context.resolves_single_check(two_forward_coords, king_position, None)
-                {
-                    buffer.push(Ply::new(
-                        current_position,
-                        two_forward_coords,
-                        what_to_move.piece(),
-                        None,
-                        None,
-                    ));
-                }
-            }
-        }
+        push_pawn_move(what_to_move, two_forward_coords, None, &[], buffer);
     }
 }
 
@@ -140,82 +118,90 @@ fn generate_captures(
     backend: &Board,
     en_passant_target: Option<Coordinate>,
     context: &LegalityContext,
-    buffer: &mut Vec<Ply>,
+    buffer: &mut MoveList,
 ) {
     let team = what_to_move.piece().team();
     let current_position = what_to_move.position();
-    let king_position = match team {
-        Side::White => backend.get_white_king(),
-        Side::Black => backend.get_black_king(),
+    let promotion_row = match team {
+        Side::White => WHITE_PAWN_PROMOTION_ROW,
+        Side::Black => BLACK_PAWN_PROMOTION_ROW,
     };
 
-    let (direction, _, promotion_row) = match team {
-        Side::White => (-1isize, WHITE_PAWN_STARTING_ROW, WHITE_PAWN_PROMOTION_ROW),
-        Side::Black => (1isize, BLACK_PAWN_STARTING_ROW, BLACK_PAWN_PROMOTION_ROW),
-    };
-
-    #[allow(clippy::cast_possible_wrap)]
-    let forward_row = current_position.row() as isize + direction;
-
-    for col_offset in [-1, 1] {
-        if context.is_legal_pin_direction(current_position, direction, col_offset) {
-            #[allow(clippy::cast_possible_wrap)]
-            let attack_col = current_position.column() as isize + col_offset;
-
-            if let Some(capture_coords) = Coordinate::new(forward_row, attack_col) {
-                // A. En Passant
-                if let Some(available_en_passant) = en_passant_target
-                    && capture_coords == available_en_passant
-                {
-                    let captured_pawn_position =
-                        Coordinate::new(current_position.row(), capture_coords.column()).unwrap();
-
-                    if context.resolves_single_check(
-                        capture_coords,
-                        king_position,
-                        Some(captured_pawn_position),
-                    ) {
-                        buffer.push(Ply::new(
-                            current_position,
-                            capture_coords,
-                            what_to_move.piece(),
-                            Some(Piece::new(
-                                what_to_move.piece().team().opposite(),
-                                Kind::Pawn,
-                            )),
-                            Some(SpecialMove::EnPassant(captured_pawn_position)),
-                        ));
-                    }
-                }
-
-                // B. Standard Capture
-                if let Some(target_piece) = backend.get(capture_coords)
-                    && target_piece.team() != what_to_move.piece().team()
-                    && context.resolves_single_check(capture_coords, king_position, None)
-                {
-                    // Capture Promotion
-                    if capture_coords.row() == promotion_row {
-                        for promotion in PROMOTIONS {
-                            buffer.push(Ply::new(
-                                current_position,
-                                capture_coords,
-                                what_to_move.piece(),
-                                Some(target_piece),
-                                Some(*promotion),
-                            ));
-                        }
-                    } else {
-                        // Normal Capture
-                        buffer.push(Ply::new(
-                            current_position,
-                            capture_coords,
-                            what_to_move.piece(),
-                            Some(target_piece),
-                            None,
-                        ));
-                    }
-                }
-            }
+    let attacked = attacks::pawn_attacks(team, current_position);
+
+    // A. En Passant
+    //
+    // The capture removes two pieces from the same rank at once, which can expose the
+    // King along that rank in a way the pin masks cannot describe. Since en passant is
+    // rare, the move is simply played on a copy of the board and the King checked.
+    if let Some(available_en_passant) = en_passant_target
+        && attacked.contains(available_en_passant)
+        && let Some(captured_pawn_position) =
+            Coordinate::new(current_position.row(), available_en_passant.column())
+    {
+        let en_passant = Ply::new(
+            current_position,
+            available_en_passant,
+            what_to_move.piece(),
+            Some(Piece::new(team.opposite(), Kind::Pawn)),
+            Some(SpecialMove::EnPassant(captured_pawn_position)),
+        );
+
+        let mut board_after_capture = *backend;
+        board_after_capture.make_move(&en_passant);
+        if !board_after_capture.is_square_under_attack(context.king_position(), team.opposite()) {
+            buffer.push(en_passant);
         }
     }
+
+    // B. Standard Capture
+    let targets = attacked
+        & backend.side(team.opposite())
+        & context.check_mask()
+        & context.pin_ray(current_position);
+
+    for capture_coords in targets {
+        let special_moves: &[SpecialMove] = if capture_coords.row() == promotion_row {
+            PROMOTIONS
+        } else {
+            &[]
+        };
+        push_pawn_move(
+            what_to_move,
+            capture_coords,
+            backend.get(capture_coords),
+            special_moves,
+            buffer,
+        );
+    }
+}
+
+/// Appends a pawn move, once per special move given (one per promotion), or once
+/// as a plain move if `special_moves` is empty.
+fn push_pawn_move(
+    what_to_move: LocatedPiece,
+    target: Coordinate,
+    captured: Option<Piece>,
+    special_moves: &[SpecialMove],
+    buffer: &mut MoveList,
+) {
+    if special_moves.is_empty() {
+        buffer.push(Ply::new(
+            what_to_move.position(),
+            target,
+            what_to_move.piece(),
+            captured,
+            None,
+        ));
+    }
+
+    for special_move in special_moves {
+        buffer.push(Ply::new(
+            what_to_move.position(),
+            target,
+            what_to_move.piece(),
+            captured,
+            Some(*special_move),
+        ));
+    }
 }
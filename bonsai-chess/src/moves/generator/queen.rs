@@ -7,7 +7,7 @@
 
 use crate::{
     moves::{
-        LegalityContext, Ply,
+        LegalityContext, MoveList,
         generator::{bishop, rook},
     },
     pieces::LocatedPiece,
@@ -31,12 +31,12 @@ use crate::{
 /// * `what_to_move` - The Queen being moved and its starting location.
 /// * `backend` - The board state used to check for occupancy and captures.
 /// * `context` - The pre-calculated legality constraints (pins, checks, and danger squares).
-/// * `buffer` - A mutable vector where the generated [`Ply`] instances will be appended.
+/// * `buffer` - The list where the generated [`Ply`](crate::moves::Ply) instances will be appended.
 pub fn legal_moves(
     what_to_move: LocatedPiece,
     backend: &Board,
     context: &LegalityContext,
-    buffer: &mut Vec<Ply>,
+    buffer: &mut MoveList,
 ) {
     rook::legal_moves(what_to_move, backend, context, buffer);
     bishop::legal_moves(what_to_move, backend, context, buffer);
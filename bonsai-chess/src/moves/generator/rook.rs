@@ -6,39 +6,33 @@
 //! by a friendly piece, or capture an enemy piece.
 
 use crate::{
-    moves::{
-        LegalityContext, Ply,
-        generator::{directions, sliding},
-    },
+    moves::{LegalityContext, MoveList, attacks, generator::push_plies},
     pieces::LocatedPiece,
     state::Board,
 };
 
 /// Generates strictly legal moves for a Rook.
 ///
-/// This function relies on the shared [`sliding::slide`] utility, passing it the
-/// 4 orthogonal directions and a maximum slide distance of 7 squares (the maximum
-/// possible distance across a standard 8x8 board). It respects all board boundaries,
-/// piece blocking, pins, and checks.
+/// This function looks up the orthogonal attacks from the Rook's square given the
+/// current occupancy, which already accounts for board boundaries and piece blocking,
+/// then keeps only the targets allowed by pins and checks.
 ///
 /// # Arguments
 ///
 /// * `what_to_move` - The Rook being moved and its starting location.
 /// * `backend` - The board state used to check for occupancy and captures.
 /// * `context` - The pre-calculated legality constraints (pins, checks, and danger squares).
-/// * `buffer` - A mutable vector where the generated [`Ply`] instances will be appended.
+/// * `buffer` - The list where the generated [`Ply`](crate::moves::Ply) instances will be appended.
 pub fn legal_moves(
     what_to_move: LocatedPiece,
     backend: &Board,
     context: &LegalityContext,
-    buffer: &mut Vec<Ply>,
+    buffer: &mut MoveList,
 ) {
-    sliding::slide(
-        what_to_move,
-        7,
-        &directions::ORTHOGONAL_DIRECTIONS,
-        backend,
-        context,
-        buffer,
-    );
+    let targets = attacks::rook_attacks(what_to_move.position(), backend.occupied())
+        & !backend.side(what_to_move.piece().team())
+        & context.check_mask()
+        & context.pin_ray(what_to_move.position());
+
+    push_plies(what_to_move, targets, backend, buffer);
 }
@@ -4,18 +4,31 @@
 //! * **Definitions**:
 //!     * [`Ply`]: A concrete move (start square -> end square).
 //!     * [`SpecialMove`]: Complex mechanics like castling, en passant, and promotion.
+//!     * [`MoveList`]: A stack-allocated list of moves, filled by the generators.
+//!     * [`MoveFlags`]: What a move does (captures, promotes, checks) without playing it.
 //! * **Generation**:
 //!     * [`generate_legal_moves`]: The core logic that determines where pieces can physically go.
+//!     * [`generate_drops`]: Where the pieces held in a pocket can be dropped (Crazyhouse).
 
 /// Contains the logic for generating valid moves for all piece types, including sliding pieces, knights, pawns, and kings.
 mod generator;
 
+/// Classifies moves as captures, promotions, checks, or quiet moves.
+mod move_flags;
+
+/// Stores generated moves on the stack, so that move generation does not allocate.
+mod move_list;
+
 /// Defines a single half-move (ply) from a starting square to a target square.
 mod ply;
 
 /// Defines complex board mechanics such as castling, en passant, and pawn promotion.
 mod special_move;
 
-pub use generator::{LegalityContext, directions, generate_legal_moves};
+pub use generator::{
+    CheckContext, LegalityContext, attacks, directions, generate_drops, generate_legal_moves,
+};
+pub use move_flags::MoveFlags;
+pub use move_list::MoveList;
 pub use ply::Ply;
 pub use special_move::{CastlingSide, SpecialMove};
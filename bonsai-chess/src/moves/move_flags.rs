@@ -0,0 +1,110 @@
+//! # Move Flags
+//!
+//! This module provides the [`MoveFlags`] struct, a compact description of what
+//! a move does: whether it captures, promotes, or gives check. Move ordering and
+//! user interfaces use it to sort and annotate moves without playing them.
+
+use std::ops::{BitOr, BitOrAssign};
+
+/// A set of properties of a move, stored as bits.
+///
+/// The flags are combined with `|` and tested with [`contains`](Self::contains), or
+/// with the dedicated methods. A move giving a discovered check has both
+/// [`CHECK`](Self::CHECK) and [`DISCOVERED_CHECK`](Self::DISCOVERED_CHECK) set.
+///
+/// # Examples
+///
+/// ```rust
+/// use bonsai_chess::prelude::*;
+///
+/// // The Knight on d4 blocks the Queen's path to the King
+/// let mut game = Game::from_fen("3k4/8/8/8/3N4/8/8/3QK3 w - - 0 1");
+///
+/// let uncovering = game.parse_san("Nf5").unwrap();
+/// let flags = game.classify_move(&uncovering);
+/// assert_eq!(flags, MoveFlags::CHECK | MoveFlags::DISCOVERED_CHECK);
+/// assert!(flags.is_quiet());
+///
+/// let king_move = game.parse_san("Kf2").unwrap();
+/// assert_eq!(game.classify_move(&king_move), MoveFlags::EMPTY);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoveFlags(u8);
+
+impl MoveFlags {
+    /// No flag set: a quiet move.
+    pub const EMPTY: Self = Self(0);
+    /// The move captures a piece, en passant included.
+    pub const CAPTURE: Self = Self(1);
+    /// The move promotes a pawn.
+    pub const PROMOTION: Self = Self(1 << 1);
+    /// The move checks the enemy King, directly or not.
+    pub const CHECK: Self = Self(1 << 2);
+    /// The move uncovers a slider checking the enemy King.
+    pub const DISCOVERED_CHECK: Self = Self(1 << 3);
+    /// The move is a castle.
+    pub const CASTLE: Self = Self(1 << 4);
+    /// The move captures en passant.
+    pub const EN_PASSANT: Self = Self(1 << 5);
+    /// The move drops a piece from the pocket (Crazyhouse).
+    pub const DROP: Self = Self(1 << 6);
+
+    /// Returns `true` if every flag set in `other` is also set in `self`.
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Sets every flag of `other`.
+    pub const fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    /// Returns `true` if the move captures a piece.
+    #[must_use]
+    pub const fn is_capture(self) -> bool {
+        self.contains(Self::CAPTURE)
+    }
+
+    /// Returns `true` if the move promotes a pawn.
+    #[must_use]
+    pub const fn is_promotion(self) -> bool {
+        self.contains(Self::PROMOTION)
+    }
+
+    /// Returns `true` if the move checks the enemy King.
+    #[must_use]
+    pub const fn is_check(self) -> bool {
+        self.contains(Self::CHECK)
+    }
+
+    /// Returns `true` if the move checks the enemy King by uncovering a slider.
+    #[must_use]
+    pub const fn is_discovered_check(self) -> bool {
+        self.contains(Self::DISCOVERED_CHECK)
+    }
+
+    /// Returns `true` if the move neither captures nor promotes.
+    ///
+    /// Quiet moves may still give check: search usually orders them after the captures
+    /// and promotions all the same.
+    #[must_use]
+    pub const fn is_quiet(self) -> bool {
+        !self.is_capture() && !self.is_promotion()
+    }
+}
+
+impl BitOr for MoveFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for MoveFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
@@ -0,0 +1,229 @@
+//! # Move List
+//!
+//! This module provides the [`MoveList`] struct, the buffer legal moves are
+//! generated into. It lives on the stack, so that generating the moves of a
+//! position, which search and perft do millions of times, never touches the heap.
+
+use crate::{
+    atoms::{Coordinate, Side},
+    moves::Ply,
+    pieces::{Kind, Piece},
+};
+
+/// Fills the slots of the list that hold no move yet. It is never read.
+const EMPTY_SLOT: Ply = {
+    let a8 = Coordinate::from_index(0).expect("index 0 is a8");
+    Ply::new(a8, a8, Piece::new(Side::White, Kind::Pawn), None, None)
+};
+
+/// A list of moves stored on the stack, up to [`MoveList::CAPACITY`] of them.
+///
+/// That capacity covers every position of standard chess, which never has more
+/// than 218 legal moves. Only variants with drops, where a full pocket can be
+/// dropped on most of the board, can go beyond it: the list then moves its
+/// content to the heap instead of losing moves.
+///
+/// The list dereferences to a slice of [`Ply`], which gives it `len`, `iter`,
+/// `contains`, sorting, and the rest of the slice methods.
+///
+/// # Examples
+///
+/// ```rust
+/// use bonsai_chess::prelude::*;
+///
+/// let game = Game::from_starting_position();
+/// let mut moves = MoveList::new();
+/// game.generate_moves_into(&mut moves);
+///
+/// assert_eq!(moves.len(), 20);
+/// assert!(moves.iter().all(|ply| ply.piece_moved().team() == Side::White));
+/// ```
+#[derive(Clone)]
+pub struct MoveList {
+    /// The moves, of which only the first `len` are meaningful.
+    plies: [Ply; Self::CAPACITY],
+    len: usize,
+    /// Every move of the list, once there are more than fit in `plies`.
+    spilled: Vec<Ply>,
+}
+
+impl MoveList {
+    /// The number of moves the list holds without allocating.
+    pub const CAPACITY: usize = 256;
+
+    /// Creates an empty list.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            plies: [EMPTY_SLOT; Self::CAPACITY],
+            len: 0,
+            spilled: Vec::new(),
+        }
+    }
+
+    /// Appends a move to the end of the list.
+    pub fn push(&mut self, ply: Ply) {
+        if !self.spilled.is_empty() {
+            self.spilled.push(ply);
+        } else if self.len < Self::CAPACITY {
+            self.plies[self.len] = ply;
+            self.len += 1;
+        } else {
+            self.spilled.reserve(Self::CAPACITY * 2);
+            self.spilled.extend_from_slice(&self.plies);
+            self.spilled.push(ply);
+        }
+    }
+
+    /// Removes every move, keeping the heap storage, if any, for later use.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.spilled.clear();
+    }
+
+    /// Keeps only the moves for which `keep` returns `true`, in their original order.
+    pub fn retain(&mut self, mut keep: impl FnMut(&Ply) -> bool) {
+        if !self.spilled.is_empty() {
+            self.spilled.retain(keep);
+
+            // The survivors go back to the stack when they fit, as the list is read from
+            // there whenever `spilled` is empty
+            if self.spilled.len() <= Self::CAPACITY {
+                self.len = self.spilled.len();
+                self.plies[..self.len].copy_from_slice(&self.spilled);
+                self.spilled.clear();
+            }
+            return;
+        }
+
+        let mut kept = 0;
+        for index in 0..self.len {
+            if keep(&self.plies[index]) {
+                self.plies[kept] = self.plies[index];
+                kept += 1;
+            }
+        }
+        self.len = kept;
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::ops::Deref for MoveList {
+    type Target = [Ply];
+
+    fn deref(&self) -> &[Ply] {
+        if self.spilled.is_empty() {
+            &self.plies[..self.len]
+        } else {
+            &self.spilled
+        }
+    }
+}
+
+impl std::ops::DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut [Ply] {
+        if self.spilled.is_empty() {
+            &mut self.plies[..self.len]
+        } else {
+            &mut self.spilled
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Ply;
+    type IntoIter = std::slice::Iter<'a, Ply>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl std::fmt::Debug for MoveList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for MoveList {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for MoveList {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spills_to_the_heap_beyond_capacity() {
+        let mut moves = MoveList::new();
+        let plies: Vec<Ply> = (0..MoveList::CAPACITY + 10)
+            .map(|index| {
+                let square = Coordinate::from_index(index % 64).unwrap();
+                Ply::new_drop(square, Piece::new(Side::White, Kind::Knight))
+            })
+            .collect();
+
+        for ply in &plies {
+            moves.push(*ply);
+        }
+        assert_eq!(*moves, *plies);
+
+        moves.retain(|ply| ply.ending_square().index() < 32);
+        assert!(moves.iter().all(|ply| ply.ending_square().index() < 32));
+
+        moves.clear();
+        assert!(moves.is_empty());
+        moves.push(plies[0]);
+        assert_eq!(*moves, plies[..1]);
+    }
+
+    #[test]
+    fn test_retain_on_a_spilled_list() {
+        let plies: Vec<Ply> = (0..300)
+            .map(|index| {
+                let square = Coordinate::from_index(index % 64).unwrap();
+                let kind = if index < 64 {
+                    Kind::Knight
+                } else {
+                    Kind::Bishop
+                };
+                Ply::new_drop(square, Piece::new(Side::White, kind))
+            })
+            .collect();
+        let spilled = || {
+            let mut moves = MoveList::new();
+            for ply in &plies {
+                moves.push(*ply);
+            }
+            moves
+        };
+
+        let mut moves = spilled();
+        moves.retain(|_| false);
+        assert!(moves.is_empty());
+        moves.push(plies[0]);
+        assert_eq!(*moves, plies[..1]);
+
+        // The survivors fit on the stack again
+        let mut moves = spilled();
+        moves.retain(|ply| ply.piece_moved().kind() == Kind::Knight);
+        assert_eq!(*moves, plies[..64]);
+        moves.push(plies[64]);
+        assert_eq!(*moves, plies[..65]);
+
+        // The survivors still need the heap
+        let mut moves = spilled();
+        moves.retain(|ply| ply.ending_square().index() != 0);
+        assert_eq!(moves.len(), 300 - 5);
+        assert!(moves.iter().all(|ply| ply.ending_square().index() != 0));
+    }
+}
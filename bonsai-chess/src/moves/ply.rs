@@ -76,8 +76,30 @@ impl Ply {
         }
     }
 
+    /// Creates a `Ply` dropping `piece` from its owner's pocket onto `square` (Crazyhouse).
+    ///
+    /// A drop has no origin square, so the ply starts and ends on `square`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// let f3 = Coordinate::from_algebraic_notation("f3").unwrap();
+    /// let ply = Ply::new_drop(f3, Piece::new(Side::White, Kind::Knight));
+    ///
+    /// assert!(ply.is_drop());
+    /// assert_eq!(ply.to_uci(), "N@f3");
+    /// ```
+    #[must_use]
+    pub const fn new_drop(square: Coordinate, piece: Piece) -> Self {
+        Self::new(square, square, piece, None, Some(SpecialMove::Drop))
+    }
+
     /// Returns the coordinate the piece moved from.
     ///
+    /// For a drop, which has no origin, this is the square the piece was dropped on.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -125,6 +147,60 @@ impl Ply {
     pub const fn special_move(&self) -> Option<SpecialMove> {
         self.special_move
     }
+
+    /// Returns `true` if the ply drops a piece from the pocket instead of moving one.
+    #[must_use]
+    pub const fn is_drop(&self) -> bool {
+        matches!(self.special_move, Some(SpecialMove::Drop))
+    }
+
+    /// Formats the ply in the coordinate notation used by the Universal Chess Interface.
+    ///
+    /// The notation is the origin square, the destination square and, for promotions,
+    /// the lowercase letter of the new piece (`e2e4`, `e7e8q`). Castling is written as
+    /// the King's own move (`e1g1`), except for Chess960 castles, which are written as the
+    /// King capturing its own Rook (`b1a1`), as with the `UCI_Chess960` option. Drops are
+    /// written as the uppercase letter of the piece and its square (`N@f3`, `P@e4`).
+    ///
+    /// Unlike SAN, this does not depend on the position, and [`Game::parse_uci_move`](crate::state::Game::parse_uci_move)
+    /// reads it back.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// let mut game = Game::from_starting_position();
+    /// let ply = game.parse_san("Nf3").unwrap();
+    /// assert_eq!(ply.to_uci(), "g1f3");
+    /// ```
+    #[must_use]
+    pub fn to_uci(&self) -> String {
+        if self.is_drop() {
+            return format!("{}@{}", self.moved.kind(), self.to.to_algebraic_notation());
+        }
+
+        let promotion = match self.special_move {
+            Some(SpecialMove::Promotion(promotion)) => promotion.to_string().to_ascii_lowercase(),
+            _ => String::new(),
+        };
+
+        // The King's destination is ambiguous in Chess960 (it may not even move), the Rook is not
+        let to = match self.special_move {
+            Some(SpecialMove::Castle(_, rook_start))
+                if self.from.column() != 4 || !matches!(rook_start.column(), 0 | 7) =>
+            {
+                rook_start
+            }
+            _ => self.to,
+        };
+
+        format!(
+            "{}{}{promotion}",
+            self.from.to_algebraic_notation(),
+            to.to_algebraic_notation()
+        )
+    }
 }
 
 /// Formats the ply using a variant of Long Algebraic Notation (LAN).
@@ -139,9 +215,10 @@ impl Ply {
 /// * `e2-e4` (Pawn push)
 /// * `Ng1xf3` (Knight capture)
 /// * `e7-e8=Q` (Pawn promotion)
+/// * `N@f3` (Knight drop)
 impl std::fmt::Display for Ply {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if let Some(SpecialMove::Castle(castling_side)) = self.special_move {
+        if let Some(SpecialMove::Castle(castling_side, _)) = self.special_move {
             let long_algebraic_notation = match castling_side {
                 crate::moves::CastlingSide::Short => "0-0",
                 crate::moves::CastlingSide::Long => "0-0-0",
@@ -149,7 +226,16 @@ impl std::fmt::Display for Ply {
 
             return write!(f, "{long_algebraic_notation}");
         }
-        
+
+        if self.is_drop() {
+            return write!(
+                f,
+                "{}@{}",
+                self.moved.kind(),
+                self.to.to_algebraic_notation()
+            );
+        }
+
         // Pawns are not denoted by a letter in standard algebraic notation
         let piece = match self.moved.kind() {
             Kind::Pawn => String::new(),
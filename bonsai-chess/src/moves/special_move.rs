@@ -2,8 +2,8 @@
 //!
 //! This module defines the [`SpecialMove`] and [`CastlingSide`] enums, which
 //! represent chess maneuvers that break the standard rules of single-piece
-//! displacement and direct capture. These moves require complex, multi-step
-//! updates to the board state.
+//! displacement and direct capture, including the drops of Crazyhouse. These
+//! moves require complex, multi-step updates to the board state.
 
 use crate::{atoms::Coordinate, pieces::Promotion};
 
@@ -19,8 +19,9 @@ use crate::{atoms::Coordinate, pieces::Promotion};
 /// ```rust
 /// use bonsai_chess::prelude::{Coordinates, ValidPromotions, SpecialMove, CastlingSide};
 ///
-/// // A Kingside castle
-/// let castle = SpecialMove::Castle(CastlingSide::Short);
+/// // A Kingside castle with the h1 Rook
+/// let h1 = Coordinates::from_algebraic_notation("h1").unwrap();
+/// let castle = SpecialMove::Castle(CastlingSide::Short, h1);
 ///
 /// // A pawn promoting to a Queen
 /// let promotion = SpecialMove::Promotion(ValidPromotions::Queen);
@@ -28,14 +29,23 @@ use crate::{atoms::Coordinate, pieces::Promotion};
 /// // An en passant capture, where the captured pawn is on d5
 /// let d5 = Coordinates::from_algebraic_notation("d5").unwrap();
 /// let en_passant = SpecialMove::EnPassant(d5);
+///
+/// // A piece dropped from the pocket
+/// let drop = SpecialMove::Drop;
 /// ```
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SpecialMove {
     /// Castling (King-side or Queen-side).
     ///
-    /// The move is represented as the King's movement. The backend logic must
-    /// identify the corresponding Rook and move it to the correct adjacent square.
-    Castle(CastlingSide),
+    /// The move is represented as the King's movement to the g- or c-file. The
+    /// backend logic moves the Rook to the f- or d-file alongside it.
+    ///
+    /// # Payload
+    /// * [`CastlingSide`]: The side of the board the King castles towards.
+    /// * `Coordinates`: The starting square of the **castling Rook**. In Chess960 the
+    ///   Rook may start on any file, so it cannot be inferred from the side alone.
+    Castle(CastlingSide, Coordinate),
 
     /// En Passant capture.
     ///
@@ -55,30 +65,58 @@ pub enum SpecialMove {
     /// * [`ValidPromotions`]: The specific piece type selected by the player
     ///   (Queen, Rook, Bishop, or Knight).
     Promotion(Promotion),
+
+    /// A piece dropped from the player's pocket onto an empty square (Crazyhouse).
+    ///
+    /// A drop has no origin square: the [`Ply`](crate::moves::Ply) starts and ends on the
+    /// square the piece lands on, and the backend only places the piece there.
+    Drop,
 }
 
 /// Represents the side of the board where the king castled.
 ///
-/// Castling involves the King moving two squares towards a Rook, and the Rook
-/// jumping over the King to the adjacent square.
+/// Castling involves the King moving towards a Rook, and the Rook jumping over
+/// the King to the adjacent square. In standard chess the King moves two squares;
+/// in Chess960 it always lands on the g-file (short) or c-file (long).
 ///
 /// # Examples
 ///
 /// ```rust
-/// use bonsai_chess::prelude::{CastlingSide, SpecialMove};
+/// use bonsai_chess::prelude::{CastlingSide, Coordinate, SpecialMove};
 ///
 /// let side = CastlingSide::Short;
-/// let move_type = SpecialMove::Castle(side);
+/// let move_type = SpecialMove::Castle(side, Coordinate::from_algebraic_notation("h8").unwrap());
 /// ```
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CastlingSide {
     /// Kingside castling (O-O).
     ///
-    /// The King moves two squares towards the h-file Rook.
+    /// The King lands on the g-file and the Rook on the f-file.
     Short,
 
     /// Queenside castling (O-O-O).
     ///
-    /// The King moves two squares towards the a-file Rook.
+    /// The King lands on the c-file and the Rook on the d-file.
     Long,
 }
+
+impl CastlingSide {
+    /// Returns the column (0-7 for a-h) the King lands on when castling on this side.
+    #[must_use]
+    pub const fn king_destination_column(self) -> usize {
+        match self {
+            Self::Short => 6,
+            Self::Long => 2,
+        }
+    }
+
+    /// Returns the column (0-7 for a-h) the Rook lands on when castling on this side.
+    #[must_use]
+    pub const fn rook_destination_column(self) -> usize {
+        match self {
+            Self::Short => 5,
+            Self::Long => 3,
+        }
+    }
+}
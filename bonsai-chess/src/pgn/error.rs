@@ -0,0 +1,95 @@
+//! # PGN Errors
+//!
+//! Every error found while reading PGN carries the line it was found on, counted
+//! from the start of the input.
+
+use crate::state::{FenParsingError, SanError};
+
+/// An error found while reading PGN, and the line it was found on.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PgnError {
+    line: usize,
+    kind: PgnErrorKind,
+}
+
+/// The reasons why PGN can fail to be read.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PgnErrorKind {
+    /// The input does not contain a game.
+    Empty,
+    /// The underlying reader failed, with the message of its error.
+    Io(String),
+    /// A character that cannot start any PGN token.
+    UnexpectedCharacter(char),
+    /// A quoted string is not closed before the end of its line.
+    UnterminatedString,
+    /// A `{` comment is never closed.
+    UnterminatedComment,
+    /// A Numeric Annotation Glyph that is out of range or unknown.
+    InvalidNag(String),
+    /// A tag pair is not of the form `[Name "value"]`.
+    MalformedTag,
+    /// A token that is not allowed where it appears.
+    UnexpectedToken(String),
+    /// A `(` is never closed.
+    UnterminatedVariation,
+    /// A `)` without a matching `(`.
+    UnmatchedParenthesis,
+    /// A variation that does not follow a move it could be an alternative to.
+    VariationWithoutMove,
+    /// The `FEN` tag does not describe a valid position.
+    InvalidFen(FenParsingError),
+    /// A move that cannot be played in its position.
+    InvalidMove(SanError),
+    /// A move that comes after the game ended (checkmate, stalemate or a forced draw).
+    MoveAfterGameEnd(String),
+}
+
+impl PgnError {
+    /// Creates an error found on `line`.
+    #[must_use]
+    pub const fn new(line: usize, kind: PgnErrorKind) -> Self {
+        Self { line, kind }
+    }
+
+    /// Returns the line the error was found on, starting at 1.
+    #[must_use]
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the reason of the error.
+    #[must_use]
+    pub const fn kind(&self) -> &PgnErrorKind {
+        &self.kind
+    }
+}
+
+impl std::fmt::Display for PgnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl std::fmt::Display for PgnErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "No game found"),
+            Self::Io(error) => write!(f, "I/O error: {error}"),
+            Self::UnexpectedCharacter(c) => write!(f, "Unexpected character '{c}'"),
+            Self::UnterminatedString => write!(f, "Unterminated string"),
+            Self::UnterminatedComment => write!(f, "Unterminated comment"),
+            Self::InvalidNag(nag) => write!(f, "Invalid annotation glyph {nag}"),
+            Self::MalformedTag => write!(f, "Malformed tag pair"),
+            Self::UnexpectedToken(token) => write!(f, "Unexpected token {token}"),
+            Self::UnterminatedVariation => write!(f, "Unterminated variation"),
+            Self::UnmatchedParenthesis => write!(f, "Unmatched ')'"),
+            Self::VariationWithoutMove => write!(f, "Variation does not follow a move"),
+            Self::InvalidFen(error) => write!(f, "Invalid FEN tag: {error}"),
+            Self::InvalidMove(error) => write!(f, "{error}"),
+            Self::MoveAfterGameEnd(san) => write!(f, "Move {san} played after the game ended"),
+        }
+    }
+}
+
+impl std::error::Error for PgnError {}
@@ -0,0 +1,213 @@
+//! # PGN Game
+//!
+//! The data read from a single PGN game: its tags, its annotated mainline with
+//! any nested variations, its result, and the position reached at the end of
+//! the mainline.
+
+use crate::{atoms::Side, moves::Ply, rules::Outcome, state::Game};
+
+/// The names of the Seven Tag Roster, which every PGN game is expected to have, in their standard order.
+pub const SEVEN_TAG_ROSTER: [&str; 7] =
+    ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+/// A game read from PGN.
+#[derive(Clone, Debug)]
+pub struct PgnGame {
+    pub(crate) tags: Vec<(String, String)>,
+    pub(crate) mainline: Variation,
+    pub(crate) result: String,
+    pub(crate) starting_position: Game,
+    pub(crate) game: Game,
+}
+
+/// A sequence of moves, with the comments that precede its first move.
+///
+/// Both the mainline of a game and the alternatives given in `( )` are variations.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Variation {
+    pub(crate) comments: Vec<String>,
+    pub(crate) moves: Vec<PgnMove>,
+}
+
+/// A move of a PGN game, with the annotations that follow it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PgnMove {
+    pub(crate) ply: Ply,
+    pub(crate) nags: Vec<u8>,
+    pub(crate) comments: Vec<String>,
+    pub(crate) variations: Vec<Variation>,
+}
+
+impl PgnGame {
+    /// Creates a PGN game from the moves played in `game`.
+    ///
+    /// The tags are the Seven Tag Roster, with unknown values set to `?` and the result
+    /// taken from the outcome of the game, followed by `SetUp` and `FEN` when the game did not
+    /// start from the standard position. Use [`set_tag`](Self::set_tag) to fill them in.
+    #[must_use]
+    pub fn from_game(game: &Game) -> Self {
+        let moves = game.get_move_log();
+
+        let mut starting_position = game.clone();
+        for _ in &moves {
+            starting_position.undo_last_move();
+        }
+
+        let result = match game.outcome() {
+            Some(Outcome::Win {
+                winner: Side::White,
+                ..
+            }) => "1-0",
+            Some(Outcome::Win {
+                winner: Side::Black,
+                ..
+            }) => "0-1",
+            Some(Outcome::Draw { .. }) => "1/2-1/2",
+            None => "*",
+        };
+
+        let tags = Self::default_tags(&starting_position);
+
+        let mut pgn_game = Self {
+            tags,
+            mainline: Variation {
+                comments: Vec::new(),
+                moves: moves
+                    .into_iter()
+                    .map(|ply| PgnMove {
+                        ply,
+                        nags: Vec::new(),
+                        comments: Vec::new(),
+                        variations: Vec::new(),
+                    })
+                    .collect(),
+            },
+            result: result.to_string(),
+            starting_position,
+            game: game.clone(),
+        };
+        pgn_game.set_tag("Result", result);
+
+        pgn_game
+    }
+
+    /// Returns the Seven Tag Roster with unknown values, followed by `SetUp` and `FEN` when
+    /// `starting_position` is not the standard one.
+    pub(crate) fn default_tags(starting_position: &Game) -> Vec<(String, String)> {
+        let mut tags: Vec<(String, String)> = SEVEN_TAG_ROSTER
+            .iter()
+            .map(|name| (name.to_string(), String::from("?")))
+            .collect();
+
+        let starting_fen = starting_position.to_fen();
+        if starting_fen != Game::from_starting_position().to_fen() {
+            tags.push((String::from("SetUp"), String::from("1")));
+            tags.push((String::from("FEN"), starting_fen));
+        }
+
+        tags
+    }
+
+    /// Sets the value of the tag named `name`, adding it after the existing tags if it is new.
+    ///
+    /// Setting the `Result` tag also sets the game termination marker.
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        if name == "Result" {
+            self.result = value.to_string();
+        }
+
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, old_value)) => *old_value = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// Returns all the tag pairs, in the order they were read.
+    #[must_use]
+    pub fn tags(&self) -> &[(String, String)] {
+        &self.tags
+    }
+
+    /// Returns the value of the tag named `name`, if present.
+    ///
+    /// Tag names are case sensitive, as the standard requires.
+    #[must_use]
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the mainline of the game.
+    #[must_use]
+    pub const fn mainline(&self) -> &Variation {
+        &self.mainline
+    }
+
+    /// Returns the game termination marker (`1-0`, `0-1`, `1/2-1/2` or `*`).
+    ///
+    /// When the movetext has no marker, the `Result` tag is used instead.
+    #[must_use]
+    pub fn result(&self) -> &str {
+        &self.result
+    }
+
+    /// Returns the game as it stood before the first move of the mainline.
+    #[must_use]
+    pub const fn starting_position(&self) -> &Game {
+        &self.starting_position
+    }
+
+    /// Returns the game as it stands after replaying the mainline.
+    #[must_use]
+    pub const fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Consumes the PGN game and returns the game as it stands after replaying the mainline.
+    #[must_use]
+    pub fn into_game(self) -> Game {
+        self.game
+    }
+}
+
+impl Variation {
+    /// Returns the comments that come before the first move.
+    #[must_use]
+    pub fn comments(&self) -> &[String] {
+        &self.comments
+    }
+
+    /// Returns the moves of the variation, in order.
+    #[must_use]
+    pub fn moves(&self) -> &[PgnMove] {
+        &self.moves
+    }
+}
+
+impl PgnMove {
+    /// Returns the move played.
+    #[must_use]
+    pub const fn ply(&self) -> Ply {
+        self.ply
+    }
+
+    /// Returns the Numeric Annotation Glyphs of the move, with suffixes like `!?` converted to their number.
+    #[must_use]
+    pub fn nags(&self) -> &[u8] {
+        &self.nags
+    }
+
+    /// Returns the comments that follow the move.
+    #[must_use]
+    pub fn comments(&self) -> &[String] {
+        &self.comments
+    }
+
+    /// Returns the alternatives to this move, each starting from the position before it.
+    #[must_use]
+    pub fn variations(&self) -> &[Variation] {
+        &self.variations
+    }
+}
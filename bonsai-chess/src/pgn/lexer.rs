@@ -0,0 +1,174 @@
+//! # PGN Tokenizer
+//!
+//! Splits the text of a PGN game into the tokens defined by the PGN standard,
+//! remembering the line each token starts on so errors can point at it.
+
+use crate::pgn::{PgnError, PgnErrorKind};
+
+/// A lexical unit of a PGN game.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    /// `[`, opening a tag pair.
+    TagOpen,
+    /// `]`, closing a tag pair.
+    TagClose,
+    /// A quoted string, with its escapes resolved.
+    String(String),
+    /// A move, a move number, a tag name or a game termination marker.
+    Symbol(String),
+    /// A Numeric Annotation Glyph, written `$n` or as a suffix like `!?`.
+    Nag(u8),
+    /// The text of a `{ }` or `;` comment, trimmed.
+    Comment(String),
+    /// `(`, opening a variation.
+    VariationOpen,
+    /// `)`, closing a variation.
+    VariationClose,
+}
+
+/// A token and the line it starts on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub line: usize,
+}
+
+/// Tokenizes `text`, whose first line is numbered `first_line`.
+///
+/// Lines starting with `%` are escaped lines and are skipped, as the standard requires.
+pub fn tokenize(text: &str, first_line: usize) -> Result<Vec<Token>, PgnError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line = first_line;
+    let mut at_line_start = true;
+
+    while let Some(c) = chars.next() {
+        let token_line = line;
+        let error = |kind| PgnError::new(token_line, kind);
+
+        if c == '\n' {
+            line += 1;
+            at_line_start = true;
+            continue;
+        }
+        if c == '%' && at_line_start {
+            skip_line(&mut chars);
+            continue;
+        }
+        at_line_start = false;
+
+        let kind = match c {
+            _ if c.is_whitespace() => continue,
+            '[' => TokenKind::TagOpen,
+            ']' => TokenKind::TagClose,
+            '(' => TokenKind::VariationOpen,
+            ')' => TokenKind::VariationClose,
+            '*' => TokenKind::Symbol(String::from('*')),
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') if matches!(chars.peek(), Some('"' | '\\')) => {
+                            value.extend(chars.next());
+                        }
+                        Some('\n') | None => {
+                            return Err(error(PgnErrorKind::UnterminatedString));
+                        }
+                        Some(c) => value.push(c),
+                    }
+                }
+                TokenKind::String(value)
+            }
+            '{' => {
+                let mut comment = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            comment.push(c);
+                        }
+                        None => return Err(error(PgnErrorKind::UnterminatedComment)),
+                    }
+                }
+                TokenKind::Comment(comment.trim().to_string())
+            }
+            ';' => TokenKind::Comment(skip_line(&mut chars).trim().to_string()),
+            '$' => {
+                let mut digits = String::new();
+                while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                    digits.push(digit);
+                }
+                let nag = digits
+                    .parse()
+                    .map_err(|_| error(PgnErrorKind::InvalidNag(format!("${digits}"))))?;
+                TokenKind::Nag(nag)
+            }
+            '!' | '?' => {
+                let mut glyph = String::from(c);
+                while let Some(c) = chars.next_if(|c| matches!(c, '!' | '?')) {
+                    glyph.push(c);
+                }
+                let nag = match glyph.as_str() {
+                    "!" => 1,
+                    "?" => 2,
+                    "!!" => 3,
+                    "??" => 4,
+                    "!?" => 5,
+                    "?!" => 6,
+                    _ => return Err(error(PgnErrorKind::InvalidNag(glyph))),
+                };
+                TokenKind::Nag(nag)
+            }
+            _ if c.is_ascii_alphanumeric() => {
+                let mut symbol = String::from(c);
+                while let Some(c) = chars.next_if(|&c| is_symbol_continuation(c)) {
+                    symbol.push(c);
+                }
+                TokenKind::Symbol(symbol)
+            }
+            _ => return Err(error(PgnErrorKind::UnexpectedCharacter(c))),
+        };
+
+        tokens.push(Token {
+            kind,
+            line: token_line,
+        });
+    }
+
+    Ok(tokens)
+}
+
+/// Returns `true` for the characters that can continue a symbol.
+///
+/// On top of the standard set, `.` is accepted so that `1.e4` and `exd6e.p.` stay a single token.
+const fn is_symbol_continuation(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '#' | '=' | ':' | '-' | '/' | '.')
+}
+
+/// Consumes the rest of the line, without its newline, and returns it.
+fn skip_line(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    let mut rest = String::new();
+    while let Some(c) = chars.next_if(|&c| c != '\n') {
+        rest.push(c);
+    }
+    rest
+}
+
+impl std::fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TagOpen => write!(f, "'['"),
+            Self::TagClose => write!(f, "']'"),
+            Self::String(value) => write!(f, "\"{value}\""),
+            Self::Symbol(symbol) => write!(f, "{symbol}"),
+            Self::Nag(nag) => write!(f, "${nag}"),
+            Self::Comment(comment) => write!(f, "{{{comment}}}"),
+            Self::VariationOpen => write!(f, "'('"),
+            Self::VariationClose => write!(f, "')'"),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! # Portable Game Notation
+//!
+//! This module reads and writes games in Portable Game Notation (PGN), the standard
+//! text format for recording chess games.
+//!
+//! Reading happens in two steps:
+//! * **Tokenizing**: The text is split into tags, strings, symbols, comments,
+//!   annotation glyphs and parentheses, each remembering its line.
+//! * **Parsing**: The tokens are assembled into a [`PgnGame`], replaying every move
+//!   so that the notation is checked against the actual position.
+//!
+//! Databases holding many games are read lazily with a [`PgnReader`], and written
+//! with a [`PgnWriter`].
+
+/// The errors that can occur while reading PGN.
+mod error;
+
+/// The data of a game read from PGN: tags, annotated moves and variations.
+mod game;
+
+/// Splits PGN text into tokens.
+mod lexer;
+
+/// Assembles tokens into a [`PgnGame`].
+mod parser;
+
+/// Iterates over the games of a PGN database.
+mod reader;
+
+/// Writes games in the PGN export format.
+mod writer;
+
+pub use error::{PgnError, PgnErrorKind};
+pub use game::{PgnGame, PgnMove, SEVEN_TAG_ROSTER, Variation};
+pub use reader::PgnReader;
+pub use writer::PgnWriter;
@@ -0,0 +1,345 @@
+//! # PGN Parser
+//!
+//! Reads the tokens of a single game: the tag pair section, then the movetext.
+//! Moves are resolved against the position they are played in, so the mainline
+//! is replayed through [`Game::make_move`] and every variation is replayed from
+//! the position before the move it is an alternative to.
+
+use crate::{
+    pgn::{
+        PgnError, PgnErrorKind, PgnGame, PgnMove, Variation,
+        lexer::{Token, TokenKind, tokenize},
+    },
+    state::Game,
+};
+
+/// The game termination markers.
+const RESULTS: [&str; 4] = ["1-0", "0-1", "1/2-1/2", "*"];
+
+impl PgnGame {
+    /// Reads a single game in Portable Game Notation.
+    ///
+    /// The tag pair section is optional. When it has a `FEN` tag, the game starts from
+    /// that position instead of the standard one. The movetext can contain move numbers,
+    /// `{ }` and `;` comments, annotation glyphs (`$n`, `!`, `?!`, ...) and nested
+    /// `( )` variations, and may end with a game termination marker.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PgnError`] with the line of the first problem found, which can be a
+    /// syntax error, an invalid `FEN` tag, or an illegal or ambiguous move anywhere
+    /// in the mainline or in a variation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// let pgn = r#"[Event "Casual game"]
+    /// [White "Alice"]
+    /// [Black "Bob"]
+    ///
+    /// 1. e4 e5 2. Qh5?! Nc6 3. Bc4 Nf6?? (3... g6 4. Qf3) 4. Qxf7# 1-0"#;
+    ///
+    /// let pgn_game = PgnGame::parse(pgn).unwrap();
+    /// assert_eq!(pgn_game.tag("White"), Some("Alice"));
+    /// assert_eq!(pgn_game.result(), "1-0");
+    /// assert_eq!(pgn_game.mainline().moves().len(), 7);
+    /// assert!(pgn_game.game().outcome().is_some_and(|outcome| outcome.is_win()));
+    ///
+    /// let error = PgnGame::parse("1. e4 e5\n2. Ke3").unwrap_err();
+    /// assert_eq!(error.line(), 2);
+    /// ```
+    pub fn parse(pgn: &str) -> Result<Self, PgnError> {
+        Self::parse_from_line(pgn, 1)
+    }
+
+    /// Reads a single game whose first line is numbered `first_line` in the input it comes from.
+    pub(super) fn parse_from_line(pgn: &str, first_line: usize) -> Result<Self, PgnError> {
+        let tokens = tokenize(pgn, first_line)?;
+        if tokens.is_empty() {
+            return Err(PgnError::new(first_line, PgnErrorKind::Empty));
+        }
+
+        let mut parser = Parser {
+            tokens: tokens.into_iter().peekable(),
+            result: None,
+        };
+
+        let tags = parser.parse_tags()?;
+        let starting_position = starting_position(&tags)?;
+        let mut game = starting_position.clone();
+        let mainline = parser.parse_variation(&mut game, None)?;
+
+        if let Some(token) = parser.tokens.next() {
+            return Err(unexpected(&token));
+        }
+
+        let result = parser
+            .result
+            .or_else(|| {
+                tags.iter()
+                    .find(|(name, _, _)| name == "Result")
+                    .map(|(_, value, _)| value.clone())
+            })
+            .unwrap_or_else(|| String::from("*"));
+
+        Ok(Self {
+            tags: tags
+                .into_iter()
+                .map(|(name, value, _)| (name, value))
+                .collect(),
+            mainline,
+            result,
+            starting_position,
+            game,
+        })
+    }
+}
+
+/// A tag pair and the line it was read on.
+type Tag = (String, String, usize);
+
+struct Parser {
+    tokens: std::iter::Peekable<std::vec::IntoIter<Token>>,
+    result: Option<String>,
+}
+
+impl Parser {
+    /// Reads the tag pair section.
+    fn parse_tags(&mut self) -> Result<Vec<Tag>, PgnError> {
+        let mut tags = Vec::new();
+
+        while let Some(open) = self
+            .tokens
+            .next_if(|token| token.kind == TokenKind::TagOpen)
+        {
+            let name = self.tokens.next().map(|token| token.kind);
+            let value = self.tokens.next().map(|token| token.kind);
+            let close = self.tokens.next().map(|token| token.kind);
+
+            let (
+                Some(TokenKind::Symbol(name)),
+                Some(TokenKind::String(value)),
+                Some(TokenKind::TagClose),
+            ) = (name, value, close)
+            else {
+                return Err(PgnError::new(open.line, PgnErrorKind::MalformedTag));
+            };
+
+            tags.push((name, value, open.line));
+        }
+
+        Ok(tags)
+    }
+
+    /// Reads moves and their annotations, playing them on `game`.
+    ///
+    /// `opening_line` is the line of the `(` for variations, and `None` for the mainline,
+    /// which is the only one allowed to end with a game termination marker.
+    fn parse_variation(
+        &mut self,
+        game: &mut Game,
+        opening_line: Option<usize>,
+    ) -> Result<Variation, PgnError> {
+        let mut variation = Variation::default();
+
+        loop {
+            let Some(token) = self.tokens.next() else {
+                return opening_line.map_or(Ok(variation), |line| {
+                    Err(PgnError::new(line, PgnErrorKind::UnterminatedVariation))
+                });
+            };
+
+            match token.kind {
+                TokenKind::Comment(comment) => match variation.moves.last_mut() {
+                    Some(last) => last.comments.push(comment),
+                    None => variation.comments.push(comment),
+                },
+                TokenKind::Nag(nag) => match variation.moves.last_mut() {
+                    Some(last) => last.nags.push(nag),
+                    None => return Err(unexpected(&token)),
+                },
+                TokenKind::VariationOpen => {
+                    let Some(last) = variation.moves.last_mut() else {
+                        return Err(PgnError::new(
+                            token.line,
+                            PgnErrorKind::VariationWithoutMove,
+                        ));
+                    };
+
+                    let mut alternative = game.clone();
+                    alternative.undo_last_move();
+                    last.variations
+                        .push(self.parse_variation(&mut alternative, Some(token.line))?);
+                }
+                TokenKind::VariationClose => {
+                    return match opening_line {
+                        Some(_) => Ok(variation),
+                        None => Err(PgnError::new(
+                            token.line,
+                            PgnErrorKind::UnmatchedParenthesis,
+                        )),
+                    };
+                }
+                TokenKind::Symbol(ref symbol) if RESULTS.contains(&symbol.as_str()) => {
+                    if opening_line.is_some() {
+                        return Err(unexpected(&token));
+                    }
+                    self.result = Some(symbol.clone());
+                    return Ok(variation);
+                }
+                TokenKind::Symbol(ref symbol) => {
+                    let san = strip_move_number(symbol);
+
+                    // Move numbers, and en passant markers written apart from their move
+                    if san.is_empty() || san == "e.p." {
+                        continue;
+                    }
+
+                    if game.outcome().is_some() {
+                        return Err(PgnError::new(
+                            token.line,
+                            PgnErrorKind::MoveAfterGameEnd(san.to_string()),
+                        ));
+                    }
+
+                    let ply = game.parse_san(san).map_err(|error| {
+                        PgnError::new(token.line, PgnErrorKind::InvalidMove(error))
+                    })?;
+                    game.make_move(&ply);
+
+                    variation.moves.push(PgnMove {
+                        ply,
+                        nags: Vec::new(),
+                        comments: Vec::new(),
+                        variations: Vec::new(),
+                    });
+                }
+                TokenKind::TagOpen | TokenKind::TagClose | TokenKind::String(_) => {
+                    return Err(unexpected(&token));
+                }
+            }
+        }
+    }
+}
+
+/// Builds the starting position from the `FEN` tag, or the standard one without it.
+fn starting_position(tags: &[Tag]) -> Result<Game, PgnError> {
+    match tags.iter().find(|(name, _, _)| name == "FEN") {
+        Some((_, fen, line)) => Game::try_from_fen(fen)
+            .map_err(|error| PgnError::new(*line, PgnErrorKind::InvalidFen(error))),
+        None => Ok(Game::from_starting_position()),
+    }
+}
+
+/// Removes a leading move number (`12.`, `12...`) from a symbol.
+///
+/// A bare move number becomes empty, and symbols without one, like `0-0`, are returned unchanged.
+fn strip_move_number(symbol: &str) -> &str {
+    let rest = symbol.trim_start_matches(|c: char| c.is_ascii_digit());
+
+    if rest.len() == symbol.len() || !(rest.is_empty() || rest.starts_with('.')) {
+        symbol
+    } else {
+        rest.trim_start_matches('.')
+    }
+}
+
+fn unexpected(token: &Token) -> PgnError {
+    PgnError::new(
+        token.line,
+        PgnErrorKind::UnexpectedToken(token.kind.to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        pgn::{PgnErrorKind, PgnGame},
+        state::SanError,
+    };
+
+    #[test]
+    fn test_annotations_and_nested_variations() {
+        let pgn = r#"[Event "Annotated"]
+[Annotator "Someone \"quoted\""]
+% An escaped line, ignored
+{Opening comment} 1. e4 $1 e5!? ; rest of the line
+2. Nf3 (2. f4 exf4 (2... d5) 3. Nf3) 2... Nc6 {A comment; not a line comment}
+3.Bb5 a6 *"#;
+
+        let pgn_game = PgnGame::parse(pgn).unwrap();
+        assert_eq!(pgn_game.tag("Annotator"), Some(r#"Someone "quoted""#));
+        assert_eq!(pgn_game.result(), "*");
+
+        let mainline = pgn_game.mainline();
+        assert_eq!(mainline.comments(), ["Opening comment"]);
+        assert_eq!(mainline.moves().len(), 6);
+        assert_eq!(mainline.moves()[0].nags(), [1]);
+        assert_eq!(mainline.moves()[1].nags(), [5]);
+        assert_eq!(mainline.moves()[1].comments(), ["rest of the line"]);
+
+        let king_gambit = &mainline.moves()[2].variations()[0];
+        assert_eq!(king_gambit.moves().len(), 3);
+        assert_eq!(
+            king_gambit.moves()[1].variations()[0].moves()[0]
+                .ply()
+                .to_uci(),
+            "d7d5"
+        );
+    }
+
+    #[test]
+    fn test_fen_tag_and_result_tag() {
+        let pgn = r#"[SetUp "1"]
+[FEN "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1"]
+[Result "1/2-1/2"]
+
+1. exd6 e.p. Kd7"#;
+
+        let pgn_game = PgnGame::parse(pgn).unwrap();
+        assert_eq!(pgn_game.result(), "1/2-1/2");
+        assert_eq!(pgn_game.game().to_fen(), "8/3k4/3P4/8/8/8/8/4K3 w - - 1 2");
+    }
+
+    #[test]
+    fn test_errors_report_their_line() {
+        let error = PgnGame::parse("1. e4 e5\n2. Nf3 Nc6\n3. Nd5 (3. Ng6) *").unwrap_err();
+        assert_eq!(error.line(), 3);
+        assert_eq!(
+            *error.kind(),
+            PgnErrorKind::InvalidMove(SanError::IllegalMove("Nd5".to_string()))
+        );
+
+        let error = PgnGame::parse(
+            "[FEN \"4k3/8/8/8/8/8/4K3/R6R w - - 0 1\"]\n1. Ra2 (1. Kd3)\n(1. Rd1) *",
+        )
+        .unwrap_err();
+        assert_eq!(error.line(), 3);
+        assert!(matches!(
+            error.kind(),
+            PgnErrorKind::InvalidMove(SanError::AmbiguousMove(_))
+        ));
+
+        let cases = [
+            ("", PgnErrorKind::Empty),
+            ("[Event]\n1. e4", PgnErrorKind::MalformedTag),
+            ("1. e4 {never closed", PgnErrorKind::UnterminatedComment),
+            ("1. e4 e5 (1... c5", PgnErrorKind::UnterminatedVariation),
+            ("1. e4 e5)", PgnErrorKind::UnmatchedParenthesis),
+            ("(1. d4) 1. e4", PgnErrorKind::VariationWithoutMove),
+            (
+                "1. e4 1-0 e5",
+                PgnErrorKind::UnexpectedToken("e5".to_string()),
+            ),
+            (
+                "1. f3 e5 2. g4 Qh4# 3. a3",
+                PgnErrorKind::MoveAfterGameEnd("a3".to_string()),
+            ),
+        ];
+        for (pgn, kind) in cases {
+            assert_eq!(*PgnGame::parse(pgn).unwrap_err().kind(), kind, "{pgn}");
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! # Streaming PGN Reader
+//!
+//! Reads a PGN database one game at a time from any [`BufRead`] source, so that
+//! archives far larger than memory can be processed.
+//!
+//! Games are split on the text level before being parsed: a game ends when a tag
+//! pair line follows its movetext, or at the end of the input. A game that fails to
+//! parse therefore does not prevent reading the ones after it.
+
+use std::io::BufRead;
+
+use crate::pgn::{PgnError, PgnErrorKind, PgnGame};
+
+/// An iterator over the games of a PGN database.
+///
+/// Each item is either a game or the error that made it unreadable. Errors in a game
+/// do not stop the iteration: skipping an `Err` moves on to the next game. Only an I/O
+/// error of the underlying reader ends it, after being yielded once.
+///
+/// Line numbers in errors count from the start of the whole input.
+///
+/// # Examples
+///
+/// ```rust
+/// use bonsai_chess::prelude::*;
+///
+/// let database = "[Event \"First\"]\n\n1. e4 e5 1-0\n\n\
+///                 [Event \"Broken\"]\n\n1. e4 e4 *\n\n\
+///                 [Event \"Third\"]\n\n1. d4 0-1\n";
+///
+/// let games: Vec<_> = PgnReader::new(database.as_bytes()).collect();
+/// assert_eq!(games.len(), 3);
+/// assert_eq!(games[0].as_ref().unwrap().tag("Event"), Some("First"));
+/// assert_eq!(games[1].as_ref().unwrap_err().line(), 7);
+/// assert_eq!(games[2].as_ref().unwrap().result(), "0-1");
+/// ```
+#[derive(Debug)]
+pub struct PgnReader<R> {
+    reader: R,
+    /// The number of lines read so far.
+    line: usize,
+    /// A tag pair line read past the end of the previous game, which starts the next one.
+    pending: Option<String>,
+    /// Set after an I/O error, which ends the iteration.
+    failed: bool,
+}
+
+impl<R: BufRead> PgnReader<R> {
+    /// Creates a reader over the games of `reader`.
+    #[must_use]
+    pub const fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: 0,
+            pending: None,
+            failed: false,
+        }
+    }
+
+    /// Reads the next line of the input, or `None` at its end.
+    fn read_line(&mut self) -> Result<Option<String>, PgnError> {
+        let mut line = String::new();
+
+        match self.reader.read_line(&mut line) {
+            Ok(0) => Ok(None),
+            Ok(_) => {
+                self.line += 1;
+                if self.line == 1 {
+                    // Byte order marks are common at the start of files saved on Windows
+                    line = line.trim_start_matches('\u{feff}').to_string();
+                }
+                Ok(Some(line))
+            }
+            Err(error) => Err(PgnError::new(
+                self.line + 1,
+                PgnErrorKind::Io(error.to_string()),
+            )),
+        }
+    }
+
+    /// Reads the text of the next game and the number of its first line, or `None` at the end of the input.
+    fn next_game_text(&mut self) -> Result<Option<(String, usize)>, PgnError> {
+        let mut text = String::new();
+        let mut first_line = None;
+        let mut in_movetext = false;
+        let mut in_comment = false;
+
+        loop {
+            let line = if let Some(line) = self.pending.take() {
+                line
+            } else if let Some(line) = self.read_line()? {
+                line
+            } else {
+                break;
+            };
+
+            let trimmed = line.trim_start();
+            let is_tag = !in_comment && trimmed.starts_with('[');
+
+            if is_tag && in_movetext {
+                self.pending = Some(line);
+                break;
+            }
+            if first_line.is_none() {
+                if trimmed.is_empty() {
+                    continue;
+                }
+                first_line = Some(self.line);
+            }
+
+            if !in_comment && !is_tag && !trimmed.is_empty() && !trimmed.starts_with('%') {
+                in_movetext = true;
+            }
+            in_comment = ends_in_comment(&line, in_comment);
+            text.push_str(&line);
+        }
+
+        Ok(first_line.map(|first_line| (text, first_line)))
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        match self.next_game_text() {
+            Ok(Some((text, first_line))) => Some(PgnGame::parse_from_line(&text, first_line)),
+            Ok(None) => None,
+            Err(error) => {
+                self.failed = true;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+/// Returns `true` if a `{` comment is still open at the end of `line`.
+///
+/// `in_comment` tells whether one was already open at its start. Braces inside strings
+/// and `;` comments do not count.
+fn ends_in_comment(line: &str, mut in_comment: bool) -> bool {
+    let mut in_string = false;
+
+    for c in line.chars() {
+        match c {
+            '}' if in_comment => in_comment = false,
+            _ if in_comment => {}
+            '"' => in_string = !in_string,
+            '{' if !in_string => in_comment = true,
+            ';' if !in_string => break,
+            _ => {}
+        }
+    }
+
+    in_comment
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pgn::{PgnErrorKind, PgnReader};
+
+    #[test]
+    fn test_games_are_split_on_tags_after_movetext() {
+        let database = "\u{feff}[Event \"One\"]\n[Round \"1\"]\n\n\
+                        1. e4 {A comment over\n[two lines]} e5 *\n\
+                        [Event \"Two\"]\n1. d4 d5\n\n\n\
+                        [Event \"Three\"]\n\n1. c4 (1. Nf3\n\n) 1... e5 1/2-1/2";
+
+        let events: Vec<_> = PgnReader::new(database.as_bytes())
+            .map(|game| game.unwrap().tag("Event").unwrap().to_string())
+            .collect();
+
+        assert_eq!(events, ["One", "Two", "Three"]);
+    }
+
+    #[test]
+    fn test_malformed_games_are_skipped() {
+        let database = "1. e4 e5\n\n1. e4 Ke7 Ke2 {never closed\n\n\n[Event \"Last\"]\n1. f4 *";
+
+        let games: Vec<_> = PgnReader::new(database.as_bytes()).collect();
+
+        // The unterminated comment swallows the tag pairs that follow it
+        assert_eq!(games.len(), 1);
+        let error = games[0].as_ref().unwrap_err();
+        assert_eq!(error.line(), 3);
+        assert_eq!(*error.kind(), PgnErrorKind::UnterminatedComment);
+
+        let database = "[Event \"Bad\"]\n1. e5 *\n[Event \"Good\"]\n1. e4 *";
+        let games: Vec<_> = PgnReader::new(database.as_bytes()).collect();
+        assert_eq!(games[0].as_ref().unwrap_err().line(), 2);
+        assert_eq!(games[1].as_ref().unwrap().tag("Event"), Some("Good"));
+    }
+}
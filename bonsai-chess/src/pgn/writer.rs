@@ -0,0 +1,205 @@
+//! # PGN Writer
+//!
+//! Writes games in the export format of the PGN standard: the tag pairs one per
+//! line, a blank line, then the movetext wrapped at 80 columns and ending with the
+//! game termination marker.
+
+use std::io::Write;
+
+use crate::{
+    atoms::Side,
+    pgn::{PgnGame, Variation},
+    state::Game,
+};
+
+/// The longest line allowed in the movetext of the export format.
+const MAX_LINE_LENGTH: usize = 80;
+
+/// Writes games one after another to a [`Write`] sink, such as a file opened for appending.
+///
+/// Every game is followed by a blank line, so the output can be read back by a
+/// [`PgnReader`](crate::pgn::PgnReader) and more games can be appended later.
+///
+/// # Examples
+///
+/// ```rust
+/// use bonsai_chess::prelude::*;
+///
+/// let mut game = Game::from_starting_position();
+/// let ply = game.parse_san("e4").unwrap();
+/// game.make_move(&ply);
+///
+/// let mut writer = PgnWriter::new(Vec::new());
+/// writer.write_game(&PgnGame::from_game(&game)).unwrap();
+///
+/// let pgn = String::from_utf8(writer.into_inner()).unwrap();
+/// assert!(pgn.ends_with("\n\n1. e4 *\n\n"));
+/// ```
+#[derive(Debug)]
+pub struct PgnWriter<W> {
+    writer: W,
+}
+
+impl<W: Write> PgnWriter<W> {
+    /// Creates a writer that appends games to `writer`.
+    #[must_use]
+    pub const fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Writes a game, followed by a blank line.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error of the underlying writer.
+    pub fn write_game(&mut self, game: &PgnGame) -> std::io::Result<()> {
+        write!(self.writer, "{game}\n\n")
+    }
+
+    /// Flushes the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error of the underlying writer.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Consumes the PGN writer and returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Formats the game in the PGN export format, without a trailing newline.
+impl std::fmt::Display for PgnGame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (name, value) in &self.tags {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(f, "[{name} \"{value}\"]")?;
+        }
+        if !self.tags.is_empty() {
+            writeln!(f)?;
+        }
+
+        let mut tokens = Vec::new();
+        movetext_tokens(
+            &self.mainline,
+            &mut self.starting_position.clone(),
+            &mut tokens,
+        );
+        tokens.push(self.result.clone());
+
+        let mut line_length = 0;
+        for token in tokens {
+            if line_length > 0 && line_length + 1 + token.len() > MAX_LINE_LENGTH {
+                writeln!(f)?;
+                line_length = 0;
+            }
+            if line_length > 0 {
+                write!(f, " ")?;
+                line_length += 1;
+            }
+            write!(f, "{token}")?;
+            line_length += token.len();
+        }
+
+        Ok(())
+    }
+}
+
+/// Appends the tokens of `variation`, played from `position`, to `tokens`.
+///
+/// Parentheses are glued to the first and last tokens of a variation, and the
+/// move number is repeated for Black (`5...`) whenever the flow of moves was
+/// interrupted by a comment or a variation.
+fn movetext_tokens(variation: &Variation, position: &mut Game, tokens: &mut Vec<String>) {
+    tokens.extend(
+        variation
+            .comments
+            .iter()
+            .map(|comment| format!("{{{comment}}}")),
+    );
+
+    let mut needs_number = true;
+    for pgn_move in &variation.moves {
+        let fullmove = position.move_counter().fullmove();
+        match position.turn() {
+            Side::White => tokens.push(format!("{fullmove}.")),
+            Side::Black if needs_number => tokens.push(format!("{fullmove}...")),
+            Side::Black => {}
+        }
+
+        tokens.push(position.to_san(&pgn_move.ply));
+        tokens.extend(pgn_move.nags.iter().map(|nag| format!("${nag}")));
+        tokens.extend(
+            pgn_move
+                .comments
+                .iter()
+                .map(|comment| format!("{{{comment}}}")),
+        );
+        needs_number = !pgn_move.comments.is_empty() || !pgn_move.variations.is_empty();
+
+        for alternative in &pgn_move.variations {
+            let first = tokens.len();
+            movetext_tokens(alternative, &mut position.clone(), tokens);
+
+            if tokens.len() == first {
+                tokens.push(String::from("()"));
+            } else {
+                tokens[first].insert(0, '(');
+                if let Some(closing) = tokens.last_mut() {
+                    closing.push(')');
+                }
+            }
+        }
+
+        position.make_move(&pgn_move.ply);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        pgn::{PgnGame, PgnReader, PgnWriter},
+        state::Game,
+    };
+
+    #[test]
+    fn test_written_games_read_back() {
+        let annotated = PgnGame::parse(
+            "[Event \"Annotated \\\"game\\\"\"]\n\n{Start} 1. e4 e5 $1 {Solid} (1... c5 2. Nf3 (2. c3)) \
+             2. Nf3 Nc6 3. Bb5 a6 4. Ba4 Nf6 5. O-O Be7 6. Re1 b5 7. Bb3 d6 8. c3 O-O 9. h3 Nb8 \
+             10. d4 Nbd7 1/2-1/2",
+        )
+        .unwrap();
+
+        let mut played = Game::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 30");
+        for san in ["exd6", "Kd7"] {
+            let ply = played.parse_san(san).unwrap();
+            played.make_move(&ply);
+        }
+        let played = PgnGame::from_game(&played);
+
+        let mut writer = PgnWriter::new(Vec::new());
+        writer.write_game(&annotated).unwrap();
+        writer.write_game(&played).unwrap();
+        let database = String::from_utf8(writer.into_inner()).unwrap();
+
+        assert!(database.lines().all(|line| line.len() <= 80));
+        assert!(database.contains("1. e4 e5 $1 {Solid} (1... c5 2. Nf3 (2. c3)) 2. Nf3"));
+        assert!(
+            database.contains("[FEN \"4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 30\"]\n\n30. exd6 Kd7 *")
+        );
+
+        let games: Vec<_> = PgnReader::new(database.as_bytes())
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].tags(), annotated.tags());
+        assert_eq!(games[0].mainline(), annotated.mainline());
+        assert_eq!(games[0].result(), "1/2-1/2");
+        assert_eq!(games[1].mainline(), played.mainline());
+        assert_eq!(games[1].game().to_fen(), played.game().to_fen());
+    }
+}
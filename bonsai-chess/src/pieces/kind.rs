@@ -8,6 +8,7 @@
 ///
 /// This enum covers all six standard piece types defined in chess.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Kind {
     /// The most important piece. The game ends when it is in check and has no legal moves.
     King,
@@ -24,6 +25,31 @@ pub enum Kind {
 }
 
 impl Kind {
+    /// All six piece kinds, ordered by their [`Kind::index`].
+    pub const ALL: [Self; 6] = [
+        Self::King,
+        Self::Queen,
+        Self::Rook,
+        Self::Bishop,
+        Self::Knight,
+        Self::Pawn,
+    ];
+
+    /// Returns a dense index for the kind, from 0 (King) to 5 (Pawn).
+    ///
+    /// Useful for addressing per-kind lookup tables.
+    #[must_use]
+    pub const fn index(self) -> usize {
+        match self {
+            Self::King => 0,
+            Self::Queen => 1,
+            Self::Rook => 2,
+            Self::Bishop => 3,
+            Self::Knight => 4,
+            Self::Pawn => 5,
+        }
+    }
+
     /// Converts a specific promotion choice back into a general piece `Kind`.
     ///
     /// This is used when a pawn reaches the final rank and transforms into the selected piece.
@@ -71,6 +97,7 @@ impl std::fmt::Display for Kind {
 /// By using a distinct enum, we enforce this restriction at the type system level,
 /// making illegal promotions (like promoting to a King) impossible to represent in safe code.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Promotion {
     /// Promote to a Queen.
     Queen,
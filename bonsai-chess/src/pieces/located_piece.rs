@@ -26,6 +26,7 @@ use crate::{atoms::Coordinate, pieces::Piece};
 /// let located_pawn = LocatedPiece::new(white_pawn, e4);
 /// ```
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LocatedPiece {
     piece: Piece,
     position: Coordinate,
@@ -31,6 +31,7 @@ use crate::atoms::Side;
 /// assert_eq!(describe_result(mate), "White wins by Checkmate");
 /// ```
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Outcome {
     /// The game ended in a decisive win for one team.
     Win {
@@ -138,6 +139,7 @@ impl Outcome {
 
 /// Represents the specific reason a chess game ended with a win.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WinReason {
     /// A player's king is in check and has no legal moves to escape.
     Checkmate,
@@ -163,10 +165,15 @@ pub enum WinReason {
     /// * The opponent arrives more than one hour late for the resumption
     ///   of an adjourned game (Article 10).
     Forfeit,
+
+    /// A player fulfilled a win condition specific to the variant being played, like
+    /// bringing their King to the centre in King of the Hill.
+    VariantWin,
 }
 
 /// Represents the specific reason a chess game ended in a draw.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DrawReason {
     /// A draw declared by forfeit.
     ///
@@ -198,4 +205,8 @@ pub enum DrawReason {
     /// A draw resulting from a timeout, such as a player running out of
     /// time when their opponent has insufficient material to mate.
     DrawOnTime,
+
+    /// A draw specific to the variant being played, like both Kings reaching the
+    /// eighth rank in Racing Kings.
+    VariantDraw,
 }
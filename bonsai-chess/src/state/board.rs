@@ -4,28 +4,22 @@
 //! engine of the chess board. It is responsible for the raw physical placement,
 //! movement, and removal of pieces on the 8x8 grid.
 //!
-//! It also houses the critical, high-performance ray-casting logic used to
-//! determine if squares are under attack and to calculate the [`LegalityContext`]
-//! (pins, checks, and danger zones) for move generation.
+//! Alongside the grid, the board keeps a set of bitboards (one per piece kind and
+//! one per side) in sync with it. These power the high-performance attack lookups
+//! used to determine if squares are under attack and to calculate the
+//! [`LegalityContext`] (pins, checks, and danger zones) for move generation.
 
 use crate::{
-    BOARD_COLUMNS_RANGE, BOARD_ROWS_RANGE,
-    atoms::{Coordinate, Side},
-    moves::{
-        CastlingSide, LegalityContext, Ply, SpecialMove,
-        directions::{
-            DIAGONAL_DIRECTIONS, DIAGONALLY_DOWN_LEFT, DIAGONALLY_DOWN_RIGHT, DIAGONALLY_UP_LEFT,
-            DIAGONALLY_UP_RIGHT, KING_DIRECTIONS, KNIGHT_DIRECTIONS, ORTHOGONAL_DIRECTIONS,
-        },
-    },
+    atoms::{Bitboard, Coordinate, Side},
+    moves::{CheckContext, LegalityContext, Ply, SpecialMove, attacks},
     pieces::{Kind, LocatedPiece, Piece},
-    state::{Grid, Square, positions::STARTING_POSITION},
+    state::{Grid, Square, positions::STARTING_POSITION, zobrist},
 };
 
 /// Manages the low-level state of the chess board (the 8x8 grid).
 ///
 /// `BoardBackend` is responsible for:
-/// * Storing the position of all pieces.
+/// * Storing the position of all pieces, both as a grid and as bitboards.
 /// * Executing moves on the grid (updating coordinates, clearing squares).
 /// * Handling the mechanical side effects of special moves (e.g., moving the rook during a castle).
 /// * Checking if squares are under attack.
@@ -37,6 +31,12 @@ use crate::{
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Board {
     grid: Grid,
+    /// Occupancy of each piece kind, indexed by [`Kind::index`].
+    pieces: [Bitboard; 6],
+    /// Occupancy of each side, indexed by [`Side::index`].
+    sides: [Bitboard; 2],
+    /// Zobrist hash of the piece placement, updated on every `set` and `unset`.
+    hash: u64,
     white_king_location: Coordinate,
     black_king_location: Coordinate,
 }
@@ -46,7 +46,7 @@ impl Board {
     ///
     /// # Panics
     ///
-    /// This function will not panic, the unwrap for `Coordinates` is safe.
+    /// This function will not panic, the starting position has exactly one King per side.
     ///
     /// # Examples
     ///
@@ -57,17 +57,13 @@ impl Board {
     /// ```
     #[must_use]
     pub fn from_starting_position() -> Self {
-        Self {
-            grid: STARTING_POSITION,
-            white_king_location: Coordinate::new(7, 4).unwrap(),
-            black_king_location: Coordinate::new(0, 4).unwrap(),
-        }
+        Self::new(STARTING_POSITION)
     }
 
     /// Creates a new backend from a raw grid.
     ///
-    /// This function scans the grid to locate the Kings and caches their coordinates
-    /// for high-performance lookups later.
+    /// This function scans the grid to locate the Kings and caches their coordinates,
+    /// and builds the bitboards, for high-performance lookups later.
     ///
     /// # Panics
     ///
@@ -78,12 +74,21 @@ impl Board {
     pub fn new(grid: Grid) -> Self {
         let mut white_king_location = None;
         let mut black_king_location = None;
+        let mut pieces = [Bitboard::EMPTY; 6];
+        let mut sides = [Bitboard::EMPTY; 2];
+        let mut hash = 0;
 
         for (row_index, row) in grid.0.iter().enumerate() {
             for (column_index, sq) in row.iter().enumerate() {
                 if let Some(p) = sq {
                     let location = Coordinate::new(row_index, column_index);
 
+                    if let Some(square) = location {
+                        pieces[p.kind().index()].insert(square);
+                        sides[p.team().index()].insert(square);
+                        hash ^= zobrist::piece(*p, square);
+                    }
+
                     if p.kind() == Kind::King {
                         match p.team() {
                             Side::White => {
@@ -116,6 +121,9 @@ impl Board {
 
         Self {
             grid,
+            pieces,
+            sides,
+            hash,
             white_king_location,
             black_king_location,
         }
@@ -128,7 +136,28 @@ impl Board {
     /// * **En Passant**: Removes the captured pawn (which is on a different square than the destination).
     /// * **Castle**: Moves the corresponding Rook to its new position.
     /// * **Promotion**: Replaces the pawn with the promoted piece type.
+    /// * **Drop**: Places the piece on its square, without lifting it from anywhere.
     pub fn make_move(&mut self, ply: &Ply) {
+        if ply.is_drop() {
+            self.set(ply.piece_moved(), ply.ending_square());
+            return;
+        }
+
+        if let Some(SpecialMove::Castle(side, rook_start)) = ply.special_move() {
+            // In Chess960 the King may land on the Rook's starting square (or the
+            // other way around), so both pieces are lifted before being placed.
+            let rook = Piece::new(ply.piece_moved().team(), Kind::Rook);
+            let rook_end = Coordinate::new(rook_start.row(), side.rook_destination_column());
+
+            self.unset(ply.starting_square());
+            self.unset(rook_start);
+            self.set(ply.piece_moved(), ply.ending_square());
+            if let Some(rook_end) = rook_end {
+                self.set(rook, rook_end);
+            }
+            return;
+        }
+
         self.unset(ply.starting_square());
         self.set(ply.piece_moved(), ply.ending_square());
 
@@ -138,40 +167,8 @@ impl Board {
                 SpecialMove::EnPassant(coordinates) => {
                     self.unset(coordinates);
                 }
-                SpecialMove::Castle(side) => {
-                    const LONG_CASTLE_ROOK_START_COLUMN: usize = 0; // (a column)
-                    const LONG_CASTLE_ROOK_END_COLUMN: usize = 3; // (d column)
-
-                    const SHORT_CASTLE_ROOK_START_COLUMN: usize = 7; // (h column)
-                    const SHORT_CASTLE_ROOK_END_COLUMN: usize = 5; // (f column)
-
-                    let (rook_start, rook_end) = match side {
-                        CastlingSide::Short => (
-                            Coordinate::new(
-                                ply.ending_square().row(),
-                                SHORT_CASTLE_ROOK_START_COLUMN,
-                            ),
-                            Coordinate::new(
-                                ply.ending_square().row(),
-                                SHORT_CASTLE_ROOK_END_COLUMN,
-                            ),
-                        ),
-                        CastlingSide::Long => (
-                            Coordinate::new(
-                                ply.ending_square().row(),
-                                LONG_CASTLE_ROOK_START_COLUMN,
-                            ),
-                            Coordinate::new(ply.ending_square().row(), LONG_CASTLE_ROOK_END_COLUMN),
-                        ),
-                    };
-
-                    if let (Some(rook_start), Some(rook_end)) = (rook_start, rook_end)
-                        && let Some(rook_to_move) = self.get(rook_start)
-                    {
-                        self.set(rook_to_move, rook_end);
-                        self.unset(rook_start);
-                    }
-                }
+                // Handled above
+                SpecialMove::Castle(..) | SpecialMove::Drop => {}
                 SpecialMove::Promotion(valid_promotion) => {
                     self.set(
                         Piece::new(
@@ -190,6 +187,26 @@ impl Board {
     /// This is critical for search algorithms (like Minimax) that explore the game tree
     /// by making and unmaking moves sequentially rather than cloning the board state.
     pub fn undo_move(&mut self, ply: &Ply) {
+        if ply.is_drop() {
+            // The dropped piece goes back to the pocket, which the board does not know about
+            self.unset(ply.ending_square());
+            return;
+        }
+
+        if let Some(SpecialMove::Castle(side, rook_start)) = ply.special_move() {
+            // Lift both pieces before putting them back, for the same reason as in `make_move`
+            let rook = Piece::new(ply.piece_moved().team(), Kind::Rook);
+            if let Some(rook_end) =
+                Coordinate::new(rook_start.row(), side.rook_destination_column())
+            {
+                self.unset(rook_end);
+            }
+            self.unset(ply.ending_square());
+            self.set(ply.piece_moved(), ply.starting_square());
+            self.set(rook, rook_start);
+            return;
+        }
+
         // 1. Move the piece back to start
         self.set(ply.piece_moved(), ply.starting_square());
 
@@ -206,45 +223,11 @@ impl Board {
             self.unset(ply.ending_square());
         }
 
-        // 3. Handle Special Move side effects (un-castle, restore captured EP pawn)
+        // 3. Handle Special Move side effects (restore captured EP pawn)
         if let Some(special_move) = ply.special_move() {
             match special_move {
-                SpecialMove::Castle(side) => {
-                    // Now the constants are flipped, we use the *_ROOK_END_COLUMN as the variable rook_start because we are undoing the move
-                    const LONG_CASTLE_ROOK_START_COLUMN: usize = 0; // (a column)
-                    const LONG_CASTLE_ROOK_END_COLUMN: usize = 3; // (d column)
-
-                    const SHORT_CASTLE_ROOK_START_COLUMN: usize = 7; // (h column)
-                    const SHORT_CASTLE_ROOK_END_COLUMN: usize = 5; // (f column)
-
-                    let (rook_start, rook_end) = match side {
-                        CastlingSide::Short => (
-                            Coordinate::new(
-                                ply.ending_square().row(),
-                                SHORT_CASTLE_ROOK_END_COLUMN,
-                            ),
-                            Coordinate::new(
-                                ply.ending_square().row(),
-                                SHORT_CASTLE_ROOK_START_COLUMN,
-                            ),
-                        ),
-                        CastlingSide::Long => (
-                            Coordinate::new(ply.ending_square().row(), LONG_CASTLE_ROOK_END_COLUMN),
-                            Coordinate::new(
-                                ply.ending_square().row(),
-                                LONG_CASTLE_ROOK_START_COLUMN,
-                            ),
-                        ),
-                    };
-
-                    if let (Some(rook_start), Some(rook_end)) = (rook_start, rook_end)
-                        && let Some(rook_to_move) = self.get(rook_start)
-                    {
-                        // Move Rook back to original corner
-                        self.set(rook_to_move, rook_end);
-                        self.unset(rook_start);
-                    }
-                }
+                // Handled above
+                SpecialMove::Castle(..) | SpecialMove::Drop => {}
                 SpecialMove::EnPassant(captured_pawn_coordinates) => {
                     // Put the captured pawn back where it was (not on the move path)
                     self.set(
@@ -264,7 +247,11 @@ impl Board {
     /// Overwrites whatever was previously there. If the piece is a King, it also
     /// updates the cached King location.
     pub fn set(&mut self, piece: Piece, coordinates: Coordinate) {
+        self.unset(coordinates);
         self.grid[coordinates.row()][coordinates.column()] = Some(piece);
+        self.pieces[piece.kind().index()].insert(coordinates);
+        self.sides[piece.team().index()].insert(coordinates);
+        self.hash ^= zobrist::piece(piece, coordinates);
 
         if piece.kind() == Kind::King {
             match piece.team() {
@@ -276,6 +263,11 @@ impl Board {
 
     /// Removes a piece from the board, leaving the square empty.
     pub fn unset(&mut self, coordinates: Coordinate) {
+        if let Some(previous) = self.get(coordinates) {
+            self.pieces[previous.kind().index()].remove(coordinates);
+            self.sides[previous.team().index()].remove(coordinates);
+            self.hash ^= zobrist::piece(previous, coordinates);
+        }
         self.grid[coordinates.row()][coordinates.column()] = None;
     }
 
@@ -297,22 +289,36 @@ impl Board {
         self.black_king_location
     }
 
+    /// Returns the cached position of the King of the given side.
+    #[must_use]
+    pub const fn king(&self, side: Side) -> Coordinate {
+        match side {
+            Side::White => self.white_king_location,
+            Side::Black => self.black_king_location,
+        }
+    }
+
     /// Returns a list of all pieces currently on the board.
     #[must_use]
     pub fn get_all_pieces(&self) -> Vec<LocatedPiece> {
-        self.filter_pieces(|_| true)
+        self.located_pieces(self.occupied()).collect()
     }
 
     /// Returns a list of all White pieces.
     #[must_use]
     pub fn get_white_pieces(&self) -> Vec<LocatedPiece> {
-        self.filter_pieces(|p: Piece| p.team() == Side::White)
+        self.side_pieces(Side::White).collect()
     }
 
     /// Returns a list of all Black pieces.
     #[must_use]
     pub fn get_black_pieces(&self) -> Vec<LocatedPiece> {
-        self.filter_pieces(|p: Piece| p.team() == Side::Black)
+        self.side_pieces(Side::Black).collect()
+    }
+
+    /// Iterates over the pieces of the given side, without allocating.
+    pub fn side_pieces(&self, side: Side) -> impl Iterator<Item = LocatedPiece> + '_ {
+        self.located_pieces(self.side(side))
     }
 
     /// Returns a reference to the underlying [`Grid`].
@@ -321,11 +327,54 @@ impl Board {
         &self.grid
     }
 
+    /// Returns the Zobrist hash of the piece placement.
+    ///
+    /// It only covers where the pieces stand: the side to move, castling rights and
+    /// en passant are not known to the board, see [`Game::hash`](crate::state::Game::hash).
+    #[must_use]
+    pub const fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Returns the squares occupied by any piece.
+    #[must_use]
+    pub const fn occupied(&self) -> Bitboard {
+        Bitboard(self.sides[0].0 | self.sides[1].0)
+    }
+
+    /// Returns the squares occupied by the pieces of one side.
+    #[must_use]
+    pub const fn side(&self, side: Side) -> Bitboard {
+        self.sides[side.index()]
+    }
+
+    /// Returns the squares occupied by pieces of one kind, of either side.
+    #[must_use]
+    pub const fn kind(&self, kind: Kind) -> Bitboard {
+        self.pieces[kind.index()]
+    }
+
+    /// Returns the squares occupied by the pieces of one kind and side.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::{Board, Kind, Side};
+    ///
+    /// let board = Board::from_starting_position();
+    /// assert_eq!(board.pieces(Side::White, Kind::Pawn).count(), 8);
+    /// assert_eq!(board.pieces(Side::Black, Kind::Queen).count(), 1);
+    /// ```
+    #[must_use]
+    pub const fn pieces(&self, side: Side, kind: Kind) -> Bitboard {
+        Bitboard(self.sides[side.index()].0 & self.pieces[kind.index()].0)
+    }
+
     /// Determines if a specific square is under attack by the opposing team.
     ///
-    /// This uses direct grid lookups (ray casting and offsets) from the target square
-    /// outwards to find attackers. This completely avoids memory allocations and
-    /// redundant move generation, making it highly efficient.
+    /// Rather than searching outwards square by square, this looks up the attack
+    /// patterns of every piece type *from* the target square and intersects them
+    /// with the attacker's pieces of that type, which needs no allocation nor loops.
     ///
     /// # Arguments
     ///
@@ -333,241 +382,292 @@ impl Board {
     /// * `attacker_team`: The team that might be attacking this square.
     #[must_use]
     pub fn is_square_under_attack(&self, location: Coordinate, attacker_team: Side) -> bool {
-        let start_row = location.row().cast_signed();
-        let start_column = location.column().cast_signed();
-
-        for (row_delta, column_delta) in KNIGHT_DIRECTIONS {
-            if let Some(target) =
-                Coordinate::new(start_row + row_delta, start_column + column_delta)
-                && let Some(piece) = self.get(target)
-                && piece.team() == attacker_team
-                && piece.kind() == Kind::Knight
-            {
-                return true;
-            }
-        }
+        !self.attackers_to(location, attacker_team).is_empty()
+    }
 
-        for (row_delta, column_delta) in KING_DIRECTIONS {
-            if let Some(target) =
-                Coordinate::new(start_row + row_delta, start_column + column_delta)
-                && let Some(piece) = self.get(target)
-                && piece.team() == attacker_team
-                && piece.kind() == Kind::King
-            {
-                return true;
-            }
-        }
+    /// Returns the squares of every piece of `attacker_team` attacking `location`.
+    ///
+    /// Pieces pinned to their King are included: they attack the square, even if moving
+    /// there would be illegal.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// let game = Game::from_fen("4k3/8/8/3p4/4P3/5N2/8/4K3 w - - 0 1");
+    /// let board = game.backend();
+    /// let square = |name| Coordinate::from_algebraic_notation(name).unwrap();
+    ///
+    /// let attackers: Vec<_> = board.attackers_to(square("d5"), Side::White).into_iter().collect();
+    /// assert_eq!(attackers, [square("e4")]);
+    /// assert_eq!(board.attackers_to(square("e4"), Side::Black).count(), 1);
+    /// ```
+    #[must_use]
+    pub fn attackers_to(&self, location: Coordinate, attacker_team: Side) -> Bitboard {
+        self.attackers(location, attacker_team, self.occupied())
+    }
 
-        // Pawn Attacks
-        // Pawns attack diagonally forward. To see if we are attacked, we look diagonally backward.
-        // - White pawns attack UP (-1 row), so we look DOWN (+1 row) to find them.
-        // - Black pawns attack DOWN (+1 row), so we look UP (-1 row) to find them.
-        let pawn_dirs = match attacker_team {
-            Side::White => [DIAGONALLY_DOWN_LEFT, DIAGONALLY_DOWN_RIGHT],
-            Side::Black => [DIAGONALLY_UP_LEFT, DIAGONALLY_UP_RIGHT],
+    /// Returns the squares attacked by the piece standing on `location`, or an empty set
+    /// if the square is empty.
+    ///
+    /// Sliders stop at the first piece in each direction, which they attack whatever its
+    /// side: a piece "attacking" a friendly piece defends it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// let game = Game::from_starting_position();
+    /// let g1 = Coordinate::from_algebraic_notation("g1").unwrap();
+    /// let d1 = Coordinate::from_algebraic_notation("d1").unwrap();
+    ///
+    /// // e2, f3 and h3
+    /// assert_eq!(game.backend().attacks_from(g1).count(), 3);
+    /// // c1, c2, d2, e2 and e1
+    /// assert_eq!(game.backend().attacks_from(d1).count(), 5);
+    /// ```
+    #[must_use]
+    pub fn attacks_from(&self, location: Coordinate) -> Bitboard {
+        let Some(piece) = self.get(location) else {
+            return Bitboard::EMPTY;
         };
 
-        for (row_delta, column_delta) in pawn_dirs {
-            if let Some(target) =
-                Coordinate::new(start_row + row_delta, start_column + column_delta)
-                && let Some(piece) = self.get(target)
-                && piece.team() == attacker_team
-                && piece.kind() == Kind::Pawn
-            {
-                return true;
-            }
-        }
-
-        for (row_delta, column_delta) in ORTHOGONAL_DIRECTIONS {
-            let mut step = 1;
-            while let Some(target) = Coordinate::new(
-                start_row + row_delta * step,
-                start_column + column_delta * step,
-            ) {
-                if let Some(piece) = self.get(target) {
-                    if piece.team() == attacker_team
-                        && (piece.kind() == Kind::Rook || piece.kind() == Kind::Queen)
-                    {
-                        return true;
-                    }
-                    break; // Blocked by a piece (friendly, or an enemy that isn't a Rook/Queen)
-                }
-                step += 1;
-            }
+        let occupied = self.occupied();
+        match piece.kind() {
+            Kind::King => attacks::king_attacks(location),
+            Kind::Queen => attacks::queen_attacks(location, occupied),
+            Kind::Rook => attacks::rook_attacks(location, occupied),
+            Kind::Bishop => attacks::bishop_attacks(location, occupied),
+            Kind::Knight => attacks::knight_attacks(location),
+            Kind::Pawn => attacks::pawn_attacks(piece.team(), location),
         }
+    }
 
-        for (row_delta, column_delta) in DIAGONAL_DIRECTIONS {
-            let mut step = 1;
-            while let Some(target) = Coordinate::new(
-                start_row + row_delta * step,
-                start_column + column_delta * step,
-            ) {
-                if let Some(piece) = self.get(target) {
-                    if piece.team() == attacker_team
-                        && (piece.kind() == Kind::Bishop || piece.kind() == Kind::Queen)
-                    {
-                        return true;
-                    }
-                    break; // Blocked by a piece
-                }
-                step += 1;
-            }
-        }
+    /// Returns every square attacked by at least one piece of `side`.
+    ///
+    /// Squares holding pieces of `side` are included when they are defended.
+    #[must_use]
+    pub fn attack_map(&self, side: Side) -> Bitboard {
+        self.side(side)
+            .into_iter()
+            .fold(Bitboard::EMPTY, |map, square| {
+                map | self.attacks_from(square)
+            })
+    }
 
-        false // If we made it here, no attacks were found
+    /// Returns the pieces of `side` defended by at least one other piece of `side`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// // The Knight on c3 is attacked by the pawn on b4 and defended by nothing
+    /// let game = Game::from_fen("4k3/8/8/8/1p6/2N5/4P3/4K3 w - - 0 1");
+    /// let board = game.backend();
+    ///
+    /// let attacked = board.side(Side::White) & board.attack_map(Side::Black);
+    /// let hanging = attacked & !board.defence_map(Side::White);
+    /// let c3 = Coordinate::from_algebraic_notation("c3").unwrap();
+    /// assert_eq!(hanging.into_iter().collect::<Vec<_>>(), [c3]);
+    /// ```
+    #[must_use]
+    pub fn defence_map(&self, side: Side) -> Bitboard {
+        self.attack_map(side) & self.side(side)
     }
 
     /// Pre-calculates the legality constraints (pins, checks, and danger squares) for the active player.
     ///
-    /// This function performs complex ray-casting from the active King's position to map out
-    /// the board's tactical state. By calculating this context *once* per position, the move
-    /// generators can operate strictly and efficiently without doing redundant check validations.
+    /// By calculating this context *once* per position, the move generators can operate
+    /// strictly and efficiently without doing redundant check validations.
     ///
     /// # Logic
-    /// 1. **Danger Squares**: Calculates which adjacent squares the King cannot step to. It temporarily
-    ///    removes the King from the board to detect x-ray attacks (where a slider attacks a square "through" the King).
-    /// 2. **Pins & Sliding Checks**: Shoots rays outward from the King. The first friendly piece encountered
-    ///    becomes a potential pin. If the next piece on that ray is an enemy slider (Queen/Rook/Bishop),
-    ///    that friendly piece is permanently pinned. If the *first* piece encountered is an enemy slider, it's a check.
-    /// 3. **Stepping Checks**: Checks Knight L-shapes and reverse Pawn attack vectors for immediate checks.
+    /// 1. **Checkers**: Every enemy piece attacking the King's square.
+    /// 2. **Pins**: Friendly pieces standing alone between the King and an enemy slider.
+    /// 3. **Danger Squares**: The squares adjacent to the King that the enemy controls. The King
+    ///    is removed from the occupancy first to detect x-ray attacks (where a slider attacks a
+    ///    square "through" the King).
     ///
     /// # Arguments
     ///
     /// * `turn`: The team whose King is being evaluated.
     #[must_use]
     pub fn calculate_legality_context(&self, turn: Side) -> LegalityContext {
-        let king_position = match turn {
-            Side::White => self.white_king_location,
-            Side::Black => self.black_king_location,
-        };
+        let enemy = turn.opposite();
+        let king_position = self.king(turn);
+        let occupied = self.occupied();
 
-        let mut checkers = Vec::with_capacity(2);
-        let mut pinned_pieces = Vec::with_capacity(8);
-        let mut danger_squares = Vec::with_capacity(8);
+        // 1. Checkers
+        let checkers = self.attackers(king_position, enemy, occupied);
 
-        // The King itself blocks rays in `is_square_under_attack`.
-        // We must temporarily remove the King to see squares attacked "through" the King.
-        let mut board_without_king = *self;
-        board_without_king.unset(king_position);
+        // 2. Pins
+        let pinned_pieces = self.pinned_pieces(turn);
 
-        // 1. Calculate danger squares around king_position
-        for direction in KING_DIRECTIONS {
-            if let Some(possible_danger_square) = king_position.with_offset(direction, 1)
-                && board_without_king
-                    .is_square_under_attack(possible_danger_square, turn.opposite())
-            {
-                danger_squares.push(possible_danger_square);
-            }
-        }
+        // 3. Danger squares
+        let mut occupied_without_king = occupied;
+        occupied_without_king.remove(king_position);
 
-        // 2. Shoot rays to find pins and sliding checkers
-        for direction in ORTHOGONAL_DIRECTIONS {
-            let mut distance = 1;
-            let mut current_pin = None;
-            while let Some(target) = king_position.with_offset(direction, distance) {
-                if let Some(some_piece) = self.get(target) {
-                    if some_piece.team() == turn {
-                        match current_pin {
-                            Some(_) => {
-                                break;
-                            }
-                            None => current_pin = Some(target),
-                        }
-                    } else if some_piece.kind() == Kind::Queen || some_piece.kind() == Kind::Rook {
-                        if let Some(pinned) = current_pin {
-                            pinned_pieces.push((pinned, direction));
-                            break;
-                        }
-                        checkers.push(target);
-                        break;
-                    } else {
-                        break;
-                    }
-                }
-                distance += 1;
+        let mut danger_squares = Bitboard::EMPTY;
+        for square in attacks::king_attacks(king_position) {
+            if !self
+                .attackers(square, enemy, occupied_without_king)
+                .is_empty()
+            {
+                danger_squares.insert(square);
             }
         }
 
-        for direction in DIAGONAL_DIRECTIONS {
-            let mut distance = 1;
-            let mut current_pin = None;
-            while let Some(target) = king_position.with_offset(direction, distance) {
-                if let Some(some_piece) = self.get(target) {
-                    if some_piece.team() == turn {
-                        match current_pin {
-                            Some(_) => {
-                                break;
-                            }
-                            None => current_pin = Some(target),
-                        }
-                    } else if some_piece.kind() == Kind::Queen || some_piece.kind() == Kind::Bishop
-                    {
-                        if let Some(pinned) = current_pin {
-                            pinned_pieces.push((pinned, direction));
-                            break;
-                        }
-                        checkers.push(target);
-                        break;
-                    } else {
-                        break;
-                    }
-                }
-                distance += 1;
-            }
-        }
+        LegalityContext::from(king_position, checkers, pinned_pieces, danger_squares)
+    }
 
-        // 3. Check knight/pawn offsets to find stepping checkers
-        for direction in KNIGHT_DIRECTIONS {
-            if let Some(target) = king_position.with_offset(direction, 1)
-                && let Some(piece) = self.get(target)
-                && piece.team() == turn.opposite()
-                && piece.kind() == Kind::Knight
-            {
-                checkers.push(target);
-            }
-        }
+    /// Pre-calculates the checking squares and discovered-check candidates of `side`,
+    /// against the enemy King.
+    ///
+    /// This mirrors [`calculate_legality_context`](Self::calculate_legality_context) from
+    /// the other side of the board: the pieces that would be pinned if they belonged to
+    /// the enemy are, as they belong to `side`, the ones that give discovered checks.
+    ///
+    /// # Logic
+    /// 1. **Checking Squares**: The attack patterns of every piece type *from* the enemy
+    ///    King's square, with the current occupancy for sliders.
+    /// 2. **Discovered Checks**: Friendly pieces standing alone between the enemy King and
+    ///    a friendly slider.
+    ///
+    /// # Arguments
+    ///
+    /// * `side`: The team whose moves may give check.
+    #[must_use]
+    pub fn calculate_check_context(&self, side: Side) -> CheckContext {
+        let enemy = side.opposite();
+        let king_position = self.king(enemy);
+        let occupied = self.occupied();
+
+        // 1. Checking squares, indexed by `Kind::index`
+        let bishop_squares = attacks::bishop_attacks(king_position, occupied);
+        let rook_squares = attacks::rook_attacks(king_position, occupied);
+        let checking_squares = [
+            Bitboard::EMPTY,
+            bishop_squares | rook_squares,
+            rook_squares,
+            bishop_squares,
+            attacks::knight_attacks(king_position),
+            // A pawn of `side` attacks the King from where a pawn of the enemy would attack it
+            attacks::pawn_attacks(enemy, king_position),
+        ];
+
+        // 2. Discovered checks
+        let candidates = self.lone_blockers(enemy) & self.side(side);
+
+        CheckContext::from(king_position, checking_squares, candidates)
+    }
 
-        // Pawn Attacks
-        // Pawns attack diagonally forward. To see if we are attacked, we look diagonally backward.
-        // - White pawns attack UP (-1 row), so we look DOWN (+1 row) to find them.
-        // - Black pawns attack DOWN (+1 row), so we look UP (-1 row) to find them.
-        let pawn_dirs = match turn.opposite() {
-            Side::White => [DIAGONALLY_DOWN_LEFT, DIAGONALLY_DOWN_RIGHT],
-            Side::Black => [DIAGONALLY_UP_LEFT, DIAGONALLY_UP_RIGHT],
-        };
+    /// Returns the pieces of `side` pinned to their own King.
+    #[must_use]
+    pub(super) fn pinned_pieces(&self, side: Side) -> Bitboard {
+        self.lone_blockers(side) & self.side(side)
+    }
 
-        for direction in pawn_dirs {
-            if let Some(target) = king_position.with_offset(direction, 1)
-                && let Some(piece) = self.get(target)
-                && piece.team() == turn.opposite()
-                && piece.kind() == Kind::Pawn
-            {
-                checkers.push(target);
+    /// Returns the pieces of either side standing alone between the King of `side` and an
+    /// enemy slider.
+    ///
+    /// Enemy sliders aligned with the King ("snipers") are found by looking up slider
+    /// attacks from the King on an empty board. If exactly one piece stands between a
+    /// sniper and the King, that piece is a lone blocker: pinned if it belongs to `side`,
+    /// and a discovered-check candidate if it belongs to the enemy.
+    fn lone_blockers(&self, side: Side) -> Bitboard {
+        let enemy = side.opposite();
+        let king_position = self.king(side);
+        let occupied = self.occupied();
+
+        let enemy_queens = self.pieces(enemy, Kind::Queen);
+        let snipers = (attacks::rook_attacks(king_position, Bitboard::EMPTY)
+            & (self.pieces(enemy, Kind::Rook) | enemy_queens))
+            | (attacks::bishop_attacks(king_position, Bitboard::EMPTY)
+                & (self.pieces(enemy, Kind::Bishop) | enemy_queens));
+
+        let mut blockers = Bitboard::EMPTY;
+        for sniper in snipers {
+            let between = attacks::between(king_position, sniper) & occupied;
+            if between.count() == 1 {
+                blockers |= between;
             }
         }
-
-        LegalityContext::from(checkers, pinned_pieces, danger_squares)
+        blockers
     }
 
-    /// Helper to collect pieces matching a filter predicate.
+    /// Returns the pieces of `attacker_team` attacking `location`, given an occupancy.
+    ///
+    /// The occupancy is a parameter so that callers can evaluate hypothetical
+    /// positions, such as the board with the King lifted off it.
     #[must_use]
+    pub(super) fn attackers(
+        &self,
+        location: Coordinate,
+        attacker_team: Side,
+        occupied: Bitboard,
+    ) -> Bitboard {
+        let queens = self.pieces(attacker_team, Kind::Queen);
+        let rooks_and_queens = self.pieces(attacker_team, Kind::Rook) | queens;
+        let bishops_and_queens = self.pieces(attacker_team, Kind::Bishop) | queens;
+
+        (attacks::knight_attacks(location) & self.pieces(attacker_team, Kind::Knight))
+            | (attacks::king_attacks(location) & self.pieces(attacker_team, Kind::King))
+            // A square is attacked by a pawn of the attacker if a pawn of the *defender*
+            // standing on it would attack that pawn.
+            | (attacks::pawn_attacks(attacker_team.opposite(), location)
+                & self.pieces(attacker_team, Kind::Pawn))
+            | (attacks::rook_attacks(location, occupied) & rooks_and_queens)
+            | (attacks::bishop_attacks(location, occupied) & bishops_and_queens)
+    }
+
+    /// Helper to pair every square of a bitboard with the piece standing on it.
     #[inline]
-    fn filter_pieces(&self, filter: impl Fn(Piece) -> bool) -> Vec<LocatedPiece> {
-        let mut filtered_pieces = Vec::new();
-        for row in BOARD_ROWS_RANGE {
-            for column in BOARD_COLUMNS_RANGE {
-                if let Some(current) = self.grid[row][column]
-                    && filter(current)
-                {
-                    let location = Coordinate::new(row, column).expect("Board iteration produced invalid coordinates, either BOARD_ROWS_RANGE or BOARD_COLUMNS_RANGE is not correctly defined");
-
-                    let located_piece = LocatedPiece::new(current, location);
-
-                    filtered_pieces.push(located_piece);
+    fn located_pieces(&self, squares: Bitboard) -> impl Iterator<Item = LocatedPiece> + '_ {
+        squares.into_iter().filter_map(|location| {
+            self.get(location)
+                .map(|piece| LocatedPiece::new(piece, location))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        atoms::{Coordinate, Side},
+        state::Game,
+    };
+
+    #[test]
+    fn test_attack_maps_agree_with_attackers() {
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        ];
+
+        for fen in fens {
+            let game = Game::from_fen(fen);
+            let board = game.backend();
+            for side in [Side::White, Side::Black] {
+                let map = board.attack_map(side);
+                for index in 0..64 {
+                    let square = Coordinate::from_index(index).unwrap();
+                    let attackers = board.attackers_to(square, side);
+                    assert_eq!(
+                        map.contains(square),
+                        !attackers.is_empty(),
+                        "{fen} {square:?}"
+                    );
+                    for attacker in attackers {
+                        assert!(
+                            board.attacks_from(attacker).contains(square),
+                            "{fen} {square:?}"
+                        );
+                    }
                 }
             }
         }
-
-        filtered_pieces
     }
 }
@@ -0,0 +1,228 @@
+//! # Move Classification
+//!
+//! This module tells, without playing a move, whether it gives check and what
+//! else it does (captures, promotions, castling). The checks are found with the
+//! [`CheckContext`] of the position, so that classifying every move of a position
+//! costs a single context calculation and a couple of lookups per move.
+
+use crate::{
+    atoms::{Bitboard, Coordinate},
+    moves::{CheckContext, MoveFlags, Ply, SpecialMove, attacks},
+    pieces::Kind,
+    state::{Board, Game, Variant},
+};
+
+impl Board {
+    /// Returns `true` if `ply` checks the enemy King, directly or by uncovering a slider.
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - The move to test, legal in the current position.
+    /// * `context` - The [`CheckContext`] of the side making the move, as calculated by
+    ///   [`calculate_check_context`](Self::calculate_check_context).
+    #[must_use]
+    pub fn gives_check(&self, ply: &Ply, context: &CheckContext) -> bool {
+        self.check_flags(ply, context).is_check()
+    }
+
+    /// Returns the [`MoveFlags`] describing `ply`: whether it captures, promotes, castles,
+    /// drops a piece, or gives check.
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - The move to classify, legal in the current position.
+    /// * `context` - The [`CheckContext`] of the side making the move.
+    #[must_use]
+    pub fn classify_move(&self, ply: &Ply, context: &CheckContext) -> MoveFlags {
+        let mut flags = self.check_flags(ply, context);
+
+        if ply.piece_captured().is_some() {
+            flags.insert(MoveFlags::CAPTURE);
+        }
+        match ply.special_move() {
+            Some(SpecialMove::Promotion(_)) => flags.insert(MoveFlags::PROMOTION),
+            Some(SpecialMove::EnPassant(_)) => {
+                flags.insert(MoveFlags::CAPTURE | MoveFlags::EN_PASSANT);
+            }
+            Some(SpecialMove::Castle(..)) => flags.insert(MoveFlags::CASTLE),
+            Some(SpecialMove::Drop) => flags.insert(MoveFlags::DROP),
+            None => {}
+        }
+        flags
+    }
+
+    /// Returns [`MoveFlags::CHECK`], along with [`MoveFlags::DISCOVERED_CHECK`] when a
+    /// slider is uncovered, if `ply` checks the enemy King.
+    ///
+    /// Regular moves and drops only need the context. Promotions, en passant and castling
+    /// move or remove more than one piece, and look at the occupancy after the move.
+    fn check_flags(&self, ply: &Ply, context: &CheckContext) -> MoveFlags {
+        let side = ply.piece_moved().team();
+        let king_position = context.king_position();
+        let from = ply.starting_square();
+        let to = ply.ending_square();
+
+        let (direct, discovered) = match ply.special_move() {
+            None => (
+                context
+                    .checking_squares(ply.piece_moved().kind())
+                    .contains(to),
+                context.discovers_check(from, to),
+            ),
+            Some(SpecialMove::Drop) => (
+                context
+                    .checking_squares(ply.piece_moved().kind())
+                    .contains(to),
+                false,
+            ),
+            Some(SpecialMove::Promotion(promotion)) => {
+                // The pawn's square may stand on the new piece's path to the King
+                let mut occupied = self.occupied();
+                occupied.remove(from);
+                let attacks = match Kind::from_valid_promotions(promotion) {
+                    Kind::Queen => attacks::queen_attacks(to, occupied),
+                    Kind::Rook => attacks::rook_attacks(to, occupied),
+                    Kind::Bishop => attacks::bishop_attacks(to, occupied),
+                    _ => attacks::knight_attacks(to),
+                };
+                (
+                    attacks.contains(king_position),
+                    context.discovers_check(from, to),
+                )
+            }
+            Some(SpecialMove::EnPassant(captured_pawn)) => {
+                // Both pawns leave their square, and may uncover a slider along a rank
+                let mut occupied = self.occupied();
+                occupied.remove(from);
+                occupied.remove(captured_pawn);
+                occupied.insert(to);
+                (
+                    context.checking_squares(Kind::Pawn).contains(to),
+                    !self.attackers(king_position, side, occupied).is_empty(),
+                )
+            }
+            Some(SpecialMove::Castle(castling_side, rook_square)) => {
+                let Some(rook_destination) =
+                    Coordinate::new(to.row(), castling_side.rook_destination_column())
+                else {
+                    return MoveFlags::EMPTY;
+                };
+
+                let mut occupied = self.occupied();
+                occupied.remove(from);
+                occupied.remove(rook_square);
+                occupied.insert(to);
+                occupied.insert(rook_destination);
+
+                // The Rook is still on its starting square as far as the board knows
+                let others = self.attackers(king_position, side, occupied)
+                    & !Bitboard::from_coordinate(rook_square);
+                (
+                    attacks::rook_attacks(rook_destination, occupied).contains(king_position),
+                    !others.is_empty(),
+                )
+            }
+        };
+
+        match (direct, discovered) {
+            (_, true) => MoveFlags::CHECK | MoveFlags::DISCOVERED_CHECK,
+            (true, false) => MoveFlags::CHECK,
+            (false, false) => MoveFlags::EMPTY,
+        }
+    }
+}
+
+impl<V: Variant> Game<V> {
+    /// Returns `true` if `ply` checks the enemy King, without playing it.
+    ///
+    /// This calculates the [`CheckContext`] of the position on every call: to test many
+    /// moves of the same position, calculate it once and use
+    /// [`Board::gives_check`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// let mut game = Game::from_fen("4k3/8/8/8/8/8/8/R3K3 w Q - 0 1");
+    ///
+    /// // The Rook lands on d1, out of the King's way
+    /// let castle = game.parse_san("O-O-O").unwrap();
+    /// assert!(!game.gives_check(&castle));
+    ///
+    /// let rook_lift = game.parse_san("Ra8").unwrap();
+    /// assert!(game.gives_check(&rook_lift));
+    /// ```
+    #[must_use]
+    pub fn gives_check(&self, ply: &Ply) -> bool {
+        let context = self
+            .backend()
+            .calculate_check_context(ply.piece_moved().team());
+        self.backend().gives_check(ply, &context)
+    }
+
+    /// Returns the [`MoveFlags`] describing `ply`, without playing it.
+    ///
+    /// As with [`gives_check`](Self::gives_check), use [`Board::classify_move`] with a
+    /// shared [`CheckContext`] to classify every move of a position.
+    #[must_use]
+    pub fn classify_move(&self, ply: &Ply) -> MoveFlags {
+        let context = self
+            .backend()
+            .calculate_check_context(ply.piece_moved().team());
+        self.backend().classify_move(ply, &context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        moves::{MoveFlags, MoveList},
+        state::Game,
+    };
+
+    #[test]
+    fn test_gives_check_agrees_with_playing_the_move() {
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            // En passant uncovers the Rook on a5, and castling checks from f1
+            "8/8/8/R2pP2k/8/8/8/4K3 w - d6 0 1",
+            "5k2/8/8/8/8/8/8/4K2R w K - 0 1",
+            // Every promotion, and the file the promoting pawn leaves behind
+            "2n1k3/1P6/8/8/8/8/8/4K3 w - - 0 1",
+            "8/1P6/8/8/8/8/1k6/4K3 w - - 0 1",
+        ];
+
+        for fen in fens {
+            let mut game = Game::from_fen(fen);
+            let mut moves = MoveList::new();
+            game.generate_moves_into(&mut moves);
+
+            for ply in &moves {
+                let predicted = game.gives_check(ply);
+                game.make_move_unchecked(ply);
+                assert_eq!(predicted, game.is_in_check(), "{ply} in {fen}");
+                game.unmake_move();
+            }
+        }
+    }
+
+    #[test]
+    fn test_classify_move() {
+        let game = Game::from_fen("3rk3/4P3/8/8/8/8/3N4/3QK2R w K - 0 1");
+        let classify = |san| {
+            let ply = game.parse_san(san).unwrap();
+            game.classify_move(&ply)
+        };
+
+        assert_eq!(
+            classify("exd8=Q+"),
+            MoveFlags::CAPTURE | MoveFlags::PROMOTION | MoveFlags::CHECK
+        );
+        assert_eq!(classify("Nf3"), MoveFlags::EMPTY);
+        assert!(classify("O-O").contains(MoveFlags::CASTLE));
+        assert!(classify("Kf2").is_quiet());
+    }
+}
@@ -18,7 +18,13 @@
 //!
 //! <Side to move> ::= {'w' | 'b'}
 //!
-//! <Castling ability> ::= '-' | ['K'] ['Q'] ['k'] ['q'] (1..4)
+//! <Castling ability> ::= '-' | <castling right> {<castling right>} (1..4)
+//! <castling right>   ::= 'K' | 'Q' | 'k' | 'q' | 'A'..'H' | 'a'..'h'
+//!
+//! Castling rights are read both in X-FEN, where `KQkq` name the outermost Rook on
+//! each side of the King and a file letter is only used for an inner Rook, and in
+//! Shredder-FEN, where every right is the file letter of its Rook (`HAha`). Both
+//! describe Chess960 positions as well as standard ones.
 //!
 //! <En passant target square> ::= '-' | <epsquare>
 //! <epsquare>   ::= <fileLetter> <eprank>
@@ -36,7 +42,7 @@ use crate::{
     atoms::{CastlingRights, Coordinate, MoveCounter, Side},
     moves::CastlingSide,
     pieces::{Kind, Piece},
-    state::{Grid, PositionSnapshot},
+    state::{Board, Grid, PositionSnapshot},
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -54,6 +60,7 @@ pub enum FenToken {
     // Castling
     NoCastling,
     CastlingEnabled(Side, CastlingSide),
+    CastlingRookFile(Side, usize),
 
     // En Passant
     NoEnPassant,
@@ -76,6 +83,16 @@ pub enum FenParsingError {
     InvalidEnPassant(String),
     InvalidClock(String),
     UnexpectedToken(String),
+    InvalidVariantField(String),
+
+    // Semantic errors: the FEN is well formed, but the position cannot arise in a game
+    MissingKing(Side),
+    TooManyKings(Side),
+    PawnOnBackRank(Coordinate),
+    TooManyPieces(Side),
+    SideNotToMoveInCheck(Side),
+    CastlingWithoutKingOrRook(Side, CastlingSide),
+    ImpossibleEnPassant(Coordinate),
 }
 
 impl std::fmt::Display for FenParsingError {
@@ -88,18 +105,74 @@ impl std::fmt::Display for FenParsingError {
             Self::InvalidEnPassant(s) => write!(f, "Invalid en passant target: {s}"),
             Self::InvalidClock(s) => write!(f, "Invalid clock format: {s}"),
             Self::UnexpectedToken(s) => write!(f, "Unexpected token: {s}"),
+            Self::InvalidVariantField(s) => write!(f, "Invalid variant field: {s}"),
+            Self::MissingKing(side) => write!(f, "{side:?} has no king"),
+            Self::TooManyKings(side) => write!(f, "{side:?} has more than one king"),
+            Self::PawnOnBackRank(square) => write!(
+                f,
+                "Pawn on {} cannot stand on the first or eighth rank",
+                square.to_algebraic_notation()
+            ),
+            Self::TooManyPieces(side) => write!(
+                f,
+                "{side:?} has more pieces than the starting set and promotions allow"
+            ),
+            Self::SideNotToMoveInCheck(side) => {
+                write!(f, "{side:?} is in check but it is not their turn")
+            }
+            Self::CastlingWithoutKingOrRook(side, castling_side) => write!(
+                f,
+                "{side:?} cannot castle {castling_side:?}: king or rook not on its starting square"
+            ),
+            Self::ImpossibleEnPassant(square) => write!(
+                f,
+                "En passant target {} does not follow a pawn double push",
+                square.to_algebraic_notation()
+            ),
         }
     }
 }
 
 impl std::error::Error for FenParsingError {}
 
+/// File letters, indexed by column.
+const FILES: [char; 8] = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
+
+/// How the castling rights of a FEN string are written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CastlingNotation {
+    /// `KQkq`, with a file letter only for a Rook that is not the outermost one.
+    XFen,
+    /// The file letter of every castling Rook (`HAha`).
+    Shredder,
+}
+
 /// Generates a FEN string from a `PositionSnapshot`.
 ///
 /// Since `PositionSnapshot` does not store move counters, this function
 /// defaults the Halfmove clock and Fullmove counter to "0 1".
+///
+/// Castling rights are written in X-FEN, which is the same as standard FEN for every
+/// position of standard chess.
 #[must_use]
 pub fn to_fen(position: PositionSnapshot, clocks: &MoveCounter) -> String {
+    write_fen(position, clocks, CastlingNotation::XFen)
+}
+
+/// Generates a Shredder-FEN string from a `PositionSnapshot`.
+///
+/// It only differs from [`to_fen`] in the castling rights, which are written as the files
+/// of the castling Rooks (`HAha` for the standard starting position).
+#[must_use]
+pub fn to_shredder_fen(position: PositionSnapshot, clocks: &MoveCounter) -> String {
+    write_fen(position, clocks, CastlingNotation::Shredder)
+}
+
+fn write_fen(
+    position: PositionSnapshot,
+    clocks: &MoveCounter,
+    castling_notation: CastlingNotation,
+) -> String {
     let mut fen = String::new();
 
     // 1. Piece Placement
@@ -136,19 +209,27 @@ pub fn to_fen(position: PositionSnapshot, clocks: &MoveCounter) -> String {
     fen.push(' ');
 
     // 3. Castling
+    let grid = position.get_grid();
     let rights = position.get_castling_rights();
     let mut castling_str = String::new();
-    if rights.white_king_side() {
-        castling_str.push('K');
-    }
-    if rights.white_queen_side() {
-        castling_str.push('Q');
-    }
-    if rights.black_king_side() {
-        castling_str.push('k');
-    }
-    if rights.black_queen_side() {
-        castling_str.push('q');
+    for side in [Side::White, Side::Black] {
+        for castling_side in [CastlingSide::Short, CastlingSide::Long] {
+            let Some(rook_file) = rights.rook_file(side, castling_side) else {
+                continue;
+            };
+
+            let is_outermost = outermost_rook_file(&grid, side, castling_side) == Some(rook_file);
+            let letter = match (castling_notation, castling_side) {
+                (CastlingNotation::XFen, CastlingSide::Short) if is_outermost => 'K',
+                (CastlingNotation::XFen, CastlingSide::Long) if is_outermost => 'Q',
+                _ => FILES[rook_file].to_ascii_uppercase(),
+            };
+
+            castling_str.push(match side {
+                Side::White => letter,
+                Side::Black => letter.to_ascii_lowercase(),
+            });
+        }
     }
     if castling_str.is_empty() {
         fen.push('-');
@@ -180,10 +261,32 @@ pub fn to_fen(position: PositionSnapshot, clocks: &MoveCounter) -> String {
 
 /// Parses a FEN string into a `PositionSnapshot` and the associated `MoveCounter`.
 ///
+/// Besides the syntax, the position itself is validated, so that a successfully
+/// parsed FEN can always be loaded and played. See [`validate_position`] for the
+/// list of checks.
+///
 /// # Errors
 ///
-/// Will return an error if the input string is not a valid fen string
+/// Will return an error if the input string is not a valid fen string, or if
+/// the position it describes is impossible.
 pub fn from_fen(fen: &str) -> Result<(PositionSnapshot, MoveCounter), FenParsingError> {
+    let (position, move_counter) = parse_fen(fen)?;
+
+    validate_position(&position)?;
+
+    Ok((position, move_counter))
+}
+
+/// Parses a FEN string into a `PositionSnapshot` and the associated `MoveCounter`,
+/// without checking that the position could arise in a game.
+///
+/// Variants whose positions follow other rules validate them on their own, see
+/// [`Variant::validate_position`](crate::state::Variant::validate_position).
+///
+/// # Errors
+///
+/// Will return an error if the input string is not a valid fen string.
+pub fn parse_fen(fen: &str) -> Result<(PositionSnapshot, MoveCounter), FenParsingError> {
     let mut lexer = Lexer::new(fen);
 
     let grid = parse_piece_placement(&mut lexer)?;
@@ -203,7 +306,7 @@ pub fn from_fen(fen: &str) -> Result<(PositionSnapshot, MoveCounter), FenParsing
     }
 
     // 3. Castling Rights
-    let castling = parse_castling_ability(&mut lexer)?;
+    let castling = parse_castling_ability(&mut lexer, &grid)?;
 
     // 4. En Passant
     let en_passant = parse_en_passant_target_square(&mut lexer)?;
@@ -225,6 +328,167 @@ pub fn from_fen(fen: &str) -> Result<(PositionSnapshot, MoveCounter), FenParsing
     Ok((position, move_counter))
 }
 
+/// Checks that a position could legally arise in a game of chess.
+///
+/// The checks performed are:
+/// 1. Each side has exactly one King.
+/// 2. No pawn stands on the first or eighth rank.
+/// 3. No side has more pieces than its starting set plus promotions allow.
+/// 4. The side that just moved did not leave its King in check.
+/// 5. Every castling right has its King and Rook on their starting squares.
+/// 6. The en passant target, if any, sits right behind a pawn that just made a double push.
+///
+/// # Errors
+///
+/// Returns the first [`FenParsingError`] found, in the order above.
+pub fn validate_position(position: &PositionSnapshot) -> Result<(), FenParsingError> {
+    validate(position, true)
+}
+
+/// Checks that a position could legally arise in a game of a variant with drops.
+///
+/// These are the checks of [`validate_position`], except for the number of pieces:
+/// in Crazyhouse, captured pieces come back to the board under their captor's colour.
+///
+/// # Errors
+///
+/// Returns the first [`FenParsingError`] found.
+pub fn validate_placement(position: &PositionSnapshot) -> Result<(), FenParsingError> {
+    validate(position, false)
+}
+
+/// Validates a position, counting the pieces of each side only if `count_pieces` is set.
+fn validate(position: &PositionSnapshot, count_pieces: bool) -> Result<(), FenParsingError> {
+    let grid = position.get_grid();
+
+    for side in [Side::White, Side::Black] {
+        let mut counts = [0_usize; 6];
+        for (row, row_contents) in grid.iter().enumerate() {
+            for (column, square) in row_contents.iter().enumerate() {
+                if let Some(piece) = square
+                    && piece.team() == side
+                {
+                    counts[piece.kind().index()] += 1;
+
+                    if piece.kind() == Kind::Pawn
+                        && (row == 0 || row == 7)
+                        && let Some(square) = Coordinate::new(row, column)
+                    {
+                        return Err(FenParsingError::PawnOnBackRank(square));
+                    }
+                }
+            }
+        }
+
+        match counts[Kind::King.index()] {
+            0 => return Err(FenParsingError::MissingKing(side)),
+            1 => {}
+            _ => return Err(FenParsingError::TooManyKings(side)),
+        }
+
+        // Every piece beyond the starting set must come from a promoted pawn
+        let pawns = counts[Kind::Pawn.index()];
+        let promoted: usize = [
+            (Kind::Queen, 1),
+            (Kind::Rook, 2),
+            (Kind::Bishop, 2),
+            (Kind::Knight, 2),
+        ]
+        .iter()
+        .map(|&(kind, starting_count)| counts[kind.index()].saturating_sub(starting_count))
+        .sum();
+        if count_pieces && pawns + promoted > 8 {
+            return Err(FenParsingError::TooManyPieces(side));
+        }
+    }
+
+    let board = Board::new(grid);
+    let turn = position.get_turn();
+
+    if board.is_square_under_attack(board.king(turn.opposite()), turn) {
+        return Err(FenParsingError::SideNotToMoveInCheck(turn.opposite()));
+    }
+
+    validate_castling_rights(&board, position.get_castling_rights())?;
+
+    if let Some(target) = position.get_en_passant() {
+        validate_en_passant(&board, turn, target)?;
+    }
+
+    Ok(())
+}
+
+/// Checks that every castling right has its King on the back rank and its Rook on the
+/// right file, on the side of the King it castles towards.
+///
+/// The King must stand between the b- and g-files: every castling position, in standard
+/// chess and in Chess960, has a Rook on each side of it.
+fn validate_castling_rights(
+    board: &Board,
+    castling_rights: CastlingRights,
+) -> Result<(), FenParsingError> {
+    for side in [Side::White, Side::Black] {
+        for castling_side in [CastlingSide::Short, CastlingSide::Long] {
+            let Some(rook_file) = castling_rights.rook_file(side, castling_side) else {
+                continue;
+            };
+
+            let king_file = back_rank_king_file(board.grid(), side);
+            let is_rook_in_place = Coordinate::new(back_rank(side), rook_file)
+                .and_then(|square| board.get(square))
+                .is_some_and(|piece| piece == Piece::new(side, Kind::Rook));
+            let is_on_its_side = king_file.is_some_and(|king_file| {
+                (1..=6).contains(&king_file)
+                    && match castling_side {
+                        CastlingSide::Short => rook_file > king_file,
+                        CastlingSide::Long => rook_file < king_file,
+                    }
+            });
+
+            if !(is_rook_in_place && is_on_its_side) {
+                return Err(FenParsingError::CastlingWithoutKingOrRook(
+                    side,
+                    castling_side,
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that an en passant target follows a double push by the side that just moved.
+///
+/// The target must be on the third rank of the side that just moved, with that side's
+/// pawn right in front of it and both the target and the pawn's starting square empty.
+fn validate_en_passant(
+    board: &Board,
+    turn: Side,
+    target: Coordinate,
+) -> Result<(), FenParsingError> {
+    let moved = turn.opposite();
+    let (target_row, forward) = match moved {
+        Side::White => (5, -1),
+        Side::Black => (2, 1),
+    };
+
+    let pawn_square = target.with_offset((forward, 0), 1);
+    let starting_square = target.with_offset((-forward, 0), 1);
+
+    let is_possible = target.row() == target_row
+        && board.get(target).is_none()
+        && starting_square.is_some_and(|square| board.get(square).is_none())
+        && pawn_square
+            .and_then(|square| board.get(square))
+            .is_some_and(|piece| piece == Piece::new(moved, Kind::Pawn));
+
+    if is_possible {
+        Ok(())
+    } else {
+        Err(FenParsingError::ImpossibleEnPassant(target))
+    }
+}
+
 fn parse_piece_placement(lexer: &mut Lexer) -> Result<Grid, FenParsingError> {
     // 1. Piece Placement
     let mut grid = [[None; 8]; 8];
@@ -289,19 +553,34 @@ fn parse_side_to_move(lexer: &mut Lexer) -> Result<Side, FenParsingError> {
     }
 }
 
-fn parse_castling_ability(lexer: &mut Lexer) -> Result<CastlingRights, FenParsingError> {
+fn parse_castling_ability(
+    lexer: &mut Lexer,
+    grid: &Grid,
+) -> Result<CastlingRights, FenParsingError> {
     let mut castling = CastlingRights::no_rights();
     loop {
         match lexer.next_token() {
             Some(FenToken::NoCastling) => {
                 // Just continue to space
             }
-            Some(FenToken::CastlingEnabled(team, side)) => match (team, side) {
-                (Side::White, CastlingSide::Short) => castling.enable_white_king_side(),
-                (Side::White, CastlingSide::Long) => castling.enable_white_queen_side(),
-                (Side::Black, CastlingSide::Short) => castling.enable_black_king_side(),
-                (Side::Black, CastlingSide::Long) => castling.enable_black_queen_side(),
-            },
+            Some(FenToken::CastlingEnabled(team, side)) => {
+                // Fall back to the standard file, so that a missing Rook is reported by the validation
+                let standard_file = match side {
+                    CastlingSide::Short => 7,
+                    CastlingSide::Long => 0,
+                };
+                let rook_file = outermost_rook_file(grid, team, side).unwrap_or(standard_file);
+                castling.enable(team, side, rook_file);
+            }
+            Some(FenToken::CastlingRookFile(team, rook_file)) => {
+                let king_file = back_rank_king_file(grid, team).unwrap_or(4);
+                let side = if rook_file > king_file {
+                    CastlingSide::Short
+                } else {
+                    CastlingSide::Long
+                };
+                castling.enable(team, side, rook_file);
+            }
             Some(FenToken::WhiteSpace) => break,
             Some(t) => {
                 return Err(FenParsingError::UnexpectedToken(format!(
@@ -314,6 +593,33 @@ fn parse_castling_ability(lexer: &mut Lexer) -> Result<CastlingRights, FenParsin
     Ok(castling)
 }
 
+/// Returns the row of the back rank of `side`.
+const fn back_rank(side: Side) -> usize {
+    match side {
+        Side::White => 7,
+        Side::Black => 0,
+    }
+}
+
+/// Returns the file of the King of `side`, if it stands on its back rank.
+fn back_rank_king_file(grid: &Grid, side: Side) -> Option<usize> {
+    grid[back_rank(side)]
+        .iter()
+        .position(|square| *square == Some(Piece::new(side, Kind::King)))
+}
+
+/// Returns the file of the Rook of `side` furthest from its King on `castling_side`,
+/// along the back rank. This is the Rook that `K`, `Q`, `k` and `q` refer to.
+fn outermost_rook_file(grid: &Grid, side: Side, castling_side: CastlingSide) -> Option<usize> {
+    let king_file = back_rank_king_file(grid, side)?;
+    let is_rook = |file: &usize| grid[back_rank(side)][*file] == Some(Piece::new(side, Kind::Rook));
+
+    match castling_side {
+        CastlingSide::Short => (king_file + 1..8).rev().find(is_rook),
+        CastlingSide::Long => (0..king_file).find(is_rook),
+    }
+}
+
 fn parse_en_passant_target_square(
     lexer: &mut Lexer,
 ) -> Result<Option<Coordinate>, FenParsingError> {
@@ -475,6 +781,14 @@ impl<'a> Lexer<'a> {
             'Q' => Some(FenToken::CastlingEnabled(Side::White, CastlingSide::Long)),
             'k' => Some(FenToken::CastlingEnabled(Side::Black, CastlingSide::Short)),
             'q' => Some(FenToken::CastlingEnabled(Side::Black, CastlingSide::Long)),
+            'A'..='H' => Some(FenToken::CastlingRookFile(
+                Side::White,
+                (c as usize) - ('A' as usize),
+            )),
+            'a'..='h' => Some(FenToken::CastlingRookFile(
+                Side::Black,
+                (c as usize) - ('a' as usize),
+            )),
             _ => None,
         }
     }
@@ -523,3 +837,160 @@ impl<'a> Lexer<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(notation: &str) -> Coordinate {
+        Coordinate::from_algebraic_notation(notation).unwrap()
+    }
+
+    fn parse_error(fen: &str) -> FenParsingError {
+        from_fen(fen).expect_err("FEN should be rejected")
+    }
+
+    #[test]
+    fn test_valid_positions() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+            "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            // Eight promoted queens and no pawns left
+            "QQQQ4/QQQQ4/8/8/8/8/8/K6k b - - 0 1",
+        ] {
+            assert!(from_fen(fen).is_ok(), "{fen} should be accepted");
+        }
+    }
+
+    #[test]
+    fn test_kings() {
+        assert_eq!(
+            parse_error("rnbq1bnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQ - 0 1"),
+            FenParsingError::MissingKing(Side::Black)
+        );
+        assert_eq!(
+            parse_error("4k3/8/8/8/8/8/8/K3K3 w - - 0 1"),
+            FenParsingError::TooManyKings(Side::White)
+        );
+    }
+
+    #[test]
+    fn test_piece_counts() {
+        assert_eq!(
+            parse_error("P3k3/8/8/8/8/8/8/4K3 w - - 0 1"),
+            FenParsingError::PawnOnBackRank(square("a8"))
+        );
+        assert_eq!(
+            parse_error("4k3/8/8/8/8/8/8/p3K3 w - - 0 1"),
+            FenParsingError::PawnOnBackRank(square("a1"))
+        );
+        assert_eq!(
+            parse_error("4k3/pppppppp/p7/8/8/8/8/4K3 w - - 0 1"),
+            FenParsingError::TooManyPieces(Side::Black)
+        );
+        // Two extra queens with eight pawns still on the board
+        assert_eq!(
+            parse_error("4k3/8/8/8/8/8/PPPPPPPP/QQ2K3 w - - 0 1"),
+            FenParsingError::TooManyPieces(Side::White)
+        );
+    }
+
+    #[test]
+    fn test_side_not_to_move_in_check() {
+        assert_eq!(
+            parse_error("4k3/8/8/8/8/8/8/4R2K w - - 0 1"),
+            FenParsingError::SideNotToMoveInCheck(Side::Black)
+        );
+        assert!(from_fen("4k3/4R3/8/8/8/8/8/4K3 b - - 0 1").is_ok());
+    }
+
+    #[test]
+    fn test_castling_rights() {
+        assert_eq!(
+            parse_error("4k3/8/8/8/8/8/8/4K3 w K - 0 1"),
+            FenParsingError::CastlingWithoutKingOrRook(Side::White, CastlingSide::Short)
+        );
+        assert_eq!(
+            parse_error("r3k2r/8/8/8/8/8/8/4K2R w Qkq - 0 1"),
+            FenParsingError::CastlingWithoutKingOrRook(Side::White, CastlingSide::Long)
+        );
+        assert_eq!(
+            parse_error("4k3/8/8/8/8/8/8/R3K3 w H - 0 1"),
+            FenParsingError::CastlingWithoutKingOrRook(Side::White, CastlingSide::Short)
+        );
+        // A King in a corner file has no Rook outside it to castle with
+        assert_eq!(
+            parse_error("4k3/8/8/8/8/8/8/K6R w K - 0 1"),
+            FenParsingError::CastlingWithoutKingOrRook(Side::White, CastlingSide::Short)
+        );
+        assert_eq!(
+            parse_error("r6k/8/8/8/8/8/8/4K3 w q - 0 1"),
+            FenParsingError::CastlingWithoutKingOrRook(Side::Black, CastlingSide::Long)
+        );
+        assert!(from_fen("r3k3/8/8/8/8/8/8/4K2R w Kq - 0 1").is_ok());
+    }
+
+    #[test]
+    fn test_chess960_castling_rights() {
+        let round_trip = |fen: &str| {
+            let (position, clocks) = from_fen(fen).expect("FEN should be accepted");
+            (
+                to_fen(position, &clocks),
+                to_shredder_fen(position, &clocks),
+            )
+        };
+
+        // Shredder-FEN reads back as X-FEN, which uses `KQkq` for the outermost Rooks
+        let (x_fen, shredder_fen) =
+            round_trip("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9");
+        assert_eq!(
+            x_fen,
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w KQkq - 2 9"
+        );
+        assert_eq!(
+            shredder_fen,
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9"
+        );
+
+        // An inner Rook keeps its file letter in X-FEN
+        assert_eq!(
+            round_trip("4k3/8/8/8/8/8/8/RR2K3 w B - 0 1").0,
+            "4k3/8/8/8/8/8/8/RR2K3 w B - 0 1"
+        );
+        let (position, _) = from_fen("4k3/8/8/8/8/8/8/RR2K3 w Q - 0 1").unwrap();
+        assert_eq!(
+            position
+                .get_castling_rights()
+                .rook_file(Side::White, CastlingSide::Long),
+            Some(0)
+        );
+
+        // The side is given by where the Rook stands relative to the King
+        let (position, _) = from_fen("1r3kr1/8/8/8/8/8/8/4K1R1 w Gbg - 0 1").unwrap();
+        let rights = position.get_castling_rights();
+        assert_eq!(rights.rook_file(Side::White, CastlingSide::Short), Some(6));
+        assert_eq!(rights.rook_file(Side::Black, CastlingSide::Long), Some(1));
+        assert_eq!(rights.rook_file(Side::Black, CastlingSide::Short), Some(6));
+    }
+
+    #[test]
+    fn test_en_passant() {
+        // No pawn in front of the target
+        assert_eq!(
+            parse_error("4k3/8/8/8/8/8/4P3/4K3 b - e3 0 1"),
+            FenParsingError::ImpossibleEnPassant(square("e3"))
+        );
+        // Target on the wrong side's third rank
+        assert_eq!(
+            parse_error("4k3/8/8/8/4P3/8/8/4K3 w - e3 0 1"),
+            FenParsingError::ImpossibleEnPassant(square("e3"))
+        );
+        // The pawn's starting square is occupied
+        assert_eq!(
+            parse_error("4k3/8/8/8/4P3/8/4N3/4K3 b - e3 0 1"),
+            FenParsingError::ImpossibleEnPassant(square("e3"))
+        );
+    }
+}
@@ -8,21 +8,28 @@
 //! While the `BoardBackend` handles the raw spatial movement of pieces, the `BoardFrontend`
 //! acts as the arbiter. It sits on top of the backend to enforce the FIDE Laws of Chess,
 //! managing the turn cycle, maintaining move history (for undos and the 50-move rule),
-//! tracking position snapshots (for threefold repetition), and determining game outcomes
+//! tracking position hashes (for threefold repetition), and determining game outcomes
 //! like Checkmate or Stalemate.
 
-use std::{collections::HashMap, vec, fmt::Write};
+use std::vec;
 
 use crate::{
-    atoms::{CastlingRights, Coordinate, MoveCounter, Side},
-    moves::{Ply, SpecialMove, generate_legal_moves},
+    atoms::{Bitboard, CastlingRights, Coordinate, MoveCounter, Side},
+    moves::{CastlingSide, MoveList, Ply, generate_legal_moves},
+    pgn::PgnGame,
     pieces::Kind,
     rules::{
         CAN_CLAIM_FIFTY_MOVE_RULE_THRESHOLD, CAN_CLAIM_THREEFOLD_REPETITION_THRESHOLD, DrawReason,
         FORCED_FIFTY_MOVE_RULE_THRESHOLD, FORCED_THREEFOLD_REPETITION_THRESHOLD, Outcome,
         WinReason,
     },
-    state::{PositionSnapshot, board::Board, from_fen, to_fen},
+    state::{
+        FenParsingError, PositionSnapshot, Standard, Variant,
+        board::Board,
+        parse_fen,
+        positions::{chess960_back_rank, chess960_grid},
+        to_fen, to_shredder_fen, zobrist,
+    },
 };
 
 /// The main game controller for a chess game.
@@ -35,7 +42,7 @@ use crate::{
 /// * **Game Endings**: Detecting Checkmate, Stalemate, Draws (Repetition, Insufficient Material, etc.).
 /// * **FEN Parsing**: Loading game states from standard notation.
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Game {
+pub struct Game<V: Variant = Standard> {
     /// The physical state of the board (grid).
     backend: Board,
 
@@ -48,25 +55,35 @@ pub struct Game {
     /// The specific square available for En Passant capture, if any.
     en_passant_target: Option<Coordinate>,
 
+    /// A history of En Passant targets. Used to restore the target when undoing moves.
+    en_passant_log: Vec<Option<Coordinate>>,
+
     /// Tracks halfmoves, fullmoves, and the 50-move rule counter.
     move_counter: MoveCounter,
 
     /// A stack of all moves played in the game so far.
     move_log: Vec<Ply>,
 
-    /// Tracks how many times a specific position has occurred (for Threefold Repetition).
-    repetition_table: HashMap<PositionSnapshot, usize>,
+    /// The Zobrist hash of the position reached by each move in `move_log`.
+    /// Used to count repetitions (for Threefold Repetition).
+    hash_log: Vec<u64>,
+
+    /// The result declared by the players or an arbiter (resignation, agreement, claims, time).
+    /// Results following from the position itself are computed by [`Game::outcome`].
+    declared_outcome: Option<Outcome>,
 
-    /// The final result of the game, if it has ended.
-    outcome: Option<Outcome>,
+    /// The variant being played, which may override the standard rules.
+    variant: V,
 
-    /// Cached status indicating if the current player's King is in check.
-    in_check: bool,
+    /// A history of the state kept by the variant. Used to restore it when undoing moves.
+    variant_log: Vec<V>,
 
-    legal_moves_buffer: Vec<Ply>,
+    /// The length of `move_log` when each null move still in effect was made, so that a
+    /// null move is only undone as a null move, and a move only as a move.
+    null_move_log: Vec<usize>,
 }
 
-impl Game {
+impl<V: Variant> Game<V> {
     /// Creates a hashable snapshot of the current position.
     ///
     /// This is primarily used to populate the repetition table for detecting
@@ -76,224 +93,171 @@ impl Game {
         PositionSnapshot::new(
             *self.backend.grid(),
             self.turn,
-            self.castling_rights_log
-                .last()
-                .copied()
-                .unwrap_or(CastlingRights::no_rights()),
+            self.castling_rights(),
             self.en_passant_target,
         )
     }
 
-    /// Initializes a new game with the standard chess starting position.
+    /// Returns the 64-bit Zobrist hash of the current position.
+    ///
+    /// Two positions with the same piece placement, side to move, castling rights and
+    /// en passant target share the same hash, regardless of the moves that led to them.
+    /// The piece placement part is maintained incrementally by the [`Board`] as moves are
+    /// made and undone, so this is a constant-time operation.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use bonsai_chess::prelude::BoardFrontend;
+    /// use bonsai_chess::prelude::*;
     ///
-    /// let mut game = BoardFrontend::from_starting_position();
-    /// assert_eq!(game.outcome(), None);
+    /// let mut game = Game::from_starting_position();
+    /// let start = game.hash();
+    ///
+    /// // Shuffle both knights out and back in.
+    /// for (from, to) in [("g1", "f3"), ("g8", "f6"), ("f3", "g1"), ("f6", "g8")] {
+    ///     let from = Coordinate::from_algebraic_notation(from).unwrap();
+    ///     let to = Coordinate::from_algebraic_notation(to).unwrap();
+    ///     let knight_move = game
+    ///         .get_legal_moves()
+    ///         .into_iter()
+    ///         .find(|ply| ply.starting_square() == from && ply.ending_square() == to)
+    ///         .unwrap();
+    ///     game.make_move(&knight_move);
+    /// }
+    ///
+    /// assert_eq!(game.hash(), start);
     /// ```
     #[must_use]
-    pub fn from_starting_position() -> Self {
-        Self {
-            backend: Board::from_starting_position(),
-            turn: Side::White,
-            castling_rights_log: vec![CastlingRights::new()],
-            en_passant_target: None,
+    pub fn hash(&self) -> u64 {
+        self.backend.hash()
+            ^ zobrist::side_to_move(self.turn)
+            ^ zobrist::castling(self.castling_rights())
+            ^ zobrist::en_passant(self.en_passant_target)
+            ^ self.variant.zobrist_hash()
+    }
 
-            move_counter: MoveCounter::new(),
+    /// Returns the castling rights currently available to both players.
+    #[must_use]
+    pub fn castling_rights(&self) -> CastlingRights {
+        self.castling_rights_log
+            .last()
+            .copied()
+            .unwrap_or(CastlingRights::no_rights())
+    }
 
-            move_log: Vec::new(),
+    /// Returns the square a pawn can capture en passant on, if the last move was a double push.
+    #[must_use]
+    pub const fn en_passant_target(&self) -> Option<Coordinate> {
+        self.en_passant_target
+    }
 
-            repetition_table: HashMap::new(),
+    /// Creates a game of `variant` from a Forsyth–Edwards Notation (FEN) string, reporting
+    /// why it was rejected if it is invalid.
+    ///
+    /// Variants that keep extra state in the FEN, like the check counters of
+    /// [`ThreeCheck`](crate::state::ThreeCheck) or the pockets of
+    /// [`Crazyhouse`](crate::state::Crazyhouse), read it before the rest of the FEN is
+    /// parsed, and validate the position by their own rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`FenParsingError`] describing the first problem found in the FEN.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// let game = Game::try_from_fen_with_variant(
+    ///     "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 +2+0",
+    ///     ThreeCheck::default(),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(game.variant().checks_given(Side::White), 2);
+    /// ```
+    pub fn try_from_fen_with_variant(fen: &str, mut variant: V) -> Result<Self, FenParsingError> {
+        let fen = variant.read_fen(fen)?;
 
-            outcome: None,
+        let (position_snapshot, clock) = parse_fen(&fen)?;
+        variant.validate_position(&position_snapshot)?;
 
-            in_check: false,
+        Ok(Self::from_snapshot(position_snapshot, clock, variant))
+    }
 
-            legal_moves_buffer: Vec::with_capacity(256),
+    /// Creates a game starting from `position`, with no move played.
+    ///
+    /// The position is trusted to be valid for the variant.
+    pub(super) fn from_snapshot(
+        position: PositionSnapshot,
+        clock: MoveCounter,
+        variant: V,
+    ) -> Self {
+        Self {
+            backend: Board::new(position.get_grid()),
+            turn: position.get_turn(),
+            castling_rights_log: vec![position.get_castling_rights()],
+            en_passant_target: position.get_en_passant(),
+            en_passant_log: Vec::new(),
+            move_counter: clock,
+            move_log: Vec::new(),
+            hash_log: Vec::new(),
+            declared_outcome: None,
+            variant,
+            variant_log: Vec::new(),
+            null_move_log: Vec::new(),
         }
     }
 
-    /// Creates a game state from a Forsyth–Edwards Notation (FEN) string.
+    /// Creates a game of `variant` from its own starting position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the variant's [`starting_fen`](Variant::starting_fen) is not a valid FEN.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use bonsai_chess::prelude::BoardFrontend;
+    /// use bonsai_chess::prelude::*;
     ///
-    /// // Standard start
-    /// let start = BoardFrontend::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    /// let game = Game::with_variant(RacingKings);
+    /// assert_eq!(game.to_fen(), "8/8/8/8/8/8/krbnNBRK/qrbnNBRQ w - - 0 1");
     /// ```
     #[must_use]
-    pub fn from_fen(fen: &str) -> Self {
-        if let Ok((position_snapshot, clock)) = from_fen(fen) {
-            let backend = Board::new(position_snapshot.get_grid());
-            Self {
-                backend,
-                turn: position_snapshot.get_turn(),
-                castling_rights_log: vec![position_snapshot.get_castling_rights()],
-                en_passant_target: position_snapshot.get_en_passant(),
-                move_counter: clock,
-                move_log: Vec::new(),
-                repetition_table: HashMap::new(),
-                outcome: None,
-                in_check: backend.is_square_under_attack(
-                    match position_snapshot.get_turn() {
-                        Side::White => backend.get_white_king(),
-                        Side::Black => backend.get_black_king(),
-                    },
-                    position_snapshot.get_turn().opposite(),
-                ),
-                legal_moves_buffer: Vec::with_capacity(256),
-            }
-        } else {
-            Self::from_starting_position()
-        }
+    pub fn with_variant(variant: V) -> Self {
+        Self::try_from_fen_with_variant(variant.starting_fen(), variant)
+            .expect("the starting position of a variant must be a valid FEN")
+    }
+
+    /// Returns the variant being played, along with any state it keeps.
+    #[must_use]
+    pub const fn variant(&self) -> &V {
+        &self.variant
     }
 
     /// Serializes the current game state into a standard FEN string.
+    ///
+    /// In Chess960, castling rights are written in X-FEN: `KQkq` unless a right castles
+    /// with a Rook that is not the outermost one, which is written as its file letter.
     #[must_use]
     pub fn to_fen(&self) -> String {
-        to_fen(self.create_snapshot(), &self.move_counter)
+        let mut fen = to_fen(self.create_snapshot(), &self.move_counter);
+        self.variant.write_fen(&mut fen);
+        fen
     }
 
-
-    /// Generates a PGN (Portable Game Notation) string of the current game.
+    /// Serializes the current game state into a Shredder-FEN string, where castling
+    /// rights are written as the files of the castling Rooks (`HAha`).
     #[must_use]
-    pub fn to_pgn(&self) -> String {
-        let mut pgn = String::new();
-
-        let mut replay_game = self.clone();
-        let mut moves = Vec::new();
-        while let Some(m) = replay_game.move_log.last().copied() {
-            moves.push(m);
-            replay_game.undo_last_move();
-        }
-        moves.reverse();
-
-        let initial_fen = replay_game.to_fen();
-        let is_standard = initial_fen.starts_with("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq");
-
-        // 1. Add required PGN Headers
-        pgn.push_str("[Event \"Bonsai Chess Game\"]\n");
-        pgn.push_str("[Site \"https://bonsai.niblit.dev\"]\n");
-        pgn.push_str("[Date \"??\"]\n");
-        pgn.push_str("[Round \"1\"]\n");
-        pgn.push_str("[White \"Bonsai Website User\"]\n");
-        pgn.push_str("[Black \"Bonsai Chess Engine\"]\n");
-
-        let result_str = match self.outcome() {
-            Some(Outcome::Win { winner: Side::White, .. }) => "1-0",
-            Some(Outcome::Win { winner: Side::Black, .. }) => "0-1",
-            Some(Outcome::Draw { .. }) => "1/2-1/2",
-            None => "*",
-        };
-        
-        let _ = writeln!(pgn, "[Result \"{result_str}\"]");
-
-        if !is_standard {
-            pgn.push_str("[SetUp \"1\"]\n");
-            let _ = writeln!(pgn, "[FEN \"{initial_fen}\"]");
-        }
-
-        pgn.push_str("[Mode \"online\"]\n\n");
-
-        // 2. Format the Move Log
-        let mut move_sans = Vec::new();
-        for ply in &moves {
-            let mut san = String::new();
-            
-            if let Some(SpecialMove::Castle(castling_side)) = ply.special_move() {
-                san.push_str(match castling_side {
-                    crate::moves::CastlingSide::Short => "O-O",
-                    crate::moves::CastlingSide::Long => "O-O-O",
-                });
-            } else {
-                let piece = ply.piece_moved();
-                if piece.kind() != Kind::Pawn {
-                    san.push_str(&piece.kind().to_string());
-                    
-                    let legal_moves = replay_game.get_legal_moves();
-                    let mut similar_starts = Vec::new();
-                    for m in legal_moves {
-                        if m.piece_moved().kind() == piece.kind() 
-                            && m.ending_square() == ply.ending_square() 
-                            && m.starting_square() != ply.starting_square() {
-                            similar_starts.push(m.starting_square());
-                        }
-                    }
-                    
-                    if !similar_starts.is_empty() {
-                        let mut same_file = false;
-                        let mut same_rank = false;
-                        for start in &similar_starts {
-                            if start.column() == ply.starting_square().column() {
-                                same_file = true;
-                            }
-                            if start.row() == ply.starting_square().row() {
-                                same_rank = true;
-                            }
-                        }
-                        
-                        let file_char = (ply.starting_square().column() as u8 + b'a') as char;
-                        let rank_char = (8 - ply.starting_square().row() as u8 + b'0') as char;
-                        
-                        if !same_file {
-                            san.push(file_char);
-                        } else if !same_rank {
-                            san.push(rank_char);
-                        } else {
-                            san.push(file_char);
-                            san.push(rank_char);
-                        }
-                    }
-                }
-                
-                let is_capture = ply.piece_captured().is_some() || matches!(ply.special_move(), Some(SpecialMove::EnPassant(_)));
-                
-                if is_capture {
-                    if piece.kind() == Kind::Pawn {
-                        let file_char = (ply.starting_square().column() as u8 + b'a') as char;
-                        san.push(file_char);
-                    }
-                    san.push('x');
-                }
-                
-                san.push_str(&ply.ending_square().to_algebraic_notation());
-                
-                if let Some(SpecialMove::Promotion(promoted_piece)) = ply.special_move() {
-                    san.push('=');
-                    san.push_str(&promoted_piece.to_string());
-                }
-            }
-            
-            replay_game.make_move(ply);
-            
-            if replay_game.outcome().is_some() {
-                if let Some(Outcome::Win { reason: WinReason::Checkmate, .. }) = replay_game.outcome() {
-                    san.push('#');
-                }
-            } else if replay_game.is_in_check() {
-                san.push('+');
-            }
-            
-            move_sans.push(san);
-        }
-
-        for (i, chunk) in move_sans.chunks(2).enumerate() {
-            let _ = write!(pgn, "{}.{} ", i + 1, chunk[0]);
-            
-            if let Some(black_move) = chunk.get(1) {
-                let _ = write!(pgn, "{black_move} ");
-            }
-        }
-
-        // 3. Append the game result marker to the very end
-        pgn.push_str(result_str);
+    pub fn to_shredder_fen(&self) -> String {
+        let mut fen = to_shredder_fen(self.create_snapshot(), &self.move_counter);
+        self.variant.write_fen(&mut fen);
+        fen
+    }
 
-        pgn
+    /// Returns the halfmove clock and fullmove number of the game.
+    #[must_use]
+    pub const fn move_counter(&self) -> &MoveCounter {
+        &self.move_counter
     }
 
     /// Returns the team whose turn it is to move.
@@ -312,34 +276,56 @@ impl Game {
     ///
     /// This process involves calculating the tactical context (pins, checks, and danger squares)
     /// and then generating strictly legal moves for all pieces of the active color.
+    ///
+    /// This allocates a new vector on every call; see
+    /// [`generate_moves_into`](Self::generate_moves_into) for hot loops.
     #[must_use]
-    pub fn get_legal_moves(&mut self) -> Vec<Ply> {
-        self.legal_moves_buffer.clear();
-        let legality_context = self.backend.calculate_legality_context(self.turn);
-
-        // 2. Generate strictly legal moves
-        let pieces = match self.turn {
-            Side::White => self.backend.get_white_pieces(),
-            Side::Black => self.backend.get_black_pieces(),
-        };
+    pub fn get_legal_moves(&self) -> Vec<Ply> {
+        let mut moves = MoveList::new();
+        self.generate_moves_into(&mut moves);
+        moves.to_vec()
+    }
 
-        let castling = self
-            .castling_rights_log
-            .last()
-            .copied()
-            .unwrap_or(CastlingRights::no_rights());
+    /// Generates all fully legal moves for the current position into `moves`,
+    /// replacing its previous content.
+    ///
+    /// Unlike [`get_legal_moves`](Self::get_legal_moves), this never allocates: the
+    /// list lives on the caller's stack, and can be reused from one position to the next.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// let mut game = Game::from_starting_position();
+    /// let mut moves = MoveList::new();
+    ///
+    /// game.generate_moves_into(&mut moves);
+    /// game.make_move(&moves[0]);
+    ///
+    /// game.generate_moves_into(&mut moves);
+    /// assert_eq!(moves.len(), 20);
+    /// ```
+    pub fn generate_moves_into(&self, moves: &mut MoveList) {
+        moves.clear();
+        let legality_context = self.backend.calculate_legality_context(self.turn);
+        let castling = self.castling_rights();
 
-        for current_piece in pieces {
+        for current_piece in self.backend.side_pieces(self.turn) {
             generate_legal_moves(
                 current_piece,
                 &self.backend,
                 self.en_passant_target,
                 castling,
                 &legality_context,
-                &mut self.legal_moves_buffer,
+                moves,
             );
         }
-        self.legal_moves_buffer.clone()
+
+        self.variant
+            .extend_legal_moves(&self.backend, self.turn, &legality_context, moves);
+        self.variant
+            .retain_legal_moves(&self.backend, self.turn, moves);
     }
 
     /// Helper to identify if a move is a pawn double-push that enables En Passant.
@@ -368,20 +354,59 @@ impl Game {
     /// Executes a move and updates the game state.
     ///
     /// This function handles the full lifecycle of a move:
+    /// * Refusing it if the game is already over.
     /// * Delegating the physical piece displacement to the backend.
     /// * Logging the move and updating state constraints (castling, en passant).
     /// * Switching the turn.
-    /// * Detecting Checkmate, Stalemate, and automatic Draw conditions.
+    ///
+    /// Checkmate, Stalemate, and automatic Draw conditions are then reported by
+    /// [`outcome`](Self::outcome).
     ///
     /// # Arguments
     ///
-    /// * `ply` - The fully formed legal move to execute.
+    /// * `ply` - The fully formed legal move to execute. It is trusted to be legal: use
+    ///   [`try_make_move`](Self::try_make_move) for moves from untrusted sources.
     pub fn make_move(&mut self, ply: &Ply) {
         // Cannot perform action if game is over
-        if self.outcome.is_some() {
+        if self.outcome().is_some() {
             return;
         }
 
+        self.make_move_unchecked(ply);
+    }
+
+    /// Executes a move without checking whether the game is already over.
+    ///
+    /// This is the fast path used by search and perft: it only updates the board, the
+    /// castling and en passant rights, the counters and the position history, which are
+    /// all needed to [`unmake_move`](Self::unmake_move) it. Whether the move ended the
+    /// game is only worked out when [`outcome`](Self::outcome) is asked for, and the
+    /// variant only pays for the state it keeps: standard chess neither looks for checks
+    /// nor logs any variant state.
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - The fully formed legal move to execute.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// let mut game = Game::from_starting_position();
+    /// for san in ["f3", "e5", "g4"] {
+    ///     let ply = game.parse_san(san).unwrap();
+    ///     game.make_move_unchecked(&ply);
+    /// }
+    ///
+    /// let mate = game.parse_san("Qh4#").unwrap();
+    /// game.make_move_unchecked(&mate);
+    /// assert!(game.outcome().is_some_and(|outcome| outcome.is_win()));
+    ///
+    /// game.unmake_move();
+    /// assert_eq!(game.outcome(), None);
+    /// ```
+    pub fn make_move_unchecked(&mut self, ply: &Ply) {
         // Do low level board move
         self.backend.make_move(ply);
 
@@ -389,6 +414,7 @@ impl Game {
         self.move_log.push(*ply);
 
         // If move is a pawn double extension, keep track of en_passant possibility
+        self.en_passant_log.push(self.en_passant_target);
         self.en_passant_target = Self::get_en_passant_target(ply);
 
         // update CastlingRights
@@ -397,73 +423,26 @@ impl Game {
         // Set turn to opponent
         self.change_turn();
 
-        // Check if last move left opponent's king in check
-        self.in_check = self.is_in_check();
-
-        // --- Draw Detection: Threefold Repetition ---
-        let snapshot = self.create_snapshot();
-
-        // Increment the repetition count for the current position
-        let repetitions = self.repetition_table.entry(snapshot).or_insert(0);
-        *repetitions += 1;
-
-        // Only the current position got incremented, so check for Threefold Repetition
-        if *repetitions >= FORCED_THREEFOLD_REPETITION_THRESHOLD && self.outcome.is_none() {
-            self.outcome = Some(Outcome::Draw {
-                reason: DrawReason::ThreefoldRepetition,
-            });
-        }
+        // Let the variant follow the move, knowing whether it gave check if it cares
+        let gives_check = V::TRACKS_CHECKS && self.is_in_check();
+        self.push_variant_state();
+        self.variant.move_made(ply, gives_check);
 
-        // --- Draw Detection: Dead Position (Insufficient Material) ---
-        // Currently only checks for King vs King.
-
-        // TODO: Expand to K vs K+N, K vs K+B, etc.
-        // According to the rules of a dead position, Article 5.2 b, when there is no possibility of checkmate for either side with any series of legal moves, the position is an immediate draw if
-
-        //    Both Sides have a bare King
-        //    One Side has a King and a Minor Piece against a bare King
-        //    Both Sides have a King and a Bishop, the Bishops being the same Color
-        if self
-            .backend
-            .get_all_pieces()
-            .iter()
-            .all(|located_piece| located_piece.piece().kind() == Kind::King)
-        {
-            self.outcome = Some(Outcome::Draw {
-                reason: DrawReason::DeadPosition,
-            });
-        }
+        // Remember the position reached, for Threefold Repetition
+        self.hash_log.push(self.hash());
 
-        // --- Draw Detection: 50-Move Rule ---
-        // The rule resets if a Pawn is moved or a capture is made.
+        // The 50-move rule counter resets if a Pawn is moved or a capture is made.
         let is_pawn_move = ply.piece_moved().kind() == Kind::Pawn;
         let is_capture = ply.piece_captured().is_some();
         self.move_counter.tick(is_pawn_move || is_capture);
+    }
 
-        if self.move_counter.fifty_move_rule_counter() >= FORCED_FIFTY_MOVE_RULE_THRESHOLD
-            && self.outcome.is_none()
-        {
-            self.outcome = Some(Outcome::Draw {
-                reason: DrawReason::FiftyMoveRule,
-            });
-        }
-
-        // --- Win/Loss Detection: Checkmate & Stalemate ---
-        // If the current player has NO legal moves...
-        let legal_moves_after_move = self.get_legal_moves();
-        if legal_moves_after_move.is_empty() {
-            if self.is_in_check() {
-                // ...and is in check -> Checkmate.
-                self.outcome = Some(Outcome::Win {
-                    winner: self.turn.opposite(),
-                    reason: WinReason::Checkmate,
-                });
-            } else {
-                // ...and is NOT in check -> Stalemate.
-                self.outcome = Some(Outcome::Draw {
-                    reason: DrawReason::Stalemate,
-                });
-            }
+    /// Saves the state of the variant, to be restored when the move is undone.
+    ///
+    /// Variants without state (like [`Standard`]) have nothing to restore, so nothing is saved.
+    fn push_variant_state(&mut self) {
+        if size_of::<V>() > 0 {
+            self.variant_log.push(self.variant);
         }
     }
 
@@ -472,36 +451,36 @@ impl Game {
     /// Restores the board, turn, castling rights, and move counters to their previous state.
     /// Commonly used in search algorithms (Perft, Minimax).
     pub fn undo_last_move(&mut self) {
-        if let Some(last_move) = self.move_log.pop() {
-            self.undo_move(&last_move);
-        }
+        self.unmake_move();
     }
 
-    /// Internal logic for reverting a move.
-    fn undo_move(&mut self, ply: &Ply) {
-        // check for dead position
-        self.outcome = None;
+    /// Reverts the most recent move, whether it was played by [`make_move`](Self::make_move)
+    /// or [`make_move_unchecked`](Self::make_move_unchecked).
+    ///
+    /// Null moves made after it are undone first, so that the game is back to the
+    /// position the move was played from. Any declared result, like a resignation, is
+    /// withdrawn along with it.
+    pub fn unmake_move(&mut self) {
+        while self.undo_null_move() {}
 
-        // undo threefold repetition table
-        let snapshot = self.create_snapshot();
-        if let std::collections::hash_map::Entry::Occupied(mut entry) =
-            self.repetition_table.entry(snapshot)
-        {
-            let count = entry.get_mut();
-            *count -= 1;
-            if *count == 0 {
-                entry.remove();
-            }
+        let Some(ply) = self.move_log.pop() else {
+            return;
+        };
+
+        self.declared_outcome = None;
+        self.hash_log.pop();
+
+        // restore the state of the variant, if it keeps any
+        if let Some(variant) = self.variant_log.pop() {
+            self.variant = variant;
         }
 
         // Low level move
-        self.backend.undo_move(ply);
+        self.backend.undo_move(&ply);
 
         // Keep track of en en_passant_target
-        if let Some(possible_pawn_move) = self.move_log.last() {
-            self.en_passant_target = Self::get_en_passant_target(possible_pawn_move);
-        } else {
-            self.en_passant_target = None;
+        if let Some(en_passant_target) = self.en_passant_log.pop() {
+            self.en_passant_target = en_passant_target;
         }
 
         // reduce move MoveCounter
@@ -514,9 +493,81 @@ impl Game {
 
         // set turn to opponent
         self.change_turn();
+    }
+
+    /// Passes the turn to the opponent without moving (a "null move").
+    ///
+    /// This is not a legal move in chess, but engines use it for null-move pruning, and it
+    /// answers "what would the opponent do if I passed?". The En Passant target is cleared,
+    /// and the move counters advance as for a quiet move. The position reached counts
+    /// towards repetitions, but the null move is not added to the move log.
+    ///
+    /// Undo it with [`undo_null_move`](Self::undo_null_move). Moves made after it can be
+    /// unmade as usual, and [`unmake_move`](Self::unmake_move) undoes the null moves made
+    /// after the move it reverts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the current player is in check, since passing would leave the
+    /// King to be captured.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// let mut game = Game::from_starting_position();
+    /// let e4 = game.parse_san("e4").unwrap();
+    /// game.make_move(&e4);
+    /// let before = (game.to_fen(), game.hash());
+    ///
+    /// game.make_null_move().unwrap();
+    /// assert_eq!(game.turn(), Side::White);
+    /// assert_eq!(game.to_fen(), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 1 2");
+    ///
+    /// assert!(game.undo_null_move());
+    /// assert_eq!((game.to_fen(), game.hash()), before);
+    ///
+    /// // The last action is now 1.e4, which is not a null move
+    /// assert!(!game.undo_null_move());
+    /// assert_eq!(game.to_fen(), before.0);
+    /// ```
+    pub fn make_null_move(&mut self) -> Result<(), &'static str> {
+        if self.is_in_check() {
+            return Err("Cannot pass the turn while in check.");
+        }
+
+        self.null_move_log.push(self.move_log.len());
+        self.en_passant_log.push(self.en_passant_target);
+        self.en_passant_target = None;
+        self.move_counter.tick(false);
+        self.change_turn();
 
-        // check for king check
-        self.in_check = self.is_in_check();
+        self.push_variant_state();
+        self.hash_log.push(self.hash());
+        Ok(())
+    }
+
+    /// Reverts the null move made by [`make_null_move`](Self::make_null_move), restoring
+    /// the turn, the En Passant target, and the move counters.
+    ///
+    /// Returns `false`, leaving the game untouched, if the last action was not a null move.
+    pub fn undo_null_move(&mut self) -> bool {
+        if self.null_move_log.last() != Some(&self.move_log.len()) {
+            return false;
+        }
+        self.null_move_log.pop();
+
+        self.hash_log.pop();
+        if let Some(variant) = self.variant_log.pop() {
+            self.variant = variant;
+        }
+        if let Some(en_passant_target) = self.en_passant_log.pop() {
+            self.en_passant_target = en_passant_target;
+        }
+        self.move_counter.untick();
+        self.change_turn();
+        true
     }
 
     /// Updates castling rights based on the move played.
@@ -527,11 +578,7 @@ impl Game {
     ///
     /// * `ply` - The move that was just executed.
     pub fn update_castling_rights(&mut self, ply: &Ply) {
-        let mut castling_rights = self
-            .castling_rights_log
-            .last()
-            .copied()
-            .unwrap_or(CastlingRights::no_rights());
+        let mut castling_rights = self.castling_rights();
 
         if ply.piece_moved().kind() == Kind::King {
             match ply.piece_moved().team() {
@@ -546,22 +593,28 @@ impl Game {
             }
         }
 
-        let mut check_then_ban = |x, y| match (x, y) {
-            (0, 0) => castling_rights.disable_black_queen_side(),
-            (0, 7) => castling_rights.disable_black_king_side(),
-            (7, 0) => castling_rights.disable_white_queen_side(),
-            (7, 7) => castling_rights.disable_white_king_side(),
-            _ => {}
+        // Losing the Rook a right castles with loses that right, whether it moved or was captured
+        let mut check_then_ban = |square: Coordinate| {
+            let side = match square.row() {
+                0 => Side::Black,
+                7 => Side::White,
+                _ => return,
+            };
+            for castling_side in [CastlingSide::Short, CastlingSide::Long] {
+                if castling_rights.rook_file(side, castling_side) == Some(square.column()) {
+                    castling_rights.disable(side, castling_side);
+                }
+            }
         };
 
         if ply.piece_moved().kind() == Kind::Rook {
-            check_then_ban(ply.starting_square().row(), ply.starting_square().column());
+            check_then_ban(ply.starting_square());
         }
 
         if let Some(piece_captured) = ply.piece_captured()
             && piece_captured.kind() == Kind::Rook
         {
-            check_then_ban(ply.ending_square().row(), ply.ending_square().column());
+            check_then_ban(ply.ending_square());
         }
 
         self.castling_rights_log.push(castling_rights);
@@ -591,9 +644,62 @@ impl Game {
     }
 
     /// Returns the game outcome (Win, Draw, or None if ongoing).
+    ///
+    /// A result declared with [`resign`](Self::resign), a claim, or the clock comes first.
+    /// Otherwise the outcome is worked out from the current position, in order of precedence:
+    /// * The variant's own rules.
+    /// * Checkmate and Stalemate, which need every legal move to be generated.
+    /// * A Dead Position.
+    /// * Fivefold Repetition, then the 75-move rule.
     #[must_use]
-    pub const fn outcome(&self) -> Option<Outcome> {
-        self.outcome
+    pub fn outcome(&self) -> Option<Outcome> {
+        if self.declared_outcome.is_some() {
+            return self.declared_outcome;
+        }
+
+        // --- Variant rules: extra win conditions take precedence ---
+        if let Some(outcome) = self.variant.outcome(self) {
+            return Some(outcome);
+        }
+
+        // --- Win/Loss Detection: Checkmate & Stalemate ---
+        // If the current player has NO legal moves...
+        let mut legal_moves = MoveList::new();
+        self.generate_moves_into(&mut legal_moves);
+        if legal_moves.is_empty() {
+            return Some(if self.is_in_check() {
+                // ...and is in check -> Checkmate.
+                Outcome::Win {
+                    winner: self.turn.opposite(),
+                    reason: WinReason::Checkmate,
+                }
+            } else {
+                // ...and is NOT in check -> Stalemate.
+                Outcome::Draw {
+                    reason: DrawReason::Stalemate,
+                }
+            });
+        }
+
+        // --- Draw Detection: Dead Position (Insufficient Material) ---
+        // FIDE Article 5.2.2: the game is drawn when neither side can checkmate by any series of legal moves
+        let reason = if self.variant.is_dead_position(self) {
+            DrawReason::DeadPosition
+        } else if self.repetitions() >= FORCED_THREEFOLD_REPETITION_THRESHOLD {
+            DrawReason::ThreefoldRepetition
+        } else if self.move_counter.fifty_move_rule_counter() >= FORCED_FIFTY_MOVE_RULE_THRESHOLD {
+            DrawReason::FiftyMoveRule
+        } else {
+            return None;
+        };
+
+        Some(Outcome::Draw { reason })
+    }
+
+    /// Counts how many times the current position was reached by a move.
+    fn repetitions(&self) -> usize {
+        let hash = self.hash();
+        self.hash_log.iter().filter(|&&seen| seen == hash).count()
     }
 
     /// Resigns the game on behalf of the specified player.
@@ -604,7 +710,7 @@ impl Game {
     ///
     /// * `resigning_player` - The team that is resigning (e.g., `Team::White`).
     pub const fn resign(&mut self, resigning_player: Side) {
-        self.outcome = Some(Outcome::Win {
+        self.declared_outcome = Some(Outcome::Win {
             winner: resigning_player.opposite(),
             reason: WinReason::Resign,
         });
@@ -614,13 +720,14 @@ impl Game {
     ///
     /// This should be called when a player's clock hits zero and the opponent
     /// has sufficient material to checkmate. If the opponent does *not* have
-    /// sufficient material, use [`draw_on_time`](Self::draw_on_time) instead.
+    /// sufficient material, use [`draw_on_time`](Self::draw_on_time) instead,
+    /// or let [`flag`](Self::flag) decide.
     ///
     /// # Arguments
     ///
     /// * `flagged_player` - The team that ran out of time.
     pub const fn win_on_time(&mut self, flagged_player: Side) {
-        self.outcome = Some(Outcome::Win {
+        self.declared_outcome = Some(Outcome::Win {
             winner: flagged_player.opposite(),
             reason: WinReason::WinOnTime,
         });
@@ -635,7 +742,7 @@ impl Game {
     ///
     /// * `forfeited_player` - The team that lost the game.
     pub const fn forfeit(&mut self, forfeited_player: Side) {
-        self.outcome = Some(Outcome::Win {
+        self.declared_outcome = Some(Outcome::Win {
             winner: forfeited_player.opposite(),
             reason: WinReason::Forfeit,
         });
@@ -648,7 +755,7 @@ impl Game {
     /// 2. One player commits a forfeitable offense, but the opponent does not have
     ///    sufficient material to mate.
     pub const fn draw_by_forfeit(&mut self) {
-        self.outcome = Some(Outcome::Draw {
+        self.declared_outcome = Some(Outcome::Draw {
             reason: DrawReason::Forfeit,
         });
     }
@@ -657,7 +764,7 @@ impl Game {
     ///
     /// This represents the players agreeing to a draw during the game.
     pub const fn draw_by_agreement(&mut self) {
-        self.outcome = Some(Outcome::Draw {
+        self.declared_outcome = Some(Outcome::Draw {
             reason: DrawReason::DrawByAgreement,
         });
     }
@@ -667,11 +774,80 @@ impl Game {
     /// This outcome is used when a player flags (runs out of time), but their
     /// opponent cannot theoretically checkmate them (insufficient material).
     pub const fn draw_on_time(&mut self) {
-        self.outcome = Some(Outcome::Draw {
+        self.declared_outcome = Some(Outcome::Draw {
             reason: DrawReason::DrawOnTime,
         });
     }
 
+    /// Ends the game because a player ran out of time.
+    ///
+    /// Following FIDE Article 6.9, the opponent wins if they have the material to
+    /// checkmate (see [`has_mating_material`](Self::has_mating_material)), and the game
+    /// is drawn otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// // A lone Knight cannot mate a bare King
+    /// let mut game = Game::from_fen("4k3/8/8/8/8/8/8/4KN2 w - - 0 1");
+    /// game.flag(Side::Black);
+    /// assert_eq!(game.outcome(), Some(Outcome::Draw { reason: DrawReason::DrawOnTime }));
+    ///
+    /// // But it can when the opponent's own pieces block their King
+    /// let mut game = Game::from_fen("4k3/4p3/8/8/8/8/8/4KN2 w - - 0 1");
+    /// game.flag(Side::Black);
+    /// assert!(game.outcome().is_some_and(|outcome| outcome.is_win()));
+    /// ```
+    pub fn flag(&mut self, flagged_player: Side) {
+        if self.has_mating_material(flagged_player.opposite()) {
+            self.win_on_time(flagged_player);
+        } else {
+            self.draw_on_time();
+        }
+    }
+
+    /// Returns `true` if `side` could checkmate its opponent with some series of legal moves.
+    ///
+    /// A side without Pawns, Rooks or Queens can only mate with the help of the opponent's pieces:
+    /// * A bare King never can.
+    /// * A single Knight needs an opponent Rook, Bishop, Knight or Pawn to block the mated King.
+    /// * Bishops all standing on squares of one colour, like the opponent's Bishops, need an
+    ///   opponent Knight or Pawn.
+    ///
+    /// Any other material (two minor pieces, or Bishops on both colours) can mate.
+    #[must_use]
+    pub fn has_mating_material(&self, side: Side) -> bool {
+        let board = &self.backend;
+        let own = board.side(side);
+        let opponent = board.side(side.opposite());
+
+        let majors_and_pawns =
+            board.kind(Kind::Pawn) | board.kind(Kind::Rook) | board.kind(Kind::Queen);
+        if !(own & majors_and_pawns).is_empty() {
+            return true;
+        }
+
+        let knights = own & board.kind(Kind::Knight);
+        let bishops = own & board.kind(Kind::Bishop);
+
+        if !knights.is_empty() {
+            let blockers = opponent & !board.kind(Kind::King) & !board.kind(Kind::Queen);
+            return (knights | bishops).has_several() || !blockers.is_empty();
+        }
+
+        if !bishops.is_empty() {
+            let all_bishops = board.kind(Kind::Bishop);
+            let single_colour = (all_bishops & Bitboard::LIGHT_SQUARES).is_empty()
+                || (all_bishops & Bitboard::DARK_SQUARES).is_empty();
+            let blockers = opponent & (board.kind(Kind::Knight) | board.kind(Kind::Pawn));
+            return !single_colour || !blockers.is_empty();
+        }
+
+        false
+    }
+
     /// Attempts to claim a draw based on the Threefold Repetition rule.
     ///
     /// According to FIDE rules (Article 9.2), a player can claim a draw if the
@@ -692,7 +868,7 @@ impl Game {
             return Err("Cannot claim Threefold Repetition: Conditions not met.");
         }
 
-        self.outcome = Some(Outcome::Draw {
+        self.declared_outcome = Some(Outcome::Draw {
             reason: DrawReason::ThreefoldRepetition,
         });
         Ok(())
@@ -719,7 +895,7 @@ impl Game {
             return Err("Cannot claim Fifty Move Rule: Conditions not met.");
         }
 
-        self.outcome = Some(Outcome::Draw {
+        self.declared_outcome = Some(Outcome::Draw {
             reason: DrawReason::FiftyMoveRule,
         });
         Ok(())
@@ -736,10 +912,7 @@ impl Game {
     pub fn can_claim_threefold_repetition(&self) -> bool {
         // We only care if the *current* position has appeared 3+ times.
         // FIDE rules: You lose the right to claim if you change the position.
-        let current_snapshot = self.create_snapshot();
-        self.repetition_table
-            .get(&current_snapshot)
-            .is_some_and(|&count| count >= CAN_CLAIM_THREEFOLD_REPETITION_THRESHOLD)
+        self.repetitions() >= CAN_CLAIM_THREEFOLD_REPETITION_THRESHOLD
     }
 
     /// Checks if the current player is eligible to claim a draw by the Fifty-Move Rule.
@@ -754,3 +927,271 @@ impl Game {
         self.move_log.clone()
     }
 }
+
+impl Game {
+    /// Initializes a new game with the standard chess starting position.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::BoardFrontend;
+    ///
+    /// let mut game = BoardFrontend::from_starting_position();
+    /// assert_eq!(game.outcome(), None);
+    /// ```
+    #[must_use]
+    pub fn from_starting_position() -> Self {
+        Self {
+            backend: Board::from_starting_position(),
+            turn: Side::White,
+            castling_rights_log: vec![CastlingRights::new()],
+            en_passant_target: None,
+            en_passant_log: Vec::new(),
+
+            move_counter: MoveCounter::new(),
+
+            move_log: Vec::new(),
+
+            hash_log: Vec::new(),
+
+            declared_outcome: None,
+
+            variant: Standard,
+
+            variant_log: Vec::new(),
+            null_move_log: Vec::new(),
+        }
+    }
+
+    /// Creates a game of Chess960 (Fischer Random Chess) from the index of its
+    /// starting position, or `None` if the index is not below 960.
+    ///
+    /// Positions are numbered with Scharnagl's scheme, so index 518 is the standard
+    /// starting position. Both sides may castle with either Rook, wherever it stands.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::Game;
+    ///
+    /// let game = Game::from_chess960(0).unwrap();
+    /// assert_eq!(game.to_fen(), "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1");
+    /// assert_eq!(game.to_shredder_fen(), "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w HFhf - 0 1");
+    ///
+    /// assert_eq!(Game::from_chess960(518).unwrap().to_fen(), Game::from_starting_position().to_fen());
+    /// assert!(Game::from_chess960(960).is_none());
+    /// ```
+    #[must_use]
+    pub fn from_chess960(index: usize) -> Option<Self> {
+        let back_rank = chess960_back_rank(index)?;
+
+        let mut rooks = (0..back_rank.len()).filter(|file| back_rank[*file] == Kind::Rook);
+        let (long_rook, short_rook) = (rooks.next()?, rooks.next()?);
+
+        let mut castling_rights = CastlingRights::no_rights();
+        for side in [Side::White, Side::Black] {
+            castling_rights.enable(side, CastlingSide::Short, short_rook);
+            castling_rights.enable(side, CastlingSide::Long, long_rook);
+        }
+
+        Some(Self {
+            backend: Board::new(chess960_grid(back_rank)),
+            castling_rights_log: vec![castling_rights],
+            ..Self::from_starting_position()
+        })
+    }
+
+    /// Creates a game state from a Forsyth–Edwards Notation (FEN) string.
+    ///
+    /// If the FEN is invalid, this silently falls back to the standard starting
+    /// position. Use [`try_from_fen`](Self::try_from_fen) to find out why a FEN was rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::Game;
+    ///
+    /// // Standard start
+    /// let start = Game::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    /// ```
+    #[must_use]
+    pub fn from_fen(fen: &str) -> Self {
+        Self::try_from_fen(fen).unwrap_or_else(|_| Self::from_starting_position())
+    }
+
+    /// Creates a game state from a Forsyth–Edwards Notation (FEN) string, reporting
+    /// why it was rejected if it is invalid.
+    ///
+    /// Besides syntax errors, positions that cannot arise in a game are rejected too,
+    /// such as a missing King or pawns on the back ranks.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`FenParsingError`] describing the first problem found in the FEN.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::{FenParsingError, Game, Side};
+    ///
+    /// assert!(Game::try_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").is_ok());
+    ///
+    /// // Typo: the White King is missing
+    /// assert_eq!(
+    ///     Game::try_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQ1BNR w kq - 0 1"),
+    ///     Err(FenParsingError::MissingKing(Side::White)),
+    /// );
+    /// ```
+    pub fn try_from_fen(fen: &str) -> Result<Self, FenParsingError> {
+        Self::try_from_fen_with_variant(fen, Standard)
+    }
+
+    /// Generates a PGN (Portable Game Notation) string of the current game.
+    ///
+    /// The tags describe a game played on the Bonsai website. For other tags, build a
+    /// [`PgnGame`] with [`PgnGame::from_game`] and set them with [`PgnGame::set_tag`].
+    #[must_use]
+    pub fn to_pgn(&self) -> String {
+        let mut pgn_game = PgnGame::from_game(self);
+
+        pgn_game.set_tag("Event", "Bonsai Chess Game");
+        pgn_game.set_tag("Site", "https://bonsai.niblit.dev");
+        pgn_game.set_tag("Date", "??");
+        pgn_game.set_tag("Round", "1");
+        pgn_game.set_tag("White", "Bonsai Website User");
+        pgn_game.set_tag("Black", "Bonsai Chess Engine");
+        pgn_game.set_tag("Mode", "online");
+
+        pgn_game.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        atoms::Side,
+        rules::{DrawReason, Outcome, WinReason},
+        state::Game,
+    };
+
+    #[test]
+    fn test_mating_material() {
+        // (FEN, White can mate, Black can mate)
+        let cases = [
+            ("4k3/8/8/8/8/8/8/4K3 w - - 0 1", false, false),
+            ("4k3/8/8/8/8/8/8/4KB2 w - - 0 1", false, false),
+            ("4k3/8/8/8/8/8/8/4KN2 w - - 0 1", false, false),
+            ("4k3/8/8/8/8/8/8/3NKN2 w - - 0 1", true, false),
+            ("4k3/8/8/8/8/8/8/3BKN2 w - - 0 1", true, false),
+            ("4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1", false, false),
+            ("2b1k3/8/8/8/8/8/8/2B1KB2 w - - 0 1", true, true),
+            ("4k3/1b6/b7/8/8/8/6B1/4KB2 w - - 0 1", false, false),
+            ("4kn2/8/8/8/8/8/8/4KB2 w - - 0 1", true, true),
+            ("4kq2/8/8/8/8/8/8/4KN2 w - - 0 1", false, true),
+            ("4kr2/8/8/8/8/8/8/4KN2 w - - 0 1", true, true),
+            ("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1", true, false),
+        ];
+
+        for (fen, white, black) in cases {
+            let game = Game::from_fen(fen);
+            assert_eq!(game.has_mating_material(Side::White), white, "{fen}");
+            assert_eq!(game.has_mating_material(Side::Black), black, "{fen}");
+        }
+    }
+
+    #[test]
+    fn test_dead_position_after_capture() {
+        let dead = Some(Outcome::Draw {
+            reason: DrawReason::DeadPosition,
+        });
+
+        // Bxf4 leaves a lone Bishop, Bishops on dark squares only, and Bishops on both colours
+        let cases = [
+            ("4k3/8/8/8/5n2/8/8/2B1K3 w - - 0 1", dead),
+            ("4kb2/8/8/8/5n2/8/8/2B1K3 w - - 0 1", dead),
+            ("2b1k3/8/8/8/5n2/8/8/2B1K3 w - - 0 1", None),
+        ];
+
+        for (fen, outcome) in cases {
+            let mut game = Game::from_fen(fen);
+            let capture = game.parse_san("Bxf4").unwrap();
+            game.make_move(&capture);
+            assert_eq!(game.outcome(), outcome, "{fen}");
+        }
+    }
+
+    #[test]
+    fn test_unchecked_moves_keep_the_outcome_up_to_date() {
+        let mut game = Game::from_starting_position();
+
+        // Every position of the shuffle comes back four times without ending the game...
+        for _ in 0..4 {
+            for san in ["Nf3", "Nf6", "Ng1", "Ng8"] {
+                assert_eq!(game.outcome(), None);
+                let ply = game.parse_san(san).unwrap();
+                game.make_move_unchecked(&ply);
+            }
+        }
+
+        // ...and the fifth time is a draw
+        let ply = game.parse_san("Nf3").unwrap();
+        game.make_move_unchecked(&ply);
+        assert!(game.can_claim_threefold_repetition());
+        assert_eq!(
+            game.outcome(),
+            Some(Outcome::Draw {
+                reason: DrawReason::ThreefoldRepetition
+            })
+        );
+
+        game.unmake_move();
+        assert_eq!(game.outcome(), None);
+        assert_eq!(game.get_move_log().len(), 16);
+    }
+
+    #[test]
+    fn test_outcome_of_a_loaded_position() {
+        let mated = Game::from_fen("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1");
+        assert_eq!(
+            mated.outcome(),
+            Some(Outcome::Win {
+                winner: Side::White,
+                reason: WinReason::Checkmate
+            })
+        );
+    }
+
+    #[test]
+    fn test_moves_made_after_a_null_move() {
+        let mut game = Game::from_fen("4k3/8/8/8/3p4/8/4P3/4K3 w - - 0 1");
+        let double_push = game.parse_san("e4").unwrap();
+        game.make_move(&double_push);
+        let fen = game.to_fen();
+
+        game.make_null_move().unwrap();
+        let reply = game.parse_san("Kd2").unwrap();
+        game.make_move_unchecked(&reply);
+        assert!(game.parse_san("dxe3").is_err());
+
+        // The En Passant capture comes back only once both are undone
+        game.unmake_move();
+        assert!(game.undo_null_move());
+        assert_eq!(game.to_fen(), fen);
+        assert!(game.parse_san("dxe3").is_ok());
+
+        // A real move is never undone as a null move
+        assert!(!game.undo_null_move());
+        assert_eq!(game.to_fen(), fen);
+
+        // Unmaking a move also undoes the null moves made after it, keeping the
+        // repetition history in step
+        let start = Game::from_fen("4k3/8/8/8/3p4/8/4P3/4K3 w - - 0 1");
+        game.make_null_move().unwrap();
+        game.unmake_move();
+        assert_eq!(game.to_fen(), start.to_fen());
+        assert_eq!(game, start);
+
+        let mut check = Game::from_fen("4k3/8/8/8/8/8/8/R3K2r w - - 0 1");
+        assert!(check.make_null_move().is_err());
+    }
+}
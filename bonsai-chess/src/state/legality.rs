@@ -0,0 +1,397 @@
+//! # Move Validation
+//!
+//! This module checks moves coming from untrusted sources (hand-built [`Ply`]
+//! values, network peers, user interfaces) against the current position of a
+//! [`Game`], and explains why a move is rejected.
+//!
+//! Only the moves of the piece being moved are generated, never the full list of
+//! legal moves of the position.
+
+use crate::{
+    atoms::{Bitboard, Coordinate, Side},
+    moves::{LegalityContext, MoveList, Ply, SpecialMove, attacks, generate_legal_moves},
+    pieces::{Kind, LocatedPiece, Piece},
+    state::{Game, Variant},
+};
+
+/// The reasons why a move cannot be played in a position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IllegalMove {
+    /// The game is already over.
+    GameOver,
+    /// The piece moved does not belong to the side to move.
+    WrongSide,
+    /// The piece moved is not on the starting square.
+    MissingPiece,
+    /// The captured piece recorded in the move is not what stands on the captured square.
+    StaleCapture,
+    /// The piece cannot reach the target square: it does not move that way, or another
+    /// piece stands in its path or on the target square.
+    BlockedPath,
+    /// The castling, en passant, promotion or drop recorded in the move does not apply.
+    InvalidSpecialMove,
+    /// The move would leave the King in check, or castle out of or through check.
+    LeavesKingInCheck,
+    /// The move is forbidden by the rules of the variant being played.
+    ForbiddenByVariant,
+}
+
+impl std::fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let reason = match self {
+            Self::GameOver => "the game is over",
+            Self::WrongSide => "it is not the turn of the piece moved",
+            Self::MissingPiece => "the piece moved is not on its starting square",
+            Self::StaleCapture => "the captured piece does not match the board",
+            Self::BlockedPath => "the piece cannot reach the target square",
+            Self::InvalidSpecialMove => "the special move does not apply",
+            Self::LeavesKingInCheck => "the King would be in check",
+            Self::ForbiddenByVariant => "the variant forbids the move",
+        };
+        write!(f, "Illegal move: {reason}")
+    }
+}
+
+impl std::error::Error for IllegalMove {}
+
+impl<V: Variant> Game<V> {
+    /// Plays `ply` after checking that it is legal in the current position.
+    ///
+    /// Unlike [`make_move`](Self::make_move), which trusts its input, this is safe to call
+    /// with moves from any source: an illegal move leaves the game untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IllegalMove::GameOver`] if the game has ended, or the reason given by
+    /// [`check_move`](Self::check_move) if the move is not legal.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// let mut game = Game::from_starting_position();
+    /// let e2 = Coordinate::from_algebraic_notation("e2").unwrap();
+    /// let e5 = Coordinate::from_algebraic_notation("e5").unwrap();
+    /// let pawn = Piece::new(Side::White, Kind::Pawn);
+    ///
+    /// let too_far = Ply::new(e2, e5, pawn, None, None);
+    /// assert_eq!(game.try_make_move(&too_far), Err(IllegalMove::BlockedPath));
+    /// assert!(game.get_move_log().is_empty());
+    ///
+    /// let e4 = game.parse_san("e4").unwrap();
+    /// assert_eq!(game.try_make_move(&e4), Ok(()));
+    /// ```
+    pub fn try_make_move(&mut self, ply: &Ply) -> Result<(), IllegalMove> {
+        if self.outcome().is_some() {
+            return Err(IllegalMove::GameOver);
+        }
+
+        self.check_move(ply)?;
+        self.make_move_unchecked(ply);
+        Ok(())
+    }
+
+    /// Returns `true` if `ply` is legal in the current position.
+    ///
+    /// This only looks at the position: it does not check whether the game is over.
+    #[must_use]
+    pub fn is_legal(&self, ply: &Ply) -> bool {
+        self.check_move(ply).is_ok()
+    }
+
+    /// Checks `ply` against the current position, and explains why it is illegal.
+    ///
+    /// Only the moves of the piece being moved are generated. As with
+    /// [`is_legal`](Self::is_legal), whether the game is over is not checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`IllegalMove`] describing the first problem found with the move.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// // The Knight on d2 is pinned by the Bishop on b4
+    /// let mut game = Game::from_fen("4k3/8/8/8/1b6/8/3N4/4K3 w - - 0 1");
+    /// let d2 = Coordinate::from_algebraic_notation("d2").unwrap();
+    /// let f3 = Coordinate::from_algebraic_notation("f3").unwrap();
+    /// let knight = Piece::new(Side::White, Kind::Knight);
+    ///
+    /// let pinned = Ply::new(d2, f3, knight, None, None);
+    /// assert_eq!(game.check_move(&pinned), Err(IllegalMove::LeavesKingInCheck));
+    ///
+    /// let black_knight = Ply::new(d2, f3, Piece::new(Side::Black, Kind::Knight), None, None);
+    /// assert_eq!(game.check_move(&black_knight), Err(IllegalMove::WrongSide));
+    /// ```
+    pub fn check_move(&self, ply: &Ply) -> Result<(), IllegalMove> {
+        let board = self.backend();
+        let side = self.turn();
+        let moved = ply.piece_moved();
+
+        if moved.team() != side {
+            return Err(IllegalMove::WrongSide);
+        }
+
+        if ply.is_drop() {
+            return self.check_drop(ply);
+        }
+
+        let from = ply.starting_square();
+        if board.get(from) != Some(moved) {
+            return Err(IllegalMove::MissingPiece);
+        }
+
+        // What the move should capture, as recorded on the board
+        let captured_square = match ply.special_move() {
+            Some(SpecialMove::Castle(..)) => None,
+            Some(SpecialMove::EnPassant(captured_pawn)) => Some(captured_pawn),
+            _ => Some(ply.ending_square()),
+        };
+        let captured = captured_square.and_then(|square| board.get(square));
+        if captured.is_some_and(|piece| piece.team() == side) {
+            return Err(IllegalMove::BlockedPath);
+        }
+        if ply.piece_captured() != captured {
+            return Err(IllegalMove::StaleCapture);
+        }
+
+        let piece = LocatedPiece::new(moved, from);
+        let mut candidates = MoveList::new();
+        generate_legal_moves(
+            piece,
+            board,
+            self.en_passant_target(),
+            self.castling_rights(),
+            &board.calculate_legality_context(side),
+            &mut candidates,
+        );
+        if candidates.contains(ply) {
+            return self.check_variant(ply);
+        }
+
+        if let Some(SpecialMove::Castle(..)) = ply.special_move() {
+            return Err(self.castling_error(ply));
+        }
+
+        // Ignoring checks and pins tells apart the moves that expose the King
+        candidates.clear();
+        generate_legal_moves(
+            piece,
+            board,
+            self.en_passant_target(),
+            self.castling_rights(),
+            &Self::unconstrained_context(board.king(side)),
+            &mut candidates,
+        );
+        if candidates.contains(ply) {
+            return Err(IllegalMove::LeavesKingInCheck);
+        }
+
+        let reaches_target = candidates
+            .iter()
+            .any(|candidate| candidate.ending_square() == ply.ending_square());
+        if reaches_target {
+            Err(IllegalMove::InvalidSpecialMove)
+        } else {
+            Err(IllegalMove::BlockedPath)
+        }
+    }
+
+    /// Checks a drop, which the variant generates from the pieces in hand.
+    fn check_drop(&self, ply: &Ply) -> Result<(), IllegalMove> {
+        let board = self.backend();
+        let side = self.turn();
+
+        if board.get(ply.ending_square()).is_some() {
+            return Err(IllegalMove::BlockedPath);
+        }
+        if ply.piece_captured().is_some() {
+            return Err(IllegalMove::StaleCapture);
+        }
+
+        let mut drops = MoveList::new();
+        let context = board.calculate_legality_context(side);
+        self.variant()
+            .extend_legal_moves(board, side, &context, &mut drops);
+        if drops.contains(ply) {
+            return self.check_variant(ply);
+        }
+
+        drops.clear();
+        let context = Self::unconstrained_context(board.king(side));
+        self.variant()
+            .extend_legal_moves(board, side, &context, &mut drops);
+        if drops.contains(ply) {
+            Err(IllegalMove::LeavesKingInCheck)
+        } else {
+            Err(IllegalMove::InvalidSpecialMove)
+        }
+    }
+
+    /// Checks a move that is legal by the standard rules against the variant's own rules.
+    fn check_variant(&self, ply: &Ply) -> Result<(), IllegalMove> {
+        let mut moves = MoveList::new();
+        moves.push(*ply);
+        self.variant()
+            .retain_legal_moves(self.backend(), self.turn(), &mut moves);
+
+        if moves.is_empty() {
+            Err(IllegalMove::ForbiddenByVariant)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Works out why a castling move that is not legal was rejected.
+    fn castling_error(&self, ply: &Ply) -> IllegalMove {
+        let Some(SpecialMove::Castle(castling_side, rook_square)) = ply.special_move() else {
+            return IllegalMove::InvalidSpecialMove;
+        };
+
+        let board = self.backend();
+        let side = self.turn();
+        let king_square = ply.starting_square();
+        let home_row = match side {
+            Side::White => 7,
+            Side::Black => 0,
+        };
+        let destination = |column| Coordinate::new(home_row, column);
+
+        let king_destination = destination(castling_side.king_destination_column());
+        let rook_destination = destination(castling_side.rook_destination_column());
+        let (Some(king_destination), Some(rook_destination)) = (king_destination, rook_destination)
+        else {
+            return IllegalMove::InvalidSpecialMove;
+        };
+
+        let is_valid = ply.piece_moved().kind() == Kind::King
+            && king_square.row() == home_row
+            && rook_square.row() == home_row
+            && ply.ending_square() == king_destination
+            && self.castling_rights().rook_file(side, castling_side) == Some(rook_square.column())
+            && board.get(rook_square) == Some(Piece::new(side, Kind::Rook));
+        if !is_valid {
+            return IllegalMove::InvalidSpecialMove;
+        }
+
+        // Only the King and the castling Rook may stand on the squares either one crosses
+        let mut path = attacks::between(king_square, king_destination)
+            | attacks::between(rook_square, rook_destination)
+            | Bitboard::from_coordinate(king_destination)
+            | Bitboard::from_coordinate(rook_destination);
+        path.remove(king_square);
+        path.remove(rook_square);
+
+        if (path & board.occupied()).is_empty() {
+            IllegalMove::LeavesKingInCheck
+        } else {
+            IllegalMove::BlockedPath
+        }
+    }
+
+    /// Returns a context without checks nor pins, under which every pseudo-legal move is generated.
+    const fn unconstrained_context(king_position: Coordinate) -> LegalityContext {
+        LegalityContext::from(
+            king_position,
+            Bitboard::EMPTY,
+            Bitboard::EMPTY,
+            Bitboard::EMPTY,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        atoms::{Coordinate, Side},
+        moves::{CastlingSide, Ply, SpecialMove},
+        pieces::{Kind, Piece, Promotion},
+        state::{Game, IllegalMove},
+    };
+
+    /// Returns the coordinate of a square given in algebraic notation.
+    fn square(name: &str) -> Coordinate {
+        Coordinate::from_algebraic_notation(name).unwrap()
+    }
+
+    #[test]
+    fn test_every_legal_move_is_accepted() {
+        let mut game =
+            Game::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+        for ply in game.get_legal_moves() {
+            assert_eq!(game.check_move(&ply), Ok(()), "{ply}");
+        }
+
+        let ply = game.parse_san("O-O-O").unwrap();
+        assert_eq!(game.try_make_move(&ply), Ok(()));
+        for ply in game.get_legal_moves() {
+            assert!(game.is_legal(&ply), "{ply}");
+        }
+    }
+
+    #[test]
+    fn test_tampered_moves_are_rejected() {
+        let mut game = Game::from_fen("r3k2r/8/8/3pP3/8/8/6p1/R3K2R w KQkq d6 0 1");
+        let white = |kind| Piece::new(Side::White, kind);
+        let black = |kind| Piece::new(Side::Black, kind);
+        let rook = white(Kind::Rook);
+
+        // The pawn on d5 is taken en passant, not the (empty) d6 square
+        let en_passant = game.parse_san("exd6").unwrap();
+        let stale = Ply::new(square("e5"), square("d6"), white(Kind::Pawn), None, None);
+        assert!(game.is_legal(&en_passant));
+        assert_eq!(
+            game.check_move(&stale),
+            Err(IllegalMove::InvalidSpecialMove)
+        );
+
+        let wrong_capture = Ply::new(
+            square("a1"),
+            square("a8"),
+            rook,
+            Some(black(Kind::Queen)),
+            None,
+        );
+        assert_eq!(
+            game.check_move(&wrong_capture),
+            Err(IllegalMove::StaleCapture)
+        );
+
+        let through_the_king = Ply::new(square("a1"), square("g1"), rook, None, None);
+        assert_eq!(
+            game.check_move(&through_the_king),
+            Err(IllegalMove::BlockedPath)
+        );
+
+        let missing = Ply::new(square("b1"), square("c3"), white(Kind::Knight), None, None);
+        assert_eq!(game.check_move(&missing), Err(IllegalMove::MissingPiece));
+
+        // The King would cross f1, attacked by the pawn on g2
+        let short = Ply::new(
+            square("e1"),
+            square("g1"),
+            white(Kind::King),
+            None,
+            Some(SpecialMove::Castle(CastlingSide::Short, square("h1"))),
+        );
+        assert_eq!(game.check_move(&short), Err(IllegalMove::LeavesKingInCheck));
+
+        let promotion = Ply::new(
+            square("e5"),
+            square("e6"),
+            white(Kind::Pawn),
+            None,
+            Some(SpecialMove::Promotion(Promotion::Queen)),
+        );
+        assert_eq!(
+            game.check_move(&promotion),
+            Err(IllegalMove::InvalidSpecialMove)
+        );
+
+        game.resign(Side::White);
+        assert_eq!(game.try_make_move(&en_passant), Err(IllegalMove::GameOver));
+    }
+}
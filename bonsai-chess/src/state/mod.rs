@@ -13,6 +13,9 @@
 /// Manages the raw placement and removal of pieces on the 8x8 grid.
 mod board;
 
+/// Tells whether moves give check, and classifies them, without playing them.
+mod checks;
+
 /// Manages the high-level game state, turn cycles, move history, and move validation.
 mod game;
 
@@ -22,18 +25,52 @@ mod fen;
 /// Provides the underlying 2D array representation of the chess board.
 mod grid;
 
+/// Checks untrusted moves against a position, explaining why illegal ones are rejected.
+mod legality;
+
+/// Reads and writes moves in Standard Algebraic Notation (SAN).
+mod san;
+
 /// Contains standard starting positions and notable test configurations.
 mod positions;
 
-/// Captures the complete state of the board at a specific point in time (used for threefold repetition tracking).
+/// Evaluates the exchanges of captures on a square (Static Exchange Evaluation).
+mod see;
+
+/// Implements `serde` traits for the types that do not derive them (coordinates, pieces, castling rights, pockets, moves and games).
+#[cfg(feature = "serde")]
+mod serialization;
+
+/// Captures the complete state of the board at a specific point in time (used for FEN serialization).
 mod snapshot;
 
 /// Defines a type alias for a potentially unoccupied space on the board.
 mod square;
 
+/// Builds the colour-flipped and mirrored images of a position.
+mod transforms;
+
+/// Reads moves in the coordinate notation of the Universal Chess Interface (UCI).
+mod uci;
+
+/// Defines chess variants, which change the rules of a game.
+mod variants;
+
+/// Random keys used to maintain the 64-bit Zobrist hash of a position (used for threefold repetition tracking).
+mod zobrist;
+
 pub use board::Board;
-pub use fen::{FenParsingError, from_fen, to_fen};
+pub use fen::{
+    FenParsingError, from_fen, parse_fen, to_fen, to_shredder_fen, validate_placement,
+    validate_position,
+};
 pub use game::Game;
 pub use grid::Grid;
+pub use legality::IllegalMove;
+pub use san::SanError;
 pub use snapshot::PositionSnapshot;
 pub use square::Square;
+pub use uci::UciMoveError;
+pub use variants::{
+    Crazyhouse, KingOfTheHill, RacingKings, STANDARD_STARTING_FEN, Standard, ThreeCheck, Variant,
+};
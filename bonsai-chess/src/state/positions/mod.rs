@@ -1,8 +1,7 @@
 //! Contains standard board configurations and presets.
 //!
-//! This module currently defines the standard FIDE starting position.
-//! Future expansions could include constants for Chess960 (Fischer Random) setups,
-//! specific endgame scenarios, or tactical puzzles.
+//! This module defines the standard FIDE starting position, and generates the
+//! 960 starting positions of Chess960 (Fischer Random) from their index.
 
 use crate::{
     BOARD_COLUMNS,
@@ -53,3 +52,111 @@ pub const STARTING_POSITION: Grid = Grid::new([
         Some(Piece::new(Side::White, Kind::Rook)),
     ],
 ]);
+
+/// Number of distinct Chess960 starting positions.
+pub const CHESS960_POSITIONS: usize = 960;
+
+/// Returns the back rank of the Chess960 starting position with the given index,
+/// from the a-file to the h-file, or `None` if the index is not below 960.
+///
+/// Positions are numbered with Scharnagl's scheme, in which index 518 is the
+/// standard starting position:
+/// 1. `index % 4` places the light-squared Bishop on the b, d, f or h-file.
+/// 2. The next base 4 digit places the dark-squared Bishop on the a, c, e or g-file.
+/// 3. The next base 6 digit places the Queen on one of the six remaining files.
+/// 4. The last digit (0 to 9) picks the two Knights among the five remaining files.
+/// 5. The Rooks and the King fill the last three files, King in the middle.
+#[must_use]
+pub fn chess960_back_rank(index: usize) -> Option<[Kind; BOARD_COLUMNS]> {
+    /// Pairs of remaining files the Knights stand on, for each value of the last digit.
+    const KNIGHTS: [(usize, usize); 10] = [
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (0, 4),
+        (1, 2),
+        (1, 3),
+        (1, 4),
+        (2, 3),
+        (2, 4),
+        (3, 4),
+    ];
+
+    if index >= CHESS960_POSITIONS {
+        return None;
+    }
+
+    let mut back_rank = [None; BOARD_COLUMNS];
+    back_rank[index % 4 * 2 + 1] = Some(Kind::Bishop);
+    back_rank[index / 4 % 4 * 2] = Some(Kind::Bishop);
+
+    // The n-th file still empty
+    let empty_file = |back_rank: &[Option<Kind>; BOARD_COLUMNS], n: usize| {
+        (0..BOARD_COLUMNS)
+            .filter(|file| back_rank[*file].is_none())
+            .nth(n)
+    };
+
+    let queen = empty_file(&back_rank, index / 16 % 6)?;
+    back_rank[queen] = Some(Kind::Queen);
+
+    let (first_knight, second_knight) = KNIGHTS[index / 96];
+    let first_knight = empty_file(&back_rank, first_knight)?;
+    let second_knight = empty_file(&back_rank, second_knight)?;
+    back_rank[first_knight] = Some(Kind::Knight);
+    back_rank[second_knight] = Some(Kind::Knight);
+
+    for kind in [Kind::Rook, Kind::King, Kind::Rook] {
+        let file = empty_file(&back_rank, 0)?;
+        back_rank[file] = Some(kind);
+    }
+
+    // Every file is filled by now
+    Some(back_rank.map(|kind| kind.unwrap_or(Kind::Pawn)))
+}
+
+/// Returns the grid of a Chess960 starting position, mirrored for both sides,
+/// with the pawns on their usual ranks.
+#[must_use]
+pub fn chess960_grid(back_rank: [Kind; BOARD_COLUMNS]) -> Grid {
+    let mut grid = STARTING_POSITION;
+    for (file, kind) in back_rank.into_iter().enumerate() {
+        grid[0][file] = Some(Piece::new(Side::Black, kind));
+        grid[7][file] = Some(Piece::new(Side::White, kind));
+    }
+    grid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chess960_back_ranks() {
+        let mut back_ranks: Vec<_> = (0..CHESS960_POSITIONS)
+            .filter_map(chess960_back_rank)
+            .collect();
+        assert_eq!(back_ranks.len(), CHESS960_POSITIONS);
+
+        for back_rank in &back_ranks {
+            let files =
+                |kind: Kind| (0..BOARD_COLUMNS).filter(move |file| back_rank[*file] == kind);
+            let bishops: Vec<_> = files(Kind::Bishop).collect();
+            let rooks: Vec<_> = files(Kind::Rook).collect();
+            let king = files(Kind::King).next().unwrap();
+
+            assert_ne!(bishops[0] % 2, bishops[1] % 2);
+            assert!(rooks[0] < king && king < rooks[1]);
+        }
+
+        assert_eq!(
+            chess960_back_rank(518).map(chess960_grid),
+            Some(STARTING_POSITION)
+        );
+
+        back_ranks.sort_by_key(|back_rank| back_rank.map(Kind::index));
+        back_ranks.dedup();
+        assert_eq!(back_ranks.len(), CHESS960_POSITIONS);
+        assert_eq!(chess960_back_rank(CHESS960_POSITIONS), None);
+    }
+}
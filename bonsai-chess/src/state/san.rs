@@ -0,0 +1,472 @@
+//! # Standard Algebraic Notation
+//!
+//! This module reads and writes moves in Standard Algebraic Notation (SAN), the
+//! notation used by PGN files and most chess literature (`e4`, `Nbd7`, `exd6`,
+//! `O-O-O`, `e8=Q+`, `Qh7#`), along with the drops of Crazyhouse (`N@f3`).
+//!
+//! SAN only names a move relative to a position: `Nf3` is meaningless without
+//! knowing which Knight can reach f3. Both directions are therefore implemented
+//! on [`Game`], which resolves the notation against the current legal moves.
+
+use crate::{
+    atoms::{CastlingRights, Coordinate},
+    moves::{CastlingSide, MoveList, Ply, SpecialMove, generate_legal_moves},
+    pieces::{Kind, Promotion},
+    state::{Board, Game, Variant},
+};
+
+/// Errors that can occur when parsing a move in Standard Algebraic Notation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SanError {
+    /// The input is empty, or only contains annotations.
+    Empty,
+    /// The input is not shaped like a SAN move.
+    InvalidSyntax(String),
+    /// The input is well formed, but no legal move in the position matches it.
+    IllegalMove(String),
+    /// The input matches several legal moves and lacks the disambiguation to pick one.
+    AmbiguousMove(String),
+}
+
+impl std::fmt::Display for SanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Empty move"),
+            Self::InvalidSyntax(s) => write!(f, "Invalid SAN syntax: {s}"),
+            Self::IllegalMove(s) => write!(f, "Illegal move: {s}"),
+            Self::AmbiguousMove(s) => write!(f, "Ambiguous move: {s}"),
+        }
+    }
+}
+
+impl std::error::Error for SanError {}
+
+/// The pieces of information a SAN string can carry about a non-castling move.
+struct SanMove {
+    kind: Kind,
+    from_column: Option<usize>,
+    from_row: Option<usize>,
+    to: Coordinate,
+    promotion: Option<Promotion>,
+}
+
+impl SanMove {
+    /// Returns `true` if the legal move `ply` is the one described.
+    fn matches(&self, ply: &Ply) -> bool {
+        let promotion = match ply.special_move() {
+            Some(SpecialMove::Promotion(promotion)) => Some(promotion),
+            _ => None,
+        };
+
+        ply.piece_moved().kind() == self.kind
+            && ply.ending_square() == self.to
+            && promotion == self.promotion
+            && !matches!(
+                ply.special_move(),
+                Some(SpecialMove::Castle(..) | SpecialMove::Drop)
+            )
+            && self
+                .from_column
+                .is_none_or(|column| ply.starting_square().column() == column)
+            && self
+                .from_row
+                .is_none_or(|row| ply.starting_square().row() == row)
+    }
+}
+
+impl<V: Variant> Game<V> {
+    /// Converts a legal move of the current position into Standard Algebraic Notation.
+    ///
+    /// The notation includes the minimal disambiguation (file, then rank, then both),
+    /// the capture marker, the promotion piece and the check (`+`) or checkmate (`#`) suffix.
+    /// Castling is written with the letter O (`O-O`, `O-O-O`), and drops with the letter of
+    /// the piece and its square (`N@f3`, `P@e4`).
+    ///
+    /// The move must be legal in the current position, as returned by
+    /// [`get_legal_moves`](Self::get_legal_moves).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// let game = Game::from_starting_position();
+    /// let ply = game.parse_san("Nf3").unwrap();
+    /// assert_eq!(game.to_san(&ply), "Nf3");
+    /// ```
+    #[must_use]
+    pub fn to_san(&self, ply: &Ply) -> String {
+        let mut san = String::new();
+
+        if let Some(SpecialMove::Castle(castling_side, _)) = ply.special_move() {
+            san.push_str(match castling_side {
+                CastlingSide::Short => "O-O",
+                CastlingSide::Long => "O-O-O",
+            });
+        } else if ply.is_drop() {
+            san.push_str(&ply.piece_moved().kind().to_string());
+            san.push('@');
+            san.push_str(&ply.ending_square().to_algebraic_notation());
+        } else {
+            let kind = ply.piece_moved().kind();
+            let origin = ply.starting_square().to_algebraic_notation();
+            let (origin_file, origin_rank) = origin.split_at(1);
+
+            if kind == Kind::Pawn {
+                if ply.piece_captured().is_some() {
+                    san.push_str(origin_file);
+                }
+            } else {
+                san.push_str(&kind.to_string());
+
+                let rivals: Vec<Coordinate> = self
+                    .get_legal_moves()
+                    .iter()
+                    .filter(|other| {
+                        !other.is_drop()
+                            && other.piece_moved().kind() == kind
+                            && other.ending_square() == ply.ending_square()
+                            && other.starting_square() != ply.starting_square()
+                    })
+                    .map(Ply::starting_square)
+                    .collect();
+
+                if !rivals.is_empty() {
+                    let shares_file = rivals
+                        .iter()
+                        .any(|rival| rival.column() == ply.starting_square().column());
+                    let shares_rank = rivals
+                        .iter()
+                        .any(|rival| rival.row() == ply.starting_square().row());
+
+                    if !shares_file {
+                        san.push_str(origin_file);
+                    } else if !shares_rank {
+                        san.push_str(origin_rank);
+                    } else {
+                        san.push_str(&origin);
+                    }
+                }
+            }
+
+            if ply.piece_captured().is_some() {
+                san.push('x');
+            }
+
+            san.push_str(&ply.ending_square().to_algebraic_notation());
+
+            if let Some(SpecialMove::Promotion(promotion)) = ply.special_move() {
+                san.push('=');
+                san.push_str(&promotion.to_string());
+            }
+        }
+
+        let mut variant_after = *self.variant();
+        variant_after.move_made(ply, true);
+        san.push_str(check_suffix(&self.backend_after(ply), ply, variant_after));
+
+        san
+    }
+
+    /// Finds the legal move of the current position written in Standard Algebraic Notation.
+    ///
+    /// Parsing is lenient about the forms found in the wild:
+    /// * Castling with the letter O or the digit zero (`O-O`, `0-0-0`).
+    /// * Check, checkmate and annotation suffixes are ignored (`+`, `#`, `!`, `?`).
+    /// * Redundant capture markers, disambiguation and dashes are accepted (`Ng1-f3`, `Nxf3`).
+    /// * Promotions with or without `=` (`e8=Q`, `e8Q`).
+    /// * A trailing en passant marker (`exd6e.p.`, `exd6 e.p.`, `exd6e.p.+`).
+    /// * Pawn drops with or without the letter of the pawn (`P@e4`, `@e4`).
+    ///
+    /// # Errors
+    ///
+    /// * [`SanError::Empty`] if there is nothing to parse.
+    /// * [`SanError::InvalidSyntax`] if the input is not shaped like a SAN move.
+    /// * [`SanError::IllegalMove`] if no legal move matches.
+    /// * [`SanError::AmbiguousMove`] if more than one legal move matches.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// let mut game = Game::from_starting_position();
+    /// for san in ["e4", "e5"] {
+    ///     let ply = game.parse_san(san).unwrap();
+    ///     game.make_move(&ply);
+    /// }
+    ///
+    /// assert_eq!(game.parse_san("Ke2").unwrap().piece_moved().kind(), Kind::King);
+    /// assert_eq!(game.parse_san("Ke3"), Err(SanError::IllegalMove("Ke3".to_string())));
+    /// ```
+    pub fn parse_san(&self, san: &str) -> Result<Ply, SanError> {
+        let text = strip_annotations(san);
+
+        if text.is_empty() {
+            return Err(SanError::Empty);
+        }
+
+        let castling_side = match text {
+            "O-O" | "0-0" => Some(CastlingSide::Short),
+            "O-O-O" | "0-0-0" => Some(CastlingSide::Long),
+            _ => None,
+        };
+
+        let legal_moves = self.get_legal_moves();
+
+        let candidates: Vec<Ply> = if let Some(castling_side) = castling_side {
+            legal_moves
+                .into_iter()
+                .filter(|ply| matches!(ply.special_move(), Some(SpecialMove::Castle(side, _)) if side == castling_side))
+                .collect()
+        } else if let Some((piece, square)) = text.split_once('@') {
+            let (kind, to) = parse_drop(piece, square)
+                .ok_or_else(|| SanError::InvalidSyntax(text.to_string()))?;
+            legal_moves
+                .into_iter()
+                .filter(|ply| {
+                    ply.is_drop() && ply.piece_moved().kind() == kind && ply.ending_square() == to
+                })
+                .collect()
+        } else {
+            let san_move = parse_move(text)?;
+            legal_moves
+                .into_iter()
+                .filter(|ply| san_move.matches(ply))
+                .collect()
+        };
+
+        match candidates.as_slice() {
+            [] => Err(SanError::IllegalMove(san.trim().to_string())),
+            [ply] => Ok(*ply),
+            _ => Err(SanError::AmbiguousMove(san.trim().to_string())),
+        }
+    }
+
+    /// Returns the board as it would be after playing `ply`.
+    fn backend_after(&self, ply: &Ply) -> Board {
+        let mut board = *self.backend();
+        board.make_move(ply);
+        board
+    }
+}
+
+/// Removes surrounding whitespace, the en passant marker, and check or annotation suffixes.
+///
+/// The marker may come before or after the suffixes (`exd6e.p.+`, `exd6+ e.p.`), so the
+/// suffixes are removed on both sides of it.
+fn strip_annotations(san: &str) -> &str {
+    let suffixes = ['+', '#', '!', '?'];
+
+    let text = san.trim().trim_end_matches(suffixes);
+    let text = text.strip_suffix("e.p.").unwrap_or(text).trim_end();
+    text.trim_end_matches(suffixes)
+}
+
+/// Parses the piece, disambiguation, destination and promotion of a non-castling move.
+fn parse_move(text: &str) -> Result<SanMove, SanError> {
+    let invalid = || SanError::InvalidSyntax(text.to_string());
+
+    let mut chars: Vec<char> = text
+        .chars()
+        .filter(|c| !matches!(c, 'x' | ':' | '-'))
+        .collect();
+
+    let kind = match chars.first() {
+        Some('K') => Kind::King,
+        Some('Q') => Kind::Queen,
+        Some('R') => Kind::Rook,
+        Some('B') => Kind::Bishop,
+        Some('N') => Kind::Knight,
+        _ => Kind::Pawn,
+    };
+    if kind != Kind::Pawn {
+        chars.remove(0);
+    }
+
+    let promotion = match chars.last() {
+        Some('Q' | 'q') => Some(Promotion::Queen),
+        Some('R' | 'r') => Some(Promotion::Rook),
+        Some('B' | 'b') => Some(Promotion::Bishop),
+        Some('N' | 'n') => Some(Promotion::Knight),
+        _ => None,
+    };
+    if promotion.is_some() {
+        // Guard against reading the file of a destination like "b8" as a promotion
+        if kind != Kind::Pawn || chars.len() < 3 {
+            return Err(invalid());
+        }
+        chars.pop();
+        if chars.last() == Some(&'=') {
+            chars.pop();
+        }
+    }
+
+    if chars.len() < 2 {
+        return Err(invalid());
+    }
+    let destination: String = chars.split_off(chars.len() - 2).into_iter().collect();
+    let to = Coordinate::from_algebraic_notation(&destination).ok_or_else(invalid)?;
+
+    let mut from_column = None;
+    let mut from_row = None;
+    for c in chars {
+        match c {
+            'a'..='h' if from_column.is_none() && from_row.is_none() => {
+                from_column = Some(c as usize - 'a' as usize);
+            }
+            '1'..='8' if from_row.is_none() => from_row = Some('8' as usize - c as usize),
+            _ => return Err(invalid()),
+        }
+    }
+
+    Ok(SanMove {
+        kind,
+        from_column,
+        from_row,
+        to,
+        promotion,
+    })
+}
+
+/// Parses the piece letter (none for a pawn) and the destination of a drop, split at the `@`.
+fn parse_drop(piece: &str, square: &str) -> Option<(Kind, Coordinate)> {
+    let kind = match piece {
+        "" | "P" => Kind::Pawn,
+        "N" => Kind::Knight,
+        "B" => Kind::Bishop,
+        "R" => Kind::Rook,
+        "Q" => Kind::Queen,
+        _ => return None,
+    };
+
+    Some((kind, Coordinate::from_algebraic_notation(square)?))
+}
+
+/// Returns `"#"` if `ply` checkmates, `"+"` if it checks, and an empty string otherwise.
+///
+/// The replies are searched on the board after the move, so this does not depend
+/// on the game still being in progress. `variant_after` is the state of the variant
+/// after the move, which may allow more replies, like drops blocking the check.
+fn check_suffix<V: Variant>(board_after: &Board, ply: &Ply, variant_after: V) -> &'static str {
+    let defender = ply.piece_moved().team().opposite();
+    let context = board_after.calculate_legality_context(defender);
+
+    if !context.in_check() {
+        return "";
+    }
+
+    // A double push is the only move that enables en passant, possibly as the only evasion
+    let en_passant_target = if ply.piece_moved().kind() == Kind::Pawn
+        && ply
+            .starting_square()
+            .row()
+            .abs_diff(ply.ending_square().row())
+            == 2
+    {
+        Coordinate::new(
+            usize::midpoint(ply.starting_square().row(), ply.ending_square().row()),
+            ply.starting_square().column(),
+        )
+    } else {
+        None
+    };
+
+    let mut replies = MoveList::new();
+    for piece in board_after.side_pieces(defender) {
+        // Castling is never a legal answer to a check
+        generate_legal_moves(
+            piece,
+            board_after,
+            en_passant_target,
+            CastlingRights::no_rights(),
+            &context,
+            &mut replies,
+        );
+        if !replies.is_empty() {
+            return "+";
+        }
+    }
+
+    variant_after.extend_legal_moves(board_after, defender, &context, &mut replies);
+    variant_after.retain_legal_moves(board_after, defender, &mut replies);
+    if replies.is_empty() { "#" } else { "+" }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{Game, SanError};
+
+    /// Parses `san` in the position `fen` and returns the notation written back for that move.
+    fn round_trip(fen: &str, san: &str) -> String {
+        let game = Game::from_fen(fen);
+        let ply = game.parse_san(san).unwrap();
+        game.to_san(&ply)
+    }
+
+    #[test]
+    fn test_disambiguation() {
+        // Knights on b1 and f1 both reach d2; Rooks on a1 and a5 both reach a3
+        let fen = "4k3/8/8/R7/8/8/8/RN2KN2 w - - 0 1";
+        assert_eq!(round_trip(fen, "Nbd2"), "Nbd2");
+        assert_eq!(round_trip(fen, "R1a3"), "R1a3");
+        assert_eq!(round_trip(fen, "Ra1-a3"), "R1a3");
+
+        let game = Game::from_fen(fen);
+        assert_eq!(
+            game.parse_san("Nd2"),
+            Err(SanError::AmbiguousMove("Nd2".to_string()))
+        );
+        assert_eq!(
+            game.parse_san("Nc3").map(|ply| game.to_san(&ply)),
+            Ok("Nc3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_castling_en_passant_and_promotion() {
+        let castling = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+        assert_eq!(round_trip(castling, "0-0"), "O-O");
+        assert_eq!(round_trip(castling, "O-O-O"), "O-O-O");
+
+        let en_passant = "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1";
+        assert_eq!(round_trip(en_passant, "exd6e.p."), "exd6");
+        assert_eq!(round_trip(en_passant, "exd6 e.p."), "exd6");
+
+        let promotion = "8/P6k/8/8/8/8/8/K7 w - - 0 1";
+        assert_eq!(round_trip(promotion, "a8=Q"), "a8=Q");
+        assert_eq!(round_trip(promotion, "a8N"), "a8=N");
+    }
+
+    #[test]
+    fn test_en_passant_marker_with_check_suffix() {
+        // Taking on d6 attacks the King on e7
+        let fen = "8/4k3/8/3pP3/8/8/8/4K3 w - d6 0 1";
+        for san in ["exd6e.p.+", "exd6 e.p.#", "exd6+ e.p.", "exd6 e.p.!?"] {
+            assert_eq!(round_trip(fen, san), "exd6+");
+        }
+    }
+
+    #[test]
+    fn test_check_and_checkmate_suffixes() {
+        assert_eq!(
+            round_trip("6k1/5ppp/8/8/8/8/8/K2R4 w - - 0 1", "Rd8"),
+            "Rd8#"
+        );
+        assert_eq!(round_trip("7k/8/8/8/8/8/8/K4R2 w - - 0 1", "Rf8"), "Rf8+");
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let game = Game::from_starting_position();
+        assert_eq!(game.parse_san(" +"), Err(SanError::Empty));
+        assert!(matches!(
+            game.parse_san("Zz9"),
+            Err(SanError::InvalidSyntax(_))
+        ));
+        assert!(matches!(
+            game.parse_san("e5"),
+            Err(SanError::IllegalMove(_))
+        ));
+    }
+}
@@ -0,0 +1,221 @@
+//! # Static Exchange Evaluation
+//!
+//! This module implements Static Exchange Evaluation (SEE) on the [`Board`]: the
+//! material balance of a capture once every piece attacking the target square has
+//! joined the exchange, cheapest first. Search uses it to order and prune captures
+//! without playing them out, and analysis tools to spot hanging pieces.
+
+use crate::{
+    atoms::{Bitboard, Side},
+    moves::{Ply, SpecialMove, attacks},
+    pieces::Kind,
+    state::Board,
+};
+
+/// The value of each piece kind in centipawns, indexed by [`Kind::index`].
+const VALUES: [i32; 6] = [20_000, 900, 500, 330, 320, 100];
+
+/// The order in which the pieces of a side join an exchange: cheapest first.
+const CAPTURE_ORDER: [Kind; 6] = [
+    Kind::Pawn,
+    Kind::Knight,
+    Kind::Bishop,
+    Kind::Rook,
+    Kind::Queen,
+    Kind::King,
+];
+
+/// The most captures a single square can see: every piece but the two Kings, and then some.
+const MAX_EXCHANGE: usize = 32;
+
+/// Returns the value of a piece kind in centipawns.
+const fn value(kind: Kind) -> i32 {
+    VALUES[kind.index()]
+}
+
+impl Board {
+    /// Returns the material won by `ply` in centipawns once the exchange it starts on
+    /// its target square is played out, or the material lost if negative.
+    ///
+    /// Both sides recapture with their least valuable piece, and stop whenever going on
+    /// would lose material. Along the way:
+    /// * **X-rays**: A slider standing behind a piece that joined the exchange joins it next.
+    /// * **Pins**: A piece pinned to its King only recaptures along the line of its pin.
+    /// * **Kings**: The King only recaptures once the opponent has no attacker left.
+    /// * **Promotions**: A Pawn promoting, by the move or by a recapture, also gains the
+    ///   difference between its new piece and itself.
+    ///
+    /// Pieces are worth 100 (Pawn), 320 (Knight), 330 (Bishop), 500 (Rook) and 900 (Queen).
+    /// Castling never captures anything, and is worth 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// // The pawn on e5 is attacked twice, and defended by the pawn on d6
+    /// let mut game = Game::from_fen("4k3/8/3p4/4p3/8/5N2/8/4RK2 w - - 0 1");
+    ///
+    /// // Nxe5 dxe5 Rxe5 trades the Knight for two pawns
+    /// let knight_takes = game.parse_san("Nxe5").unwrap();
+    /// assert_eq!(game.backend().see(&knight_takes), 2 * 100 - 320);
+    ///
+    /// // Rxe5 dxe5 Nxe5 trades the Rook for two pawns
+    /// let rook_takes = game.parse_san("Rxe5").unwrap();
+    /// assert_eq!(game.backend().see(&rook_takes), 2 * 100 - 500);
+    /// ```
+    #[must_use]
+    pub fn see(&self, ply: &Ply) -> i32 {
+        let target = ply.ending_square();
+        let mut occupied = self.occupied();
+        if !ply.is_drop() {
+            occupied.remove(ply.starting_square());
+        }
+
+        // gains[n] is what the side making the n-th capture wins if the exchange stops there
+        let mut gains = [0; MAX_EXCHANGE];
+        gains[0] = ply.piece_captured().map_or(0, |piece| value(piece.kind()));
+        let mut on_target = value(ply.piece_moved().kind());
+
+        match ply.special_move() {
+            Some(SpecialMove::Castle(..)) => return 0,
+            Some(SpecialMove::EnPassant(captured_pawn)) => occupied.remove(captured_pawn),
+            Some(SpecialMove::Promotion(promotion)) => {
+                on_target = value(Kind::from_valid_promotions(promotion));
+                gains[0] += on_target - value(Kind::Pawn);
+            }
+            Some(SpecialMove::Drop) | None => {}
+        }
+
+        // Pinned pieces can only capture along their pin
+        let mut excluded = Bitboard::EMPTY;
+        for side in [Side::White, Side::Black] {
+            for pinned in self.pinned_pieces(side) {
+                if !attacks::line(self.king(side), pinned).contains(target) {
+                    excluded.insert(pinned);
+                }
+            }
+        }
+
+        let diagonal_sliders = self.kind(Kind::Bishop) | self.kind(Kind::Queen);
+        let straight_sliders = self.kind(Kind::Rook) | self.kind(Kind::Queen);
+        let promotes = target.row() == 0 || target.row() == 7;
+
+        let mut attackers = (self.attackers(target, Side::White, occupied)
+            | self.attackers(target, Side::Black, occupied))
+            & occupied
+            & !excluded;
+        let mut side = ply.piece_moved().team().opposite();
+        let mut depth = 0;
+
+        while depth + 1 < MAX_EXCHANGE {
+            let own_attackers = attackers & self.side(side);
+            let Some((kind, from)) = CAPTURE_ORDER.into_iter().find_map(|kind| {
+                (own_attackers & self.kind(kind))
+                    .first()
+                    .map(|from| (kind, from))
+            }) else {
+                break;
+            };
+
+            // The King cannot capture onto a defended square
+            if kind == Kind::King && !(attackers & self.side(side.opposite())).is_empty() {
+                break;
+            }
+
+            depth += 1;
+            gains[depth] = on_target - gains[depth - 1];
+            on_target = value(kind);
+            if kind == Kind::Pawn && promotes {
+                gains[depth] += value(Kind::Queen) - value(Kind::Pawn);
+                on_target = value(Kind::Queen);
+            }
+
+            // Lifting the piece may uncover a slider behind it
+            occupied.remove(from);
+            attackers.remove(from);
+            attackers |= ((attacks::bishop_attacks(target, occupied) & diagonal_sliders)
+                | (attacks::rook_attacks(target, occupied) & straight_sliders))
+                & occupied
+                & !excluded;
+
+            side = side.opposite();
+        }
+
+        // Each side only captures if it is better off than stopping
+        while depth > 0 {
+            gains[depth - 1] = -(-gains[depth - 1]).max(gains[depth]);
+            depth -= 1;
+        }
+        gains[0]
+    }
+
+    /// Returns `true` if the exchange started by `ply` wins at least `threshold`
+    /// centipawns, as evaluated by [`see`](Self::see).
+    ///
+    /// A threshold of 0 tells apart the captures that do not lose material, which search
+    /// can try first, from those it can prune.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// // The Knight on d5 hangs
+    /// let mut game = Game::from_fen("4k3/8/8/3n4/8/8/8/3QK3 w - - 0 1");
+    /// let capture = game.parse_san("Qxd5").unwrap();
+    ///
+    /// assert!(game.backend().see_ge(&capture, 0));
+    /// assert!(game.backend().see_ge(&capture, 320));
+    /// assert!(!game.backend().see_ge(&capture, 321));
+    /// ```
+    #[must_use]
+    pub fn see_ge(&self, ply: &Ply, threshold: i32) -> bool {
+        self.see(ply) >= threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::state::Game;
+
+    /// Returns the SEE of the move `san` in the position `fen`.
+    fn see(fen: &str, san: &str) -> i32 {
+        let game = Game::from_fen(fen);
+        let ply = game.parse_san(san).unwrap();
+        game.backend().see(&ply)
+    }
+
+    #[test]
+    fn test_sliders_behind_the_exchange_join_it() {
+        // Doubled Rooks win the pawn defended by a single Rook
+        assert_eq!(see("4k3/4r3/8/4p3/8/8/4R3/4RK2 w - - 0 1", "Rxe5"), 100);
+        // A single one does not
+        assert_eq!(see("4k3/4r3/8/4p3/8/8/4R3/5K2 w - - 0 1", "Rxe5"), -400);
+        // The Queen behind the Bishop joins the exchange too
+        assert_eq!(see("4k3/8/2b5/3p4/4B3/5Q2/8/4K3 w - - 0 1", "Bxd5"), 100);
+    }
+
+    #[test]
+    fn test_pinned_and_royal_defenders() {
+        // The pawn on d6 is pinned to its King and cannot recapture on e5
+        assert_eq!(see("3k4/8/3p4/4p3/2N5/8/8/3RK3 w - - 0 1", "Nxe5"), 100);
+        // The King cannot recapture on e7 while the Bishop defends it
+        assert_eq!(see("4k3/4p3/8/8/1B6/8/8/4R1K1 w - - 0 1", "Rxe7+"), 100);
+        assert_eq!(see("4k3/4p3/8/8/8/8/8/4R1K1 w - - 0 1", "Rxe7+"), -400);
+    }
+
+    #[test]
+    fn test_promotions_and_en_passant() {
+        assert_eq!(
+            see("3r3k/4P3/8/8/8/8/8/6K1 w - - 0 1", "exd8=Q+"),
+            500 + 800
+        );
+        // The Rook on e8 takes the new Queen back
+        assert_eq!(
+            see("3rr2k/4P3/8/8/8/8/8/6K1 w - - 0 1", "exd8=Q"),
+            500 + 800 - 900
+        );
+        assert_eq!(see("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "exd6"), 100);
+    }
+}
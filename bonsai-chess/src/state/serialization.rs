@@ -0,0 +1,472 @@
+//! # Serialization
+//!
+//! This module implements [`Serialize`] and [`Deserialize`] for the types whose
+//! derived representation would be unreadable or unsafe to read back. The other
+//! public types derive both traits where they are defined, when the `serde`
+//! feature is enabled.
+//!
+//! * [`Coordinate`]: Its algebraic notation (`"e4"`).
+//! * [`Piece`]: Its FEN letter (`"N"`, `"p"`).
+//! * [`CastlingRights`]: The file letter of the Rook of each right still available.
+//! * [`Pocket`]: The letters of the pieces it holds (`"QNPP"`), checked against the most a
+//!   game can put in hand.
+//! * [`Ply`]: Its UCI notation, with the pieces it moves and captures.
+//! * [`Game`]: Its variant, its starting FEN and its moves in UCI notation. The moves
+//!   are played again when the game is read, so an illegal move is an error.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+use crate::{
+    atoms::{CastlingRights, Coordinate, Pocket, Side},
+    moves::{CastlingSide, Ply, SpecialMove},
+    pieces::{Kind, Piece},
+    state::{Game, Variant, uci::UciNotation},
+};
+
+impl Serialize for Coordinate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_algebraic_notation())
+    }
+}
+
+impl<'de> Deserialize<'de> for Coordinate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Self::from_algebraic_notation(&text)
+            .ok_or_else(|| D::Error::custom(format!("invalid square: {text}")))
+    }
+}
+
+impl Serialize for Piece {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Piece {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let letter = char::deserialize(deserializer)?;
+        let kind = match letter.to_ascii_lowercase() {
+            'k' => Kind::King,
+            'q' => Kind::Queen,
+            'r' => Kind::Rook,
+            'b' => Kind::Bishop,
+            'n' => Kind::Knight,
+            'p' => Kind::Pawn,
+            _ => return Err(D::Error::custom(format!("invalid piece: {letter}"))),
+        };
+        let side = if letter.is_ascii_uppercase() {
+            Side::White
+        } else {
+            Side::Black
+        };
+        Ok(Self::new(side, kind))
+    }
+}
+
+/// The serialized form of [`CastlingRights`]: the file letter of the Rook of each right
+/// still available (`"h"` and `"a"` in standard chess).
+///
+/// The rights are named rather than written as a FEN field, as the side of a Shredder-FEN
+/// letter depends on where the King stands.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "CastlingRights")]
+struct CastlingRightsRecord {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    white_short: Option<char>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    white_long: Option<char>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    black_short: Option<char>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    black_long: Option<char>,
+}
+
+/// The rights in the order of the fields of a [`CastlingRightsRecord`].
+const CASTLING_RIGHTS: [(Side, CastlingSide); 4] = [
+    (Side::White, CastlingSide::Short),
+    (Side::White, CastlingSide::Long),
+    (Side::Black, CastlingSide::Short),
+    (Side::Black, CastlingSide::Long),
+];
+
+impl Serialize for CastlingRights {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let [white_short, white_long, black_short, black_long] =
+            CASTLING_RIGHTS.map(|(side, castling_side)| {
+                self.rook_file(side, castling_side)
+                    .and_then(|file| u8::try_from(file).ok())
+                    .map(|file| char::from(b'a' + file))
+            });
+
+        CastlingRightsRecord {
+            white_short,
+            white_long,
+            black_short,
+            black_long,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Reads the rights back, checking that each Rook stands on a file of the board with room
+/// for the King on the side it castles away from.
+impl<'de> Deserialize<'de> for CastlingRights {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let record = CastlingRightsRecord::deserialize(deserializer)?;
+        let letters = [
+            record.white_short,
+            record.white_long,
+            record.black_short,
+            record.black_long,
+        ];
+
+        let mut rights = Self::no_rights();
+        for ((side, castling_side), letter) in CASTLING_RIGHTS.into_iter().zip(letters) {
+            let Some(letter) = letter else {
+                continue;
+            };
+            let file = match (castling_side, letter) {
+                (CastlingSide::Short, 'b'..='h') | (CastlingSide::Long, 'a'..='g') => {
+                    usize::from(letter as u8 - b'a')
+                }
+                _ => {
+                    return Err(D::Error::custom(format!(
+                        "invalid {castling_side:?} castling rook file for {side:?}: {letter}"
+                    )));
+                }
+            };
+            rights.enable(side, castling_side, file);
+        }
+
+        for side in [Side::White, Side::Black] {
+            if let (Some(short), Some(long)) = (
+                rights.rook_file(side, CastlingSide::Short),
+                rights.rook_file(side, CastlingSide::Long),
+            ) && short <= long + 1
+            {
+                return Err(D::Error::custom(format!(
+                    "no room for the {side:?} King between its castling rooks"
+                )));
+            }
+        }
+
+        Ok(rights)
+    }
+}
+
+impl Serialize for Pocket {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let letters: String = Self::KINDS
+            .into_iter()
+            .flat_map(|kind| std::iter::repeat_n(kind, usize::from(self.count(kind))))
+            .map(|kind| kind.to_string())
+            .collect();
+        serializer.serialize_str(&letters)
+    }
+}
+
+impl<'de> Deserialize<'de> for Pocket {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let letters = String::deserialize(deserializer)?;
+
+        let mut pocket = Self::default();
+        for letter in letters.chars() {
+            let kind = match letter {
+                'Q' => Kind::Queen,
+                'R' => Kind::Rook,
+                'B' => Kind::Bishop,
+                'N' => Kind::Knight,
+                'P' => Kind::Pawn,
+                _ => return Err(D::Error::custom(format!("invalid pocket piece: {letter}"))),
+            };
+            if pocket.count(kind) >= Self::max_count(kind) {
+                return Err(D::Error::custom(format!(
+                    "too many {kind} in pocket: {letters}"
+                )));
+            }
+            pocket.add(kind);
+        }
+        Ok(pocket)
+    }
+}
+
+/// The serialized form of a [`Ply`].
+///
+/// UCI notation only names squares, and leaves the rest to the position. A ply stands
+/// on its own, so it also records the pieces involved and the special moves UCI notation
+/// cannot tell apart from regular ones. Castles are always written as the King taking
+/// its own Rook (`e1h1`), which names the Rook even in Chess960.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Ply")]
+struct PlyRecord {
+    uci: String,
+    piece: Piece,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    captured: Option<Piece>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    castle: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    en_passant: bool,
+}
+
+/// Leaves the flags of a [`PlyRecord`] out of the output when they are not set.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+const fn is_false(flag: &bool) -> bool {
+    !*flag
+}
+
+impl Serialize for Ply {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (uci, castle, en_passant) = match self.special_move() {
+            Some(SpecialMove::Castle(_, rook_square)) => (
+                format!(
+                    "{}{}",
+                    self.starting_square().to_algebraic_notation(),
+                    rook_square.to_algebraic_notation()
+                ),
+                true,
+                false,
+            ),
+            Some(SpecialMove::EnPassant(_)) => (self.to_uci(), false, true),
+            _ => (self.to_uci(), false, false),
+        };
+
+        PlyRecord {
+            uci,
+            piece: self.piece_moved(),
+            captured: self.piece_captured(),
+            castle,
+            en_passant,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ply {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let record = PlyRecord::deserialize(deserializer)?;
+        let invalid = || D::Error::custom(format!("invalid move: {}", record.uci));
+
+        let (from, to, promotion) = match UciNotation::parse(&record.uci).ok_or_else(invalid)? {
+            UciNotation::Drop(kind, square) if kind == record.piece.kind() => {
+                return Ok(Self::new_drop(square, record.piece));
+            }
+            UciNotation::Drop(..) => return Err(invalid()),
+            UciNotation::Move(from, to, promotion) => (from, to, promotion),
+        };
+
+        if record.castle {
+            // The destination is the Rook's square, on the side the King castles to
+            let castling_side = if to.column() > from.column() {
+                CastlingSide::Short
+            } else {
+                CastlingSide::Long
+            };
+            let king_destination =
+                Coordinate::new(from.row(), castling_side.king_destination_column())
+                    .ok_or_else(invalid)?;
+            return Ok(Self::new(
+                from,
+                king_destination,
+                record.piece,
+                None,
+                Some(SpecialMove::Castle(castling_side, to)),
+            ));
+        }
+
+        let special_move = if record.en_passant {
+            let captured_pawn = Coordinate::new(from.row(), to.column()).ok_or_else(invalid)?;
+            Some(SpecialMove::EnPassant(captured_pawn))
+        } else {
+            promotion.map(SpecialMove::Promotion)
+        };
+        Ok(Self::new(
+            from,
+            to,
+            record.piece,
+            record.captured,
+            special_move,
+        ))
+    }
+}
+
+/// The serialized form of a [`Game`]: where it started, and the moves played since.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Game")]
+struct GameRecord {
+    variant: String,
+    fen: String,
+    moves: Vec<String>,
+}
+
+/// Stores the game as the FEN of its starting position and its moves in UCI notation.
+///
+/// Results declared rather than played (resignations, agreed draws, flags) are not stored.
+impl<V: Variant> Serialize for Game<V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let moves = self.get_move_log();
+
+        let mut starting_position = self.clone();
+        for _ in &moves {
+            starting_position.unmake_move();
+        }
+
+        GameRecord {
+            variant: V::NAME.to_string(),
+            fen: starting_position.to_fen(),
+            moves: moves.iter().map(Ply::to_uci).collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Reads the starting position back, and plays every move again on it.
+///
+/// The variant must be the one the game was stored with, and every move must be legal.
+impl<'de, V: Variant> Deserialize<'de> for Game<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let record = GameRecord::deserialize(deserializer)?;
+        if record.variant != V::NAME {
+            return Err(D::Error::custom(format!(
+                "expected a game of {}, found {}",
+                V::NAME,
+                record.variant
+            )));
+        }
+
+        let mut game =
+            Self::try_from_fen_with_variant(&record.fen, V::default()).map_err(D::Error::custom)?;
+        for uci in &record.moves {
+            let ply = game.parse_uci_move(uci).map_err(D::Error::custom)?;
+            game.try_make_move(&ply).map_err(D::Error::custom)?;
+        }
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        atoms::{CastlingRights, Coordinate, Pocket},
+        moves::Ply,
+        pieces::Kind,
+        rules::Outcome,
+        state::{Crazyhouse, Game},
+    };
+
+    /// Serializes `value` to JSON and reads it back.
+    fn round_trip<T>(value: &T) -> T
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        let json = serde_json::to_string(value).unwrap();
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn test_every_legal_move_round_trips() {
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1",
+            "8/P6k/8/8/8/8/6Kp/8 b - - 0 1",
+            "1r2k1r1/8/8/8/8/8/8/1R2K1R1 w GBgb - 0 1",
+        ];
+
+        for fen in fens {
+            let game = Game::from_fen(fen);
+            for ply in game.get_legal_moves() {
+                assert_eq!(round_trip(&ply), ply, "{ply}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_readable_representations() {
+        let e4 = Coordinate::from_algebraic_notation("e4").unwrap();
+        assert_eq!(serde_json::to_string(&e4).unwrap(), r#""e4""#);
+
+        let mut game = Game::from_starting_position();
+        let ply = game.parse_san("Nf3").unwrap();
+        assert_eq!(
+            serde_json::to_string(&ply).unwrap(),
+            r#"{"uci":"g1f3","piece":"N"}"#
+        );
+        game.make_move(&ply);
+        assert_eq!(
+            serde_json::to_string(&game).unwrap(),
+            format!(
+                r#"{{"variant":"Standard","fen":"{}","moves":["g1f3"]}}"#,
+                Game::from_starting_position().to_fen()
+            )
+        );
+
+        let rights = CastlingRights::new();
+        assert_eq!(
+            serde_json::to_string(&rights).unwrap(),
+            r#"{"white_short":"h","white_long":"a","black_short":"h","black_long":"a"}"#
+        );
+        assert_eq!(round_trip(&rights), rights);
+        let mut pocket = Pocket::default();
+        for kind in [Kind::Pawn, Kind::Knight, Kind::Pawn] {
+            pocket.add(kind);
+        }
+        assert_eq!(serde_json::to_string(&pocket).unwrap(), r#""NPP""#);
+        assert_eq!(round_trip(&pocket), pocket);
+        let outcome = Outcome::Draw {
+            reason: crate::rules::DrawReason::Stalemate,
+        };
+        assert_eq!(round_trip(&outcome), outcome);
+    }
+
+    #[test]
+    fn test_games_are_replayed() {
+        let mut game = Game::with_variant(Crazyhouse::default());
+        for san in ["e4", "d5", "exd5", "Qxd5", "Nc3", "Qa5", "P@e6"] {
+            let ply = game.parse_san(san).unwrap();
+            game.make_move(&ply);
+        }
+        let copy: Game<Crazyhouse> = round_trip(&game);
+        assert_eq!(copy.to_fen(), game.to_fen());
+        assert_eq!(copy.get_move_log(), game.get_move_log());
+
+        // Another variant, or an illegal move, is rejected
+        let json = serde_json::to_string(&game).unwrap();
+        assert!(serde_json::from_str::<Game>(&json).is_err());
+        let illegal = json.replace("e2e4", "e2e5");
+        assert!(serde_json::from_str::<Game<Crazyhouse>>(&illegal).is_err());
+
+        let stale: Result<Ply, _> = serde_json::from_str(r#"{"uci":"N@e4","piece":"B"}"#);
+        assert!(stale.is_err());
+    }
+
+    #[test]
+    fn test_out_of_range_state_is_rejected() {
+        // Chess960 rights round-trip
+        let rights = Game::from_fen("1r2k1r1/8/8/8/8/8/8/1R2K1R1 w GBgb - 0 1").create_snapshot();
+        let rights = rights.get_castling_rights();
+        assert_eq!(round_trip(&rights), rights);
+
+        for json in [
+            r#"{"white_short":"z"}"#,
+            r#"{"white_short":"a"}"#,
+            r#"{"black_long":"h"}"#,
+            r#"{"white_short":"c","white_long":"b"}"#,
+            r#"{"white_short":4}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<CastlingRights>(json).is_err(),
+                "{json}"
+            );
+        }
+
+        for json in [
+            r#""K""#,
+            r#""QQQ""#,
+            r#""x""#,
+            &format!(r#""{}""#, "P".repeat(17)),
+        ] {
+            assert!(serde_json::from_str::<Pocket>(json).is_err(), "{json}");
+        }
+    }
+}
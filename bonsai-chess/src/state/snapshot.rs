@@ -2,19 +2,21 @@
 //!
 //! This module provides the [`PositionSnapshot`] struct, which captures the exact,
 //! reproducible state of a chess board at a specific point in time. It is primarily
-//! used for hashing and tracking game history to enforce the FIDE Threefold
-//! Repetition draw rule.
+//! used as the intermediate representation when reading and writing FEN strings.
 
 use crate::{
     atoms::{CastlingRights, Coordinate, Side},
     state::Grid,
 };
 
-/// A hashable representation of the board state used to detect Threefold Repetition.
+/// A complete representation of the board state, without any history.
 ///
 /// This struct captures only the essential data required to uniquely identify a position
 /// according to FIDE rules (piece placement, active color, castling rights, and en passant).
 /// It excludes move counters or history logs.
+///
+/// Comparing snapshots means comparing all 64 squares; to detect repeated positions
+/// cheaply, prefer the Zobrist key returned by [`Game::hash`](crate::state::Game::hash).
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct PositionSnapshot {
     pieces_positions: Grid,
@@ -0,0 +1,223 @@
+//! # Position Transforms
+//!
+//! This module builds the images of a position through the symmetries of the board:
+//! swapping the colours (mirroring the ranks, so that each side keeps playing "up" the
+//! board), and mirroring the files. A correct move generator finds as many moves in
+//! a position as in its colour-flipped image, and a symmetric evaluation scores both
+//! the same, which makes these transforms a cheap source of test positions.
+
+use crate::{
+    atoms::{CastlingRights, Coordinate, Side},
+    moves::CastlingSide,
+    pieces::Piece,
+    state::{Board, Game, Grid, PositionSnapshot, Variant},
+};
+
+impl Board {
+    /// Returns the board with the ranks mirrored and the colours of the pieces swapped:
+    /// a White Pawn on e2 becomes a Black Pawn on e7.
+    #[must_use]
+    pub fn color_flipped(&self) -> Self {
+        Self::new(color_flipped_grid(self.grid()))
+    }
+
+    /// Returns the board with the files mirrored: a piece on a1 ends up on h1.
+    #[must_use]
+    pub fn mirrored_horizontally(&self) -> Self {
+        Self::new(mirrored_grid(self.grid()))
+    }
+}
+
+impl PositionSnapshot {
+    /// Returns the position with the colours swapped: the ranks are mirrored, the pieces
+    /// change colours, and so do the side to move and the castling rights.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// let game = Game::try_from_fen("4k3/8/8/3pP3/8/8/8/R3K3 w Q d6 0 1").unwrap();
+    /// let flipped = game.create_snapshot().color_flipped();
+    ///
+    /// assert_eq!(flipped.get_turn(), Side::Black);
+    /// assert!(flipped.get_castling_rights().black_queen_side());
+    /// assert_eq!(flipped.get_en_passant(), Coordinate::from_algebraic_notation("d3"));
+    /// ```
+    #[must_use]
+    pub fn color_flipped(&self) -> Self {
+        let rights = self.get_castling_rights();
+        let mut flipped_rights = CastlingRights::no_rights();
+        for side in [Side::White, Side::Black] {
+            for castling_side in [CastlingSide::Short, CastlingSide::Long] {
+                if let Some(rook_file) = rights.rook_file(side, castling_side) {
+                    flipped_rights.enable(side.opposite(), castling_side, rook_file);
+                }
+            }
+        }
+
+        Self::new(
+            color_flipped_grid(&self.get_grid()),
+            self.get_turn().opposite(),
+            flipped_rights,
+            self.get_en_passant()
+                .and_then(|square| Coordinate::new(7 - square.row(), square.column())),
+        )
+    }
+
+    /// Returns the position with the files mirrored: a piece on a1 ends up on h1.
+    ///
+    /// The castling rights are dropped: the King castles to the c- or g-file whatever
+    /// side it stands on, so castling in the mirrored position is not the mirror image
+    /// of castling in the original one.
+    #[must_use]
+    pub fn mirrored_horizontally(&self) -> Self {
+        Self::new(
+            mirrored_grid(&self.get_grid()),
+            self.get_turn(),
+            CastlingRights::no_rights(),
+            self.get_en_passant()
+                .and_then(|square| Coordinate::new(square.row(), 7 - square.column())),
+        )
+    }
+}
+
+impl<V: Variant> Game<V> {
+    /// Returns the current position with the colours swapped: the ranks are mirrored, the
+    /// pieces change colours, and so do the side to move, the castling rights, and the
+    /// state kept by the variant (see [`Variant::color_flipped`]).
+    ///
+    /// The clocks are kept, but the moves that led to the position are not: the new game
+    /// starts from the flipped position, as if it was read from a FEN. Rules that tell the
+    /// colours apart, like the goal rank of [`RacingKings`](crate::state::RacingKings),
+    /// are not flipped with the position.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// let game = Game::try_from_fen("r3k2r/p6p/8/8/8/8/P6P/R3K2R b Qk - 3 12").unwrap();
+    /// let flipped = game.color_flipped();
+    ///
+    /// assert_eq!(flipped.to_fen(), "r3k2r/p6p/8/8/8/8/P6P/R3K2R w Kq - 3 12");
+    /// assert_eq!(flipped.color_flipped(), game);
+    /// ```
+    #[must_use]
+    pub fn color_flipped(&self) -> Self {
+        Self::from_snapshot(
+            self.create_snapshot().color_flipped(),
+            self.move_counter().clone(),
+            self.variant().color_flipped(),
+        )
+    }
+}
+
+/// Mirrors the ranks of `grid`, and swaps the colours of its pieces.
+fn color_flipped_grid(grid: &Grid) -> Grid {
+    let mut rows = grid.0;
+    rows.reverse();
+
+    for square in rows.iter_mut().flatten() {
+        *square = square.map(|piece| Piece::new(piece.team().opposite(), piece.kind()));
+    }
+
+    Grid::new(rows)
+}
+
+/// Mirrors the files of `grid`.
+fn mirrored_grid(grid: &Grid) -> Grid {
+    let mut rows = grid.0;
+    for row in &mut rows {
+        row.reverse();
+    }
+
+    Grid::new(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        atoms::Side,
+        pieces::Kind,
+        state::{Crazyhouse, Game, ThreeCheck},
+    };
+
+    #[test]
+    fn test_color_flip_preserves_moves() {
+        let fens = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        ];
+
+        for fen in fens {
+            let mut game = Game::try_from_fen(fen).unwrap();
+            let mut flipped = game.color_flipped();
+
+            assert_eq!(flipped.color_flipped().to_fen(), fen);
+            assert_eq!(flipped.turn(), game.turn().opposite());
+
+            // Flipping a move flips its squares, so the counts match at every depth
+            for depth in 1..=2 {
+                assert_eq!(
+                    count_leaves(&mut flipped, depth),
+                    count_leaves(&mut game, depth)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_color_flip_swaps_variant_state() {
+        let three_check = Game::try_from_fen_with_variant(
+            "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2 +2+0",
+            ThreeCheck::default(),
+        )
+        .unwrap();
+        let flipped = three_check.color_flipped();
+        assert_eq!(flipped.variant().checks_given(Side::Black), 2);
+        assert_eq!(flipped.variant().checks_given(Side::White), 0);
+
+        let crazyhouse = Game::try_from_fen_with_variant(
+            "rnbqkbnr/pppp1ppp/8/8/8/8/PPP1PPPP/RNBQKBNR[P] w KQkq - 0 3",
+            Crazyhouse::default(),
+        )
+        .unwrap();
+        let flipped = crazyhouse.color_flipped();
+        assert_eq!(flipped.variant().pocket(Side::Black).count(Kind::Pawn), 1);
+        assert_eq!(flipped.variant().pocket(Side::White).count(Kind::Pawn), 0);
+        assert_eq!(flipped.color_flipped(), crazyhouse);
+    }
+
+    #[test]
+    fn test_mirror_horizontally() {
+        let game = Game::try_from_fen("4k3/8/8/2Pp4/8/8/8/R3K3 w Q d6 0 1").unwrap();
+        let mirrored = game.create_snapshot().mirrored_horizontally();
+
+        let mirrored_game =
+            Game::from_snapshot(mirrored, game.move_counter().clone(), *game.variant());
+        assert_eq!(mirrored_game.to_fen(), "3k4/8/8/4pP2/8/8/8/3K3R w - e6 0 1");
+        assert_eq!(
+            mirrored_game.backend().mirrored_horizontally(),
+            *game.backend()
+        );
+    }
+
+    /// Counts the leaves of the move tree of `game` at `depth`.
+    fn count_leaves(game: &mut Game, depth: usize) -> usize {
+        if depth == 0 {
+            return 1;
+        }
+
+        let mut count = 0;
+        for ply in game.get_legal_moves() {
+            game.make_move(&ply);
+            count += count_leaves(game, depth - 1);
+            game.undo_last_move();
+        }
+        count
+    }
+}
@@ -0,0 +1,185 @@
+//! # UCI Coordinate Notation
+//!
+//! This module reads moves written in the coordinate notation of the Universal
+//! Chess Interface (`e2e4`, `e1g1`, `e7e8q`, `N@f3`), which engines and tools exchange.
+//!
+//! The notation only names the squares involved, so the position is needed to
+//! recover the rest of the move: which piece moves, what it captures, and whether
+//! it is a castling or an en passant capture. Writing is done by [`Ply::to_uci`].
+
+use crate::{
+    atoms::Coordinate,
+    moves::{Ply, SpecialMove},
+    pieces::{Kind, Promotion},
+    state::{Game, Variant},
+};
+
+/// Errors that can occur when parsing a move in UCI coordinate notation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum UciMoveError {
+    /// The input is not two squares followed by an optional promotion letter.
+    InvalidSyntax(String),
+    /// The input is well formed, but it is not a legal move in the position.
+    IllegalMove(String),
+}
+
+impl std::fmt::Display for UciMoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidSyntax(s) => write!(f, "Invalid UCI move syntax: {s}"),
+            Self::IllegalMove(s) => write!(f, "Illegal move: {s}"),
+        }
+    }
+}
+
+impl std::error::Error for UciMoveError {}
+
+impl<V: Variant> Game<V> {
+    /// Finds the legal move of the current position written in UCI coordinate notation.
+    ///
+    /// Castling is given as the King's move (`e1g1`) or, as in Chess960, as the King
+    /// capturing its own Rook (`e1h1`). Promotions carry the lowercase letter of the new
+    /// piece (`e7e8q`), and drops the uppercase letter of the piece (`N@f3`). This is the
+    /// inverse of [`Ply::to_uci`].
+    ///
+    /// # Errors
+    ///
+    /// * [`UciMoveError::InvalidSyntax`] if the input is not shaped like a UCI move.
+    /// * [`UciMoveError::IllegalMove`] if it is not a legal move in the current position.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bonsai_chess::prelude::*;
+    ///
+    /// let game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+    /// let ply = game.parse_uci_move("e1c1").unwrap();
+    ///
+    /// assert!(matches!(ply.special_move(), Some(SpecialMove::Castle(CastlingSide::Long, _))));
+    /// assert_eq!(ply.to_uci(), "e1c1");
+    /// assert_eq!(game.parse_uci_move("e1a1"), Ok(ply));
+    /// ```
+    pub fn parse_uci_move(&self, uci: &str) -> Result<Ply, UciMoveError> {
+        let text = uci.trim();
+        let illegal = || UciMoveError::IllegalMove(text.to_string());
+
+        match UciNotation::parse(text) {
+            None => Err(UciMoveError::InvalidSyntax(text.to_string())),
+            Some(UciNotation::Drop(kind, to)) => self
+                .get_legal_moves()
+                .into_iter()
+                .find(|ply| {
+                    ply.is_drop() && ply.piece_moved().kind() == kind && ply.ending_square() == to
+                })
+                .ok_or_else(illegal),
+            Some(UciNotation::Move(from, to, promotion)) => self
+                .get_legal_moves()
+                .into_iter()
+                .find(|ply| {
+                    let ply_promotion = match ply.special_move() {
+                        Some(SpecialMove::Promotion(promotion)) => Some(promotion),
+                        _ => None,
+                    };
+
+                    let is_castle_onto_rook = matches!(
+                        ply.special_move(),
+                        Some(SpecialMove::Castle(_, rook)) if rook == to
+                    );
+
+                    !ply.is_drop()
+                        && ply.starting_square() == from
+                        && (ply.ending_square() == to || is_castle_onto_rook)
+                        && ply_promotion == promotion
+                })
+                .ok_or_else(illegal),
+        }
+    }
+}
+
+/// A move in UCI coordinate notation, as written: the position is needed to know what it does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum UciNotation {
+    /// A piece of the given kind dropped from the pocket (`N@f3`).
+    Drop(Kind, Coordinate),
+    /// A piece moved between two squares, promoting if a letter follows them (`e7e8q`).
+    Move(Coordinate, Coordinate, Option<Promotion>),
+}
+
+impl UciNotation {
+    /// Reads the squares and letters of a move, or returns `None` if `text` is not shaped
+    /// like a UCI move.
+    pub(super) fn parse(text: &str) -> Option<Self> {
+        if let Some((piece, square)) = text.split_once('@') {
+            let kind = match piece {
+                "P" => Kind::Pawn,
+                "N" => Kind::Knight,
+                "B" => Kind::Bishop,
+                "R" => Kind::Rook,
+                "Q" => Kind::Queen,
+                _ => return None,
+            };
+            return Some(Self::Drop(
+                kind,
+                Coordinate::from_algebraic_notation(square)?,
+            ));
+        }
+
+        let from = Coordinate::from_algebraic_notation(text.get(0..2)?)?;
+        let to = Coordinate::from_algebraic_notation(text.get(2..4)?)?;
+        let promotion = match text.get(4..)? {
+            "" => None,
+            "q" => Some(Promotion::Queen),
+            "r" => Some(Promotion::Rook),
+            "b" => Some(Promotion::Bishop),
+            "n" => Some(Promotion::Knight),
+            _ => return None,
+        };
+        Some(Self::Move(from, to, promotion))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{Game, UciMoveError};
+
+    #[test]
+    fn test_round_trip_of_every_legal_move() {
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1",
+            "8/P6k/8/8/8/8/6Kp/8 b - - 0 1",
+        ];
+
+        for fen in fens {
+            let game = Game::from_fen(fen);
+            for ply in game.get_legal_moves() {
+                assert_eq!(game.parse_uci_move(&ply.to_uci()), Ok(ply));
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let game = Game::from_starting_position();
+        assert!(matches!(
+            game.parse_uci_move("e2"),
+            Err(UciMoveError::InvalidSyntax(_))
+        ));
+        assert!(matches!(
+            game.parse_uci_move("e2e4x"),
+            Err(UciMoveError::InvalidSyntax(_))
+        ));
+        assert!(matches!(
+            game.parse_uci_move("0000"),
+            Err(UciMoveError::InvalidSyntax(_))
+        ));
+        assert_eq!(
+            game.parse_uci_move("e2e5"),
+            Err(UciMoveError::IllegalMove("e2e5".to_string()))
+        );
+        assert!(matches!(
+            game.parse_uci_move("e2e4q"),
+            Err(UciMoveError::IllegalMove(_))
+        ));
+    }
+}
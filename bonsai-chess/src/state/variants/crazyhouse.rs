@@ -0,0 +1,260 @@
+//! # Crazyhouse
+//!
+//! Standard chess, except that captured pieces change sides: they go to the
+//! captor's pocket, from which they can later be dropped onto any empty square
+//! instead of moving a piece. A promoted piece goes back to being a pawn when it
+//! is captured.
+//!
+//! The pockets are written in the FEN between brackets after the piece placement
+//! (`.../RNBQKB1R[Pn] w`), and promoted pieces are marked with a tilde after their
+//! letter (`Q~`).
+
+use std::borrow::Cow;
+
+use crate::{
+    atoms::{Bitboard, Coordinate, Pocket, Side},
+    moves::{LegalityContext, MoveList, Ply, SpecialMove, generate_drops},
+    pieces::{Kind, Piece},
+    state::{Board, FenParsingError, Game, PositionSnapshot, Variant, validate_placement, zobrist},
+};
+
+/// Crazyhouse: captured pieces join the captor's army, and can be dropped back onto the board.
+///
+/// The variant keeps the pocket of each side and the squares of the promoted pieces,
+/// both read from and written to the FEN (`...[Qp] w ...`, `Q~`), and both part of the
+/// position's hash.
+///
+/// # Examples
+///
+/// ```rust
+/// use bonsai_chess::prelude::*;
+///
+/// let mut game = Game::with_variant(Crazyhouse::default());
+/// for san in ["e4", "d5", "exd5", "Qxd5", "Nc3", "Qa5"] {
+///     let ply = game.parse_san(san).unwrap();
+///     game.make_move(&ply);
+/// }
+///
+/// // Both sides captured a pawn, which White can now drop
+/// assert_eq!(game.variant().pocket(Side::White).count(Kind::Pawn), 1);
+/// let drop = game.parse_san("P@e6").unwrap();
+/// assert!(drop.is_drop());
+///
+/// game.make_move(&drop);
+/// assert_eq!(
+///     game.to_fen(),
+///     "rnb1kbnr/ppp1pppp/4P3/q7/8/2N5/PPPP1PPP/R1BQKBNR[p] b KQkq - 0 4"
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Crazyhouse {
+    /// The pieces in hand of each side, indexed by [`Side::index`].
+    pockets: [Pocket; 2],
+    /// The squares of the pieces that were pawns before promoting.
+    promoted: Bitboard,
+}
+
+impl Crazyhouse {
+    /// Creates the variant with the pieces each side already holds in hand, and no promoted piece.
+    #[must_use]
+    pub const fn new(white_pocket: Pocket, black_pocket: Pocket) -> Self {
+        Self {
+            pockets: [white_pocket, black_pocket],
+            promoted: Bitboard::EMPTY,
+        }
+    }
+
+    /// Returns the pieces `side` holds in hand.
+    #[must_use]
+    pub const fn pocket(self, side: Side) -> Pocket {
+        self.pockets[side.index()]
+    }
+
+    /// Returns the squares of the promoted pieces, which go back to being pawns when captured.
+    #[must_use]
+    pub const fn promoted(self) -> Bitboard {
+        self.promoted
+    }
+}
+
+impl Variant for Crazyhouse {
+    const NAME: &'static str = "Crazyhouse";
+
+    fn read_fen<'a>(&mut self, fen: &'a str) -> Result<Cow<'a, str>, FenParsingError> {
+        *self = Self::default();
+
+        let fen = fen.trim_start();
+        let (placement, rest) = fen.split_at(fen.find(' ').unwrap_or(fen.len()));
+        let invalid = || FenParsingError::InvalidVariantField(placement.to_string());
+
+        let (board, pockets) = match placement.split_once('[') {
+            Some((board, pockets)) => (board, pockets.strip_suffix(']').ok_or_else(invalid)?),
+            None => (placement, ""),
+        };
+
+        for letter in pockets.chars() {
+            let kind = match letter.to_ascii_lowercase() {
+                'q' => Kind::Queen,
+                'r' => Kind::Rook,
+                'b' => Kind::Bishop,
+                'n' => Kind::Knight,
+                'p' => Kind::Pawn,
+                _ => return Err(invalid()),
+            };
+            let side = if letter.is_ascii_uppercase() {
+                Side::White
+            } else {
+                Side::Black
+            };
+            let pocket = &mut self.pockets[side.index()];
+            if pocket.count(kind) >= Pocket::max_count(kind) {
+                return Err(invalid());
+            }
+            pocket.add(kind);
+        }
+
+        // A tilde marks the piece right before it as promoted
+        let mut square = 0;
+        let mut follows_piece = false;
+        for c in board.chars() {
+            match c {
+                '~' if follows_piece => {
+                    if let Some(promoted) = Coordinate::from_index(square - 1) {
+                        self.promoted.insert(promoted);
+                    }
+                }
+                '~' => return Err(invalid()),
+                '/' => {}
+                '1'..='8' => square += c.to_digit(10).unwrap_or_default() as usize,
+                _ => square += 1,
+            }
+            follows_piece = c.is_ascii_alphabetic();
+        }
+
+        if board.len() == placement.len() && !board.contains('~') {
+            return Ok(Cow::Borrowed(fen));
+        }
+        Ok(Cow::Owned(format!("{}{rest}", board.replace('~', ""))))
+    }
+
+    fn write_fen(self, fen: &mut String) {
+        let placement_end = fen.find(' ').unwrap_or(fen.len());
+
+        let mut placement = String::new();
+        let mut square = 0;
+        for c in fen[..placement_end].chars() {
+            placement.push(c);
+            match c {
+                '/' => {}
+                '1'..='8' => square += c.to_digit(10).unwrap_or_default() as usize,
+                _ => {
+                    if Coordinate::from_index(square).is_some_and(|sq| self.promoted.contains(sq)) {
+                        placement.push('~');
+                    }
+                    square += 1;
+                }
+            }
+        }
+
+        placement.push('[');
+        for side in [Side::White, Side::Black] {
+            for kind in Pocket::KINDS {
+                for _ in 0..self.pocket(side).count(kind) {
+                    placement.push_str(&Piece::new(side, kind).to_string());
+                }
+            }
+        }
+        placement.push(']');
+
+        fen.replace_range(..placement_end, &placement);
+    }
+
+    /// Captured pieces change colour, so a side may have more pieces than its starting set.
+    fn validate_position(self, position: &PositionSnapshot) -> Result<(), FenParsingError> {
+        validate_placement(position)
+    }
+
+    fn zobrist_hash(self) -> u64 {
+        let pockets = [Side::White, Side::Black]
+            .into_iter()
+            .flat_map(|side| Pocket::KINDS.map(|kind| (side, kind)))
+            .fold(0, |hash, (side, kind)| {
+                hash ^ zobrist::pocket(side, kind, self.pocket(side).count(kind))
+            });
+
+        self.promoted
+            .into_iter()
+            .fold(pockets, |hash, square| hash ^ zobrist::promoted(square))
+    }
+
+    fn color_flipped(self) -> Self {
+        Self {
+            pockets: [self.pocket(Side::Black), self.pocket(Side::White)],
+            // Squares are numbered rank by rank, one byte each, so the ranks are mirrored
+            // by reversing the bytes
+            promoted: Bitboard(self.promoted.0.swap_bytes()),
+        }
+    }
+
+    fn extend_legal_moves(
+        self,
+        board: &Board,
+        side: Side,
+        context: &LegalityContext,
+        moves: &mut MoveList,
+    ) {
+        generate_drops(side, self.pocket(side), board, context, moves);
+    }
+
+    fn move_made(&mut self, ply: &Ply, _gives_check: bool) {
+        let pocket = &mut self.pockets[ply.piece_moved().team().index()];
+
+        if ply.is_drop() {
+            pocket.remove(ply.piece_moved().kind());
+            return;
+        }
+
+        if let Some(captured) = ply.piece_captured() {
+            let to = ply.ending_square();
+            if self.promoted.contains(to) {
+                pocket.add(Kind::Pawn);
+                self.promoted.remove(to);
+            } else {
+                pocket.add(captured.kind());
+            }
+        }
+
+        let from = ply.starting_square();
+        if self.promoted.contains(from)
+            || matches!(ply.special_move(), Some(SpecialMove::Promotion(_)))
+        {
+            self.promoted.remove(from);
+            self.promoted.insert(ply.ending_square());
+        }
+    }
+
+    /// Captured pieces are never lost, so there is always material to mate with.
+    fn is_dead_position(self, _game: &Game<Self>) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{Crazyhouse, FenParsingError, Game};
+
+    #[test]
+    fn test_overfull_pockets_are_rejected() {
+        let fen = |pockets: &str| format!("4k3/8/8/8/8/8/8/4K3[{pockets}] w - - 0 1");
+
+        let full = Game::try_from_fen_with_variant(&fen(&"P".repeat(16)), Crazyhouse::default());
+        assert!(full.is_ok());
+
+        for pockets in ["P".repeat(17), "P".repeat(300), "qqq".to_string()] {
+            assert!(matches!(
+                Game::try_from_fen_with_variant(&fen(&pockets), Crazyhouse::default()),
+                Err(FenParsingError::InvalidVariantField(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! # King of the Hill
+//!
+//! Standard chess, with one more way to win: bringing one's King to one of the
+//! four central squares (d4, e4, d5 or e5).
+
+use crate::{
+    atoms::{Bitboard, Coordinate, Side},
+    rules::{Outcome, WinReason},
+    state::{Game, Variant},
+};
+
+/// King of the Hill: the first King to reach d4, e4, d5 or e5 wins.
+///
+/// Checkmate still wins too. As a King can always walk towards the centre, no
+/// position is ever dead for lack of mating material.
+///
+/// # Examples
+///
+/// ```rust
+/// use bonsai_chess::prelude::*;
+///
+/// let mut game = Game::try_from_fen_with_variant("4k3/8/8/8/8/4K3/8/8 w - - 0 1", KingOfTheHill).unwrap();
+/// let ply = game.parse_san("Ke4").unwrap();
+/// game.make_move(&ply);
+///
+/// assert_eq!(
+///     game.outcome(),
+///     Some(Outcome::Win { winner: Side::White, reason: WinReason::VariantWin })
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct KingOfTheHill;
+
+impl KingOfTheHill {
+    /// The four central squares: d4, e4, d5 and e5.
+    pub const HILL: Bitboard = Bitboard(0x0000_0018_1800_0000);
+
+    /// Returns `true` if `square` is one of the four central squares.
+    #[must_use]
+    pub const fn is_on_hill(square: Coordinate) -> bool {
+        Self::HILL.contains(square)
+    }
+}
+
+impl Variant for KingOfTheHill {
+    const NAME: &'static str = "King of the Hill";
+
+    fn outcome(self, game: &Game<Self>) -> Option<Outcome> {
+        [Side::White, Side::Black]
+            .into_iter()
+            .find(|side| Self::is_on_hill(game.backend().king(*side)))
+            .map(|winner| Outcome::Win {
+                winner,
+                reason: WinReason::VariantWin,
+            })
+    }
+
+    fn is_dead_position(self, _game: &Game<Self>) -> bool {
+        false
+    }
+}
@@ -0,0 +1,354 @@
+//! # Variants
+//!
+//! The `variants` module lets a [`Game`] be played under rules other than standard chess.
+//!
+//! A [`Variant`] can override the starting position, allow moves the standard rules
+//! do not or forbid some of the ones they do, and add win conditions checked after
+//! every move. The variant is a type parameter of [`Game`], which defaults to
+//! [`Standard`]: every hook of `Standard` is an empty default that the compiler
+//! removes, so standard chess pays nothing for the flexibility.
+//!
+//! # Components
+//!
+//! * [`Variant`]: The trait implemented by every variant.
+//! * [`Standard`]: Standard chess, as defined by the FIDE Laws of Chess.
+//! * [`KingOfTheHill`]: Bringing the King to the centre of the board wins.
+//! * [`ThreeCheck`]: Checking the opponent three times wins.
+//! * [`Crazyhouse`]: Captured pieces can be dropped back onto the board.
+//! * [`RacingKings`]: The first King to reach the eighth rank wins, and checks are forbidden.
+
+/// Captured pieces change sides and can be dropped back onto the board.
+mod crazyhouse;
+
+/// Bringing the King to one of the four central squares wins.
+mod king_of_the_hill;
+
+/// The first King to reach the eighth rank wins, and no move may give check.
+mod racing_kings;
+
+/// Giving a third check wins.
+mod three_check;
+
+pub use crazyhouse::Crazyhouse;
+pub use king_of_the_hill::KingOfTheHill;
+pub use racing_kings::RacingKings;
+pub use three_check::ThreeCheck;
+
+use std::borrow::Cow;
+
+use crate::{
+    atoms::Side,
+    moves::{LegalityContext, MoveList, Ply},
+    rules::Outcome,
+    state::{Board, FenParsingError, Game, PositionSnapshot, validate_position},
+};
+
+/// The FEN of the standard starting position.
+pub const STANDARD_STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// The rules of a chess variant, as deviations from standard chess.
+///
+/// Every method has a default that keeps the standard behaviour, so a variant only
+/// implements the rules it changes. A variant is a small `Copy` value stored in the
+/// [`Game`], which lets it keep state of its own (like the number of checks given in
+/// [`ThreeCheck`]) that follows the moves made and undone.
+///
+/// # Examples
+///
+/// ```rust
+/// use bonsai_chess::prelude::*;
+///
+/// // A toy variant: capturing a Queen wins the game.
+/// #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// struct QueenHunt;
+///
+/// impl Variant for QueenHunt {
+///     const NAME: &'static str = "Queen Hunt";
+///
+///     fn outcome(self, game: &Game<Self>) -> Option<Outcome> {
+///         let last = game.get_move_log().pop()?;
+///         (last.piece_captured()?.kind() == Kind::Queen).then_some(Outcome::Win {
+///             winner: last.piece_moved().team(),
+///             reason: WinReason::VariantWin,
+///         })
+///     }
+/// }
+///
+/// let mut game = Game::try_from_fen_with_variant("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1", QueenHunt).unwrap();
+/// let capture = game.parse_san("Rxd5").unwrap();
+/// game.make_move(&capture);
+/// assert_eq!(game.outcome().and_then(|outcome| outcome.winner()), Some(Side::White));
+/// ```
+pub trait Variant: Clone + Copy + std::fmt::Debug + Default + PartialEq + Eq {
+    /// The name of the variant, as written in the `Variant` tag of a PGN.
+    const NAME: &'static str;
+
+    /// Whether [`move_made`](Self::move_made) needs to know if the move gave check.
+    ///
+    /// Finding out costs an attack query on every move, which only variants counting
+    /// checks should pay for: when `false`, `move_made` is always told `false`.
+    const TRACKS_CHECKS: bool = false;
+
+    /// Returns the FEN of the position games of this variant start from.
+    #[must_use]
+    fn starting_fen(self) -> &'static str {
+        STANDARD_STARTING_FEN
+    }
+
+    /// Reads the state kept by the variant from a FEN, and returns the standard FEN left
+    /// once the variant's own additions are removed from it.
+    ///
+    /// Variants may add fields after the fullmove counter, which the standard parser
+    /// ignores, or change the standard fields, which must then be given back without
+    /// the changes (like the pockets of [`Crazyhouse`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FenParsingError::InvalidVariantField`] if the variant's additions are malformed.
+    fn read_fen<'a>(&mut self, fen: &'a str) -> Result<Cow<'a, str>, FenParsingError> {
+        Ok(Cow::Borrowed(fen))
+    }
+
+    /// Adds the state kept by the variant to a standard FEN, as read by [`read_fen`](Self::read_fen).
+    fn write_fen(self, _fen: &mut String) {}
+
+    /// Checks that a position read from a FEN could arise in a game of this variant.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`FenParsingError`] found, as [`validate_position`] does for standard chess.
+    fn validate_position(self, position: &PositionSnapshot) -> Result<(), FenParsingError> {
+        validate_position(position)
+    }
+
+    /// Returns the Zobrist key of the state kept by the variant, so that positions that
+    /// only differ by it are not counted as repetitions.
+    #[must_use]
+    fn zobrist_hash(self) -> u64 {
+        0
+    }
+
+    /// Returns the state kept by the variant once the colours of the position are swapped,
+    /// as [`Game::color_flipped`] does: what belonged to White now belongs to Black, and
+    /// squares are mirrored from one side of the board to the other.
+    #[must_use]
+    fn color_flipped(self) -> Self {
+        self
+    }
+
+    /// Adds to `moves` the moves that only the variant allows.
+    ///
+    /// `moves` are the moves of `side` on `board` that are legal in standard chess, and
+    /// `context` holds the checks and pins of `side`'s King, which the new moves must respect.
+    fn extend_legal_moves(
+        self,
+        _board: &Board,
+        _side: Side,
+        _context: &LegalityContext,
+        _moves: &mut MoveList,
+    ) {
+    }
+
+    /// Removes from `moves` the moves that the variant forbids.
+    ///
+    /// `moves` are the moves of `side` on `board` that are legal in standard chess, along
+    /// with the ones added by [`extend_legal_moves`](Self::extend_legal_moves).
+    fn retain_legal_moves(self, _board: &Board, _side: Side, _moves: &mut MoveList) {}
+
+    /// Updates the state kept by the variant after `ply` was made.
+    ///
+    /// `gives_check` tells whether the move checked the enemy King, if the variant
+    /// [tracks checks](Self::TRACKS_CHECKS).
+    ///
+    /// The [`Game`] keeps the state from before every move, so undoing a move restores it
+    /// without the variant's help.
+    fn move_made(&mut self, _ply: &Ply, _gives_check: bool) {}
+
+    /// Returns the outcome of the game caused by the variant's own rules in the current
+    /// position, checked by [`Game::outcome`]. It takes precedence over the standard outcomes.
+    fn outcome(self, _game: &Game<Self>) -> Option<Outcome> {
+        None
+    }
+
+    /// Returns `true` if neither side can win any more, which ends the game in a draw.
+    ///
+    /// In standard chess, that is when neither side has mating material.
+    #[must_use]
+    fn is_dead_position(self, game: &Game<Self>) -> bool {
+        !game.has_mating_material(Side::White) && !game.has_mating_material(Side::Black)
+    }
+}
+
+/// Standard chess, as defined by the FIDE Laws of Chess.
+///
+/// This is the default variant of a [`Game`], and it does not override any rule.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Standard;
+
+impl Variant for Standard {
+    const NAME: &'static str = "Standard";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{DrawReason, WinReason};
+
+    #[test]
+    fn test_three_check_counters_follow_moves() {
+        let fen = "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2 +1+0";
+        let mut game = Game::try_from_fen_with_variant(fen, ThreeCheck::default()).unwrap();
+        assert_eq!(game.to_fen(), fen);
+        let hash = game.hash();
+
+        for san in ["Bc4", "Nc6", "Bxf7+"] {
+            let ply = game.parse_san(san).unwrap();
+            game.make_move(&ply);
+        }
+        assert_eq!(game.variant().checks_given(Side::White), 2);
+        assert!(game.to_fen().ends_with(" +2+0"));
+
+        for _ in 0..3 {
+            game.undo_last_move();
+        }
+        assert_eq!(game.to_fen(), fen);
+        assert_eq!(game.hash(), hash);
+        assert_ne!(hash, Game::from_fen(STANDARD_STARTING_FEN).hash());
+    }
+
+    #[test]
+    fn test_three_check_win() {
+        let fen = "4k3/8/8/8/8/8/8/R3K3 w - - 0 1 +2+0";
+        let mut game = Game::try_from_fen_with_variant(fen, ThreeCheck::default()).unwrap();
+        let ply = game.parse_san("Ra8+").unwrap();
+        game.make_move(&ply);
+
+        assert_eq!(
+            game.outcome(),
+            Some(Outcome::Win {
+                winner: Side::White,
+                reason: WinReason::VariantWin,
+            })
+        );
+        assert!(
+            Game::try_from_fen_with_variant(
+                "4k3/8/8/8/8/8/8/4K3 w - - 0 1 +4+0",
+                ThreeCheck::default()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_king_of_the_hill_win() {
+        let mut game =
+            Game::try_from_fen_with_variant("4k3/8/8/8/8/4K3/8/8 w - - 0 1", KingOfTheHill)
+                .unwrap();
+        let ply = game.parse_san("Ke4").unwrap();
+        game.make_move(&ply);
+
+        assert_eq!(
+            game.outcome(),
+            Some(Outcome::Win {
+                winner: Side::White,
+                reason: WinReason::VariantWin,
+            })
+        );
+    }
+
+    #[test]
+    fn test_racing_kings() {
+        let game = Game::with_variant(RacingKings);
+        assert_eq!(game.get_legal_moves().len(), 21);
+
+        // Black's King must follow White's to the eighth rank to draw
+        let mut game =
+            Game::try_from_fen_with_variant("8/k4K2/8/8/8/8/8/8 w - - 0 1", RacingKings).unwrap();
+        for san in ["Kf8", "Ka6"] {
+            let ply = game.parse_san(san).unwrap();
+            game.make_move(&ply);
+        }
+        assert_eq!(
+            game.outcome(),
+            Some(Outcome::Win {
+                winner: Side::White,
+                reason: WinReason::VariantWin,
+            })
+        );
+        game.undo_last_move();
+        let ply = game.parse_san("Kb8").unwrap();
+        game.make_move(&ply);
+        assert_eq!(
+            game.outcome(),
+            Some(Outcome::Draw {
+                reason: DrawReason::VariantDraw,
+            })
+        );
+
+        // Moves giving check are not legal
+        let game =
+            Game::try_from_fen_with_variant("8/8/8/8/8/2k5/8/R6K w - - 0 1", RacingKings).unwrap();
+        assert!(game.parse_san("Rc1").is_err());
+        assert!(game.parse_san("Rb1").is_ok());
+    }
+
+    #[test]
+    fn test_crazyhouse_promoted_pieces_return_as_pawns() {
+        let fen = "4k2r/8/8/8/8/8/8/4K2Q~[N] b k - 0 1";
+        let mut game = Game::try_from_fen_with_variant(fen, Crazyhouse::default()).unwrap();
+        assert_eq!(game.to_fen(), fen);
+        let hash = game.hash();
+        assert_ne!(
+            hash,
+            Game::try_from_fen_with_variant(
+                "4k2r/8/8/8/8/8/8/4K2Q[N] b k - 0 1",
+                Crazyhouse::default()
+            )
+            .unwrap()
+            .hash()
+        );
+
+        let capture = game.parse_san("Rxh1+").unwrap();
+        game.make_move(&capture);
+        assert_eq!(game.to_fen(), "4k3/8/8/8/8/8/8/4K2r[Np] w - - 0 2");
+
+        game.undo_last_move();
+        assert_eq!(game.to_fen(), fen);
+        assert_eq!(game.hash(), hash);
+    }
+
+    #[test]
+    fn test_crazyhouse_drops() {
+        // Pawns cannot be dropped on the first or eighth rank
+        let game = Game::try_from_fen_with_variant(
+            "4k3/8/8/8/8/8/8/4K3[P] w - - 0 1",
+            Crazyhouse::default(),
+        )
+        .unwrap();
+        assert_eq!(game.get_legal_moves().len(), 5 + 48);
+        assert_eq!(game.parse_san("@e4"), game.parse_san("P@e4"));
+        assert!(game.parse_san("P@e8").is_err());
+        assert_eq!(game.parse_uci_move("P@e4").unwrap().to_uci(), "P@e4");
+
+        // A drop can block a check, so this is not a mate
+        let fen = "r5k1/5ppp/8/8/8/8/5PPP/6K1[N] b - - 0 1";
+        let mut game = Game::try_from_fen_with_variant(fen, Crazyhouse::default()).unwrap();
+        let check = game.parse_san("Ra1").unwrap();
+        assert_eq!(game.to_san(&check), "Ra1+");
+        game.make_move(&check);
+        assert_eq!(game.get_legal_moves().len(), 5);
+        assert_eq!(game.outcome(), None);
+
+        let game = Game::from_fen("r5k1/5ppp/8/8/8/8/5PPP/6K1 b - - 0 1");
+        let check = game.parse_san("Ra1").unwrap();
+        assert_eq!(game.to_san(&check), "Ra1#");
+
+        assert!(
+            Game::try_from_fen_with_variant(
+                "4k3/8/8/8/8/8/8/4K3[K] w - - 0 1",
+                Crazyhouse::default()
+            )
+            .is_err()
+        );
+    }
+}
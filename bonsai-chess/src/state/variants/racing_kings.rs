@@ -0,0 +1,91 @@
+//! # Racing Kings
+//!
+//! Both sides start side by side on the first two ranks, without pawns, and race
+//! their King to the eighth rank. Giving check is forbidden, so no King can ever
+//! be mated.
+
+use crate::{
+    atoms::Side,
+    moves::MoveList,
+    pieces::Kind,
+    rules::{DrawReason, Outcome, WinReason},
+    state::{Board, Game, Variant},
+};
+
+/// Racing Kings: the first King to reach the eighth rank wins.
+///
+/// No move may give check. As White moves first, if White's King reaches the eighth
+/// rank, Black gets one last move to reach it too, which draws the game.
+///
+/// # Examples
+///
+/// ```rust
+/// use bonsai_chess::prelude::*;
+///
+/// // Black cannot reach the eighth rank in time
+/// let mut game = Game::try_from_fen_with_variant("8/5K2/8/8/8/8/k7/8 w - - 0 1", RacingKings).unwrap();
+/// let ply = game.parse_san("Kf8").unwrap();
+/// game.make_move(&ply);
+///
+/// assert_eq!(
+///     game.outcome(),
+///     Some(Outcome::Win { winner: Side::White, reason: WinReason::VariantWin })
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RacingKings;
+
+impl RacingKings {
+    /// Row of the eighth rank, the goal of both Kings.
+    const GOAL_ROW: usize = 0;
+}
+
+impl Variant for RacingKings {
+    const NAME: &'static str = "Racing Kings";
+
+    fn starting_fen(self) -> &'static str {
+        "8/8/8/8/8/8/krbnNBRK/qrbnNBRQ w - - 0 1"
+    }
+
+    fn retain_legal_moves(self, board: &Board, side: Side, moves: &mut MoveList) {
+        moves.retain(|ply| {
+            let mut after = *board;
+            after.make_move(ply);
+            !after.is_square_under_attack(after.king(side.opposite()), side)
+        });
+    }
+
+    fn outcome(self, game: &Game<Self>) -> Option<Outcome> {
+        let has_arrived = |side| game.backend().king(side).row() == Self::GOAL_ROW;
+        let arrived = (has_arrived(Side::White), has_arrived(Side::Black));
+        let win = |winner| {
+            Some(Outcome::Win {
+                winner,
+                reason: WinReason::VariantWin,
+            })
+        };
+
+        match arrived {
+            (true, true) => Some(Outcome::Draw {
+                reason: DrawReason::VariantDraw,
+            }),
+            (false, true) => win(Side::Black),
+            (true, false) if game.turn() == Side::White => win(Side::White),
+            // White arrived first: Black may still draw by arriving right after
+            (true, false) => {
+                let mut replies = MoveList::new();
+                game.generate_moves_into(&mut replies);
+                let can_follow = replies.iter().any(|ply| {
+                    ply.piece_moved().kind() == Kind::King
+                        && ply.ending_square().row() == Self::GOAL_ROW
+                });
+                if can_follow { None } else { win(Side::White) }
+            }
+            (false, false) => None,
+        }
+    }
+
+    fn is_dead_position(self, _game: &Game<Self>) -> bool {
+        false
+    }
+}
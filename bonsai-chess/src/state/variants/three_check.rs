@@ -0,0 +1,131 @@
+//! # Three-Check
+//!
+//! Standard chess, with one more way to win: giving check for the third time.
+//! The number of checks each side has given is part of the position, and is
+//! written at the end of the FEN as `+W+B`, as on lichess.
+
+use std::borrow::Cow;
+
+use crate::{
+    atoms::Side,
+    moves::Ply,
+    pieces::Kind,
+    rules::{Outcome, WinReason},
+    state::{FenParsingError, Game, Variant, zobrist},
+};
+
+/// Three-Check: the first side to give check three times wins.
+///
+/// The variant keeps the number of checks each side has given, which is read from
+/// and written to the FEN (`... 0 1 +1+0`), and is part of the position's hash.
+///
+/// # Examples
+///
+/// ```rust
+/// use bonsai_chess::prelude::*;
+///
+/// let mut game = Game::with_variant(ThreeCheck::default());
+/// for san in ["e4", "f6", "Qh5+"] {
+///     let ply = game.parse_san(san).unwrap();
+///     game.make_move(&ply);
+/// }
+///
+/// assert_eq!(game.variant().checks_given(Side::White), 1);
+/// assert!(game.to_fen().ends_with(" +1+0"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ThreeCheck {
+    /// Number of checks given by each side, indexed by [`Side::index`].
+    checks_given: [u8; 2],
+}
+
+impl ThreeCheck {
+    /// Number of checks that wins the game.
+    pub const CHECKS_TO_WIN: u8 = 3;
+
+    /// Creates the variant with the number of checks each side has already given.
+    #[must_use]
+    pub const fn new(white_checks: u8, black_checks: u8) -> Self {
+        Self {
+            checks_given: [white_checks, black_checks],
+        }
+    }
+
+    /// Returns the number of checks `side` has given so far.
+    #[must_use]
+    pub const fn checks_given(self, side: Side) -> u8 {
+        self.checks_given[side.index()]
+    }
+}
+
+impl Variant for ThreeCheck {
+    const NAME: &'static str = "Three-check";
+    const TRACKS_CHECKS: bool = true;
+
+    fn read_fen<'a>(&mut self, fen: &'a str) -> Result<Cow<'a, str>, FenParsingError> {
+        // The counters are the seventh field, if present
+        let Some(field) = fen.split_whitespace().nth(6) else {
+            *self = Self::default();
+            return Ok(Cow::Borrowed(fen));
+        };
+
+        let invalid = || FenParsingError::InvalidVariantField(field.to_string());
+        let counters = field.strip_prefix('+').ok_or_else(invalid)?;
+        let (white, black) = counters.split_once('+').ok_or_else(invalid)?;
+        let parse = |counter: &str| {
+            counter
+                .parse::<u8>()
+                .ok()
+                .filter(|checks| *checks <= Self::CHECKS_TO_WIN)
+                .ok_or_else(invalid)
+        };
+
+        *self = Self::new(parse(white)?, parse(black)?);
+        Ok(Cow::Borrowed(fen))
+    }
+
+    fn write_fen(self, fen: &mut String) {
+        let [white, black] = self.checks_given;
+        fen.push_str(" +");
+        fen.push(char::from(b'0' + white));
+        fen.push('+');
+        fen.push(char::from(b'0' + black));
+    }
+
+    fn zobrist_hash(self) -> u64 {
+        [Side::White, Side::Black]
+            .into_iter()
+            .fold(0, |hash, side| {
+                hash ^ zobrist::checks(side, self.checks_given(side))
+            })
+    }
+
+    fn color_flipped(self) -> Self {
+        Self::new(
+            self.checks_given(Side::Black),
+            self.checks_given(Side::White),
+        )
+    }
+
+    fn move_made(&mut self, ply: &Ply, gives_check: bool) {
+        if gives_check {
+            self.checks_given[ply.piece_moved().team().index()] += 1;
+        }
+    }
+
+    fn outcome(self, _game: &Game<Self>) -> Option<Outcome> {
+        [Side::White, Side::Black]
+            .into_iter()
+            .find(|side| self.checks_given(*side) >= Self::CHECKS_TO_WIN)
+            .map(|winner| Outcome::Win {
+                winner,
+                reason: WinReason::VariantWin,
+            })
+    }
+
+    /// Any piece can give check, so the game is only dead with bare Kings.
+    fn is_dead_position(self, game: &Game<Self>) -> bool {
+        let board = game.backend();
+        board.occupied() == board.kind(Kind::King)
+    }
+}
@@ -0,0 +1,274 @@
+//! # Zobrist Hashing
+//!
+//! This module provides the random keys used to compute a 64-bit Zobrist hash
+//! of a position. Every feature of a position (a piece on a square, the side to
+//! move, each castling right and its Rook, the en passant file) is assigned a random number,
+//! and the hash of a position is the XOR of the keys of all its features.
+//!
+//! Because XOR is its own inverse, making a move only needs to XOR out the keys
+//! of the features that disappeared and XOR in the ones that appeared, which lets
+//! the hash be maintained incrementally instead of recomputed from scratch.
+//!
+//! The keys are generated at compile time from a fixed seed, so hashes are stable
+//! across runs and builds.
+
+use crate::{
+    atoms::{CastlingRights, Coordinate, Side},
+    moves::CastlingSide,
+    pieces::{Kind, Piece},
+};
+
+/// Number of squares on the board.
+const SQUARES: usize = 64;
+
+/// Largest number of pieces of one kind in a pocket with a key of its own, for Crazyhouse.
+const POCKET_COUNTS: usize = 16;
+
+/// Seed of the key generator. Any value works, but it must never change
+/// between builds, or hashes stored elsewhere would become meaningless.
+const SEED: u64 = 0x5EED_B045_A1C4_E555;
+
+/// All the keys, generated in one pass so that no two features share a key.
+static KEYS: Keys = Keys::generate();
+
+/// The full set of Zobrist keys.
+struct Keys {
+    /// One key per piece per square, indexed by [`Side::index`], [`Kind::index`](crate::pieces::Kind::index)
+    /// and [`Coordinate::index`].
+    pieces: [[[u64; SQUARES]; 6]; 2],
+    /// Toggled whenever Black is to move.
+    black_to_move: u64,
+    /// One key per side per file of a Rook its King may still castle with, indexed by
+    /// [`Side::index`] and the file.
+    castling: [[u64; 8]; 2],
+    /// One key per file of the en passant target square.
+    en_passant: [u64; 8],
+    /// One key per side per number of checks given (1 to 3), for Three-Check.
+    checks: [[u64; 3]; 2],
+    /// One key per side per piece kind per number of pieces in the pocket (1 to 16), for Crazyhouse.
+    pockets: [[[u64; POCKET_COUNTS]; 6]; 2],
+    /// One key per square holding a promoted piece, for Crazyhouse.
+    promoted: [u64; SQUARES],
+}
+
+impl Keys {
+    const fn generate() -> Self {
+        let mut state = SEED;
+
+        let mut pieces = [[[0; SQUARES]; 6]; 2];
+        let mut side = 0;
+        while side < 2 {
+            let mut kind = 0;
+            while kind < 6 {
+                let mut square = 0;
+                while square < SQUARES {
+                    pieces[side][kind][square] = split_mix(&mut state);
+                    square += 1;
+                }
+                kind += 1;
+            }
+            side += 1;
+        }
+
+        let black_to_move = split_mix(&mut state);
+
+        let mut castling = [[0; 8]; 2];
+        let mut side = 0;
+        while side < 2 {
+            let mut file = 0;
+            while file < 8 {
+                castling[side][file] = split_mix(&mut state);
+                file += 1;
+            }
+            side += 1;
+        }
+
+        let mut en_passant = [0; 8];
+        let mut file = 0;
+        while file < en_passant.len() {
+            en_passant[file] = split_mix(&mut state);
+            file += 1;
+        }
+
+        // Variant keys are generated after the standard ones, so that those do not depend on them
+        let mut checks = [[0; 3]; 2];
+        let mut side = 0;
+        while side < 2 {
+            let mut count = 0;
+            while count < 3 {
+                checks[side][count] = split_mix(&mut state);
+                count += 1;
+            }
+            side += 1;
+        }
+
+        let mut pockets = [[[0; POCKET_COUNTS]; 6]; 2];
+        let mut side = 0;
+        while side < 2 {
+            let mut kind = 0;
+            while kind < 6 {
+                let mut count = 0;
+                while count < POCKET_COUNTS {
+                    pockets[side][kind][count] = split_mix(&mut state);
+                    count += 1;
+                }
+                kind += 1;
+            }
+            side += 1;
+        }
+
+        let mut promoted = [0; SQUARES];
+        let mut square = 0;
+        while square < SQUARES {
+            promoted[square] = split_mix(&mut state);
+            square += 1;
+        }
+
+        Self {
+            pieces,
+            black_to_move,
+            castling,
+            en_passant,
+            checks,
+            pockets,
+            promoted,
+        }
+    }
+}
+
+/// The `SplitMix64` pseudo-random generator: small, fast and good enough for hashing keys.
+const fn split_mix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Returns the key of a piece standing on a square.
+#[must_use]
+pub fn piece(piece: Piece, square: Coordinate) -> u64 {
+    KEYS.pieces[piece.team().index()][piece.kind().index()][square.index()]
+}
+
+/// Returns the key of the side to move (zero for White).
+#[must_use]
+pub fn side_to_move(side: Side) -> u64 {
+    match side {
+        Side::White => 0,
+        Side::Black => KEYS.black_to_move,
+    }
+}
+
+/// Returns the combined key of all the castling rights still available.
+///
+/// Each right is keyed on the file of its Rook, so that Chess960 positions where the
+/// King may castle with Rooks on different files do not share a hash. A side's two
+/// rights always use Rooks on either side of its King, so they never share a key.
+#[must_use]
+pub fn castling(castling_rights: CastlingRights) -> u64 {
+    let mut hash = 0;
+    for side in [Side::White, Side::Black] {
+        for castling_side in [CastlingSide::Short, CastlingSide::Long] {
+            if let Some(file) = castling_rights.rook_file(side, castling_side) {
+                hash ^= KEYS.castling[side.index()][file];
+            }
+        }
+    }
+    hash
+}
+
+/// Returns the key of the en passant target square, if any (zero otherwise).
+#[must_use]
+pub fn en_passant(en_passant_target: Option<Coordinate>) -> u64 {
+    en_passant_target.map_or(0, |target| KEYS.en_passant[target.column()])
+}
+
+/// Returns the key of the number of checks `side` has given in Three-Check (zero for none).
+#[must_use]
+pub fn checks(side: Side, count: u8) -> u64 {
+    match count {
+        0 => 0,
+        count => KEYS.checks[side.index()][usize::from(count.min(3)) - 1],
+    }
+}
+
+/// Returns the key of `side` holding `count` pieces of `kind` in its pocket in Crazyhouse (zero for none).
+#[must_use]
+pub fn pocket(side: Side, kind: Kind, count: u8) -> u64 {
+    match usize::from(count) {
+        0 => 0,
+        count => KEYS.pockets[side.index()][kind.index()][count.min(POCKET_COUNTS) - 1],
+    }
+}
+
+/// Returns the key of a promoted piece standing on a square in Crazyhouse.
+#[must_use]
+pub fn promoted(square: Coordinate) -> u64 {
+    KEYS.promoted[square.index()]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::state::Game;
+
+    /// Plays the last legal move `plies` times (or until the game ends), checking at every step that the
+    /// incrementally updated hash matches the one of the same position loaded fresh, and that undoing
+    /// restores both the hash and the FEN.
+    fn assert_incremental_hash(fen: &str, plies: usize) {
+        let mut game = Game::from_fen(fen);
+        let mut history = vec![(game.hash(), game.to_fen())];
+
+        for _ in 0..plies {
+            if game.outcome().is_some() {
+                break;
+            }
+            let Some(ply) = game.get_legal_moves().into_iter().last() else {
+                break;
+            };
+            game.make_move(&ply);
+            assert_eq!(game.hash(), Game::from_fen(&game.to_fen()).hash());
+            history.push((game.hash(), game.to_fen()));
+        }
+
+        // Undoing must restore every previous hash, in reverse order
+        while let Some(expected) = history.pop() {
+            assert_eq!((game.hash(), game.to_fen()), expected);
+            game.undo_last_move();
+        }
+    }
+
+    #[test]
+    fn test_incremental_hash_matches_fresh_hash() {
+        assert_incremental_hash(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            40,
+        );
+        assert_incremental_hash(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            40,
+        );
+        assert_incremental_hash("8/P6k/8/8/8/8/6Kp/8 w - - 0 1", 10);
+        assert_incremental_hash("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 30", 10);
+    }
+
+    #[test]
+    fn test_hash_depends_on_state() {
+        let white = Game::from_fen("4k3/8/8/8/8/8/8/R3K3 w Q - 0 1");
+        let black = Game::from_fen("4k3/8/8/8/8/8/8/R3K3 b Q - 0 1");
+        let no_rights = Game::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+
+        assert_ne!(white.hash(), black.hash());
+        assert_ne!(white.hash(), no_rights.hash());
+    }
+
+    #[test]
+    fn test_hash_depends_on_castling_rook_files() {
+        // The same pieces, but White may castle long with the Rook on a1 or on b1
+        let a_file = Game::from_fen("4k3/8/8/8/8/8/8/RR2K3 w A - 0 1");
+        let b_file = Game::from_fen("4k3/8/8/8/8/8/8/RR2K3 w B - 0 1");
+
+        assert_ne!(a_file.to_fen(), b_file.to_fen());
+        assert_ne!(a_file.hash(), b_file.hash());
+    }
+}
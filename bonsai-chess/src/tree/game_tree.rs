@@ -0,0 +1,520 @@
+//! # Game Tree
+//!
+//! The [`GameTree`] stores moves as an arena of [`Node`]s rooted at the starting
+//! position. Positions are not stored in the nodes: they are rebuilt on demand by
+//! replaying the moves from the root, which keeps large analysis trees small.
+
+use crate::{
+    moves::Ply,
+    pgn::{PgnGame, PgnMove, Variation},
+    state::Game,
+    tree::{Node, NodeId},
+};
+
+/// A game with all its variations, and a cursor pointing at the node being looked at.
+///
+/// # Examples
+///
+/// ```rust
+/// use bonsai_chess::prelude::*;
+///
+/// let mut tree = GameTree::default();
+/// let e4 = tree.game().parse_san("e4").unwrap();
+/// tree.play(e4);
+///
+/// // Take the move back and try something else: 1. e4 stays as the mainline
+/// tree.go_back();
+/// let d4 = tree.game().parse_san("d4").unwrap();
+/// let d4_node = tree.play(d4).unwrap();
+/// assert_eq!(tree.to_string().lines().last(), Some("1. e4 (1. d4) *"));
+///
+/// tree.promote_to_mainline(d4_node);
+/// assert_eq!(tree.to_string().lines().last(), Some("1. d4 (1. e4) *"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct GameTree {
+    tags: Vec<(String, String)>,
+    starting_position: Game,
+    /// Every node ever created, indexed by [`NodeId`]. Deleted nodes leave a `None` behind,
+    /// so that the ids of the remaining nodes stay valid.
+    nodes: Vec<Option<Node>>,
+    cursor: NodeId,
+}
+
+/// The id of the root node, which can never be deleted.
+const ROOT: NodeId = NodeId(0);
+
+impl GameTree {
+    /// Creates a tree without moves, starting from `starting_position`.
+    ///
+    /// Moves already played in `starting_position` are not part of the tree. The tags are
+    /// the Seven Tag Roster with unknown values, and `SetUp` and `FEN` when the position
+    /// is not the standard one.
+    #[must_use]
+    pub fn new(starting_position: Game) -> Self {
+        let mut tags = PgnGame::default_tags(&starting_position);
+        if let Some((_, result)) = tags.iter_mut().find(|(name, _)| name == "Result") {
+            *result = String::from("*");
+        }
+
+        Self {
+            tags,
+            starting_position,
+            nodes: vec![Some(Node::new(None, None))],
+            cursor: ROOT,
+        }
+    }
+
+    /// Returns all the tag pairs, in order.
+    #[must_use]
+    pub fn tags(&self) -> &[(String, String)] {
+        &self.tags
+    }
+
+    /// Returns the value of the tag named `name`, if present.
+    #[must_use]
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Sets the value of the tag named `name`, adding it after the existing tags if it is new.
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, old_value)) => *old_value = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// Returns the id of the root node, which stands for the starting position.
+    #[must_use]
+    pub const fn root(&self) -> NodeId {
+        ROOT
+    }
+
+    /// Returns the node with the given id, or `None` if it was deleted.
+    #[must_use]
+    pub fn node(&self, id: NodeId) -> Option<&Node> {
+        self.nodes.get(id.0).and_then(Option::as_ref)
+    }
+
+    /// Returns the nodes of the mainline, in order, without the root.
+    #[must_use]
+    pub fn mainline(&self) -> Vec<NodeId> {
+        let mut mainline = Vec::new();
+        let mut current = ROOT;
+        while let Some(&next) = self.node(current).and_then(|node| node.children.first()) {
+            mainline.push(next);
+            current = next;
+        }
+        mainline
+    }
+
+    /// Returns the game as it stands at the node with the given id, or `None` if it was deleted.
+    #[must_use]
+    pub fn game_at(&self, id: NodeId) -> Option<Game> {
+        let mut plies = Vec::new();
+        let mut current = self.node(id)?;
+        while let (Some(ply), Some(parent)) = (current.ply, current.parent) {
+            plies.push(ply);
+            current = self.node(parent)?;
+        }
+
+        let mut game = self.starting_position.clone();
+        for ply in plies.iter().rev() {
+            game.make_move(ply);
+        }
+        Some(game)
+    }
+
+    /// Adds `ply` as a continuation of the node `parent` and returns the id of the new node.
+    ///
+    /// The new node becomes the main continuation if `parent` had none, and its last
+    /// alternative otherwise. If `ply` is already a continuation of `parent`, the existing
+    /// node is returned instead.
+    ///
+    /// Returns `None` if `parent` does not exist or `ply` is not legal in its position.
+    pub fn add_move(&mut self, parent: NodeId, ply: Ply) -> Option<NodeId> {
+        let existing = self
+            .node(parent)?
+            .children
+            .iter()
+            .copied()
+            .find(|&child| self.node(child).is_some_and(|node| node.ply == Some(ply)));
+        if existing.is_some() {
+            return existing;
+        }
+
+        let game = self.game_at(parent)?;
+        if game.outcome().is_some() || !game.get_legal_moves().contains(&ply) {
+            return None;
+        }
+
+        Some(self.push_node(parent, ply))
+    }
+
+    /// Adds a comment after the move of the node `id` (before the game, for the root).
+    ///
+    /// Returns `false` if the node does not exist.
+    pub fn add_comment(&mut self, id: NodeId, comment: &str) -> bool {
+        let Some(node) = self.node_mut(id) else {
+            return false;
+        };
+
+        if id == ROOT {
+            node.starting_comments.push(comment.to_string());
+        } else {
+            node.comments.push(comment.to_string());
+        }
+        true
+    }
+
+    /// Adds a Numeric Annotation Glyph to the move of the node `id`.
+    ///
+    /// Returns `false` if the node does not exist or is the root.
+    pub fn add_nag(&mut self, id: NodeId, nag: u8) -> bool {
+        match self.node_mut(id) {
+            Some(node) if id != ROOT => {
+                node.nags.push(nag);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Makes the line leading to the node `id` the mainline.
+    ///
+    /// Every node on the way from the root becomes the main continuation of its parent,
+    /// and the lines it replaces become alternatives, keeping their order.
+    pub fn promote_to_mainline(&mut self, id: NodeId) {
+        let mut current = id;
+        while let Some(parent) = self.node(current).and_then(Node::parent) {
+            if let Some(parent_node) = self.node_mut(parent)
+                && let Some(index) = parent_node
+                    .children
+                    .iter()
+                    .position(|&child| child == current)
+            {
+                let child = parent_node.children.remove(index);
+                parent_node.children.insert(0, child);
+            }
+            current = parent;
+        }
+    }
+
+    /// Deletes the node `id` and every line continuing from it.
+    ///
+    /// If the cursor was inside the deleted lines, it moves to the parent of `id`.
+    /// Returns `false` if the node does not exist or is the root, which cannot be deleted.
+    pub fn delete(&mut self, id: NodeId) -> bool {
+        let Some(parent) = self.node(id).and_then(Node::parent) else {
+            return false;
+        };
+
+        if self.is_ancestor(id, self.cursor) {
+            self.cursor = parent;
+        }
+
+        if let Some(parent_node) = self.node_mut(parent) {
+            parent_node.children.retain(|&child| child != id);
+        }
+
+        let mut pending = vec![id];
+        while let Some(current) = pending.pop() {
+            if let Some(node) = self.nodes.get_mut(current.0).and_then(Option::take) {
+                pending.extend(node.children);
+            }
+        }
+
+        true
+    }
+
+    /// Returns the node the cursor points at.
+    #[must_use]
+    pub const fn cursor(&self) -> NodeId {
+        self.cursor
+    }
+
+    /// Moves the cursor to the node `id`.
+    ///
+    /// Returns `false`, leaving the cursor in place, if the node does not exist.
+    pub fn go_to(&mut self, id: NodeId) -> bool {
+        let exists = self.node(id).is_some();
+        if exists {
+            self.cursor = id;
+        }
+        exists
+    }
+
+    /// Moves the cursor to the parent of its node.
+    ///
+    /// Returns `false` if the cursor is already at the root.
+    pub fn go_back(&mut self) -> bool {
+        self.node(self.cursor)
+            .and_then(Node::parent)
+            .is_some_and(|parent| self.go_to(parent))
+    }
+
+    /// Moves the cursor to the main continuation of its node.
+    ///
+    /// Returns `false` if there is no continuation.
+    pub fn go_forward(&mut self) -> bool {
+        self.node(self.cursor)
+            .and_then(|node| node.children.first().copied())
+            .is_some_and(|child| self.go_to(child))
+    }
+
+    /// Adds `ply` as a continuation of the cursor (see [`add_move`](Self::add_move)), and moves the cursor to it.
+    ///
+    /// Returns the id of the node the cursor moved to, or `None` if `ply` is not legal.
+    pub fn play(&mut self, ply: Ply) -> Option<NodeId> {
+        let id = self.add_move(self.cursor, ply)?;
+        self.cursor = id;
+        Some(id)
+    }
+
+    /// Rebuilds the game as it stands at the cursor.
+    #[must_use]
+    pub fn game(&self) -> Game {
+        self.game_at(self.cursor)
+            .unwrap_or_else(|| self.starting_position.clone())
+    }
+
+    /// Converts the tree into a [`PgnGame`], with the alternatives as nested variations.
+    ///
+    /// The game of the resulting [`PgnGame`] is the one at the end of the mainline.
+    #[must_use]
+    pub fn to_pgn_game(&self) -> PgnGame {
+        let mut mainline = self
+            .node(ROOT)
+            .and_then(|root| root.children.first())
+            .map(|&first| self.variation_from(first))
+            .unwrap_or_default();
+
+        if let Some(root) = self.node(ROOT) {
+            mainline
+                .comments
+                .splice(0..0, root.starting_comments.iter().cloned());
+        }
+
+        let game = self
+            .mainline()
+            .last()
+            .and_then(|&last| self.game_at(last))
+            .unwrap_or_else(|| self.starting_position.clone());
+
+        PgnGame {
+            tags: self.tags.clone(),
+            mainline,
+            result: self.tag("Result").unwrap_or("*").to_string(),
+            starting_position: self.starting_position.clone(),
+            game,
+        }
+    }
+
+    /// Returns the line starting at the node `first` and following main continuations,
+    /// with the alternatives of each move as nested variations.
+    fn variation_from(&self, first: NodeId) -> Variation {
+        let mut variation = Variation {
+            comments: self
+                .node(first)
+                .map(|node| node.starting_comments.clone())
+                .unwrap_or_default(),
+            moves: Vec::new(),
+        };
+
+        let mut current = Some(first);
+        while let Some(node) = current.and_then(|id| self.node(id)) {
+            let Some(ply) = node.ply else {
+                break;
+            };
+
+            // Alternatives are listed after the main continuation of their parent
+            let is_main_continuation = node
+                .parent
+                .and_then(|parent| self.node(parent))
+                .is_some_and(|parent| parent.children.first() == current.as_ref());
+            let variations = if is_main_continuation {
+                node.parent
+                    .and_then(|parent| self.node(parent))
+                    .map(|parent| {
+                        parent.children[1..]
+                            .iter()
+                            .map(|&alternative| self.variation_from(alternative))
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+
+            variation.moves.push(PgnMove {
+                ply,
+                nags: node.nags.clone(),
+                comments: node.comments.clone(),
+                variations,
+            });
+
+            current = node.children.first().copied();
+        }
+
+        variation
+    }
+
+    /// Adds a node for `ply` under `parent`, without checking its legality.
+    fn push_node(&mut self, parent: NodeId, ply: Ply) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(Some(Node::new(Some(ply), Some(parent))));
+        if let Some(parent_node) = self.node_mut(parent) {
+            parent_node.children.push(id);
+        }
+        id
+    }
+
+    /// Adds the moves of `variation` under `parent`, with their annotations and nested variations.
+    fn push_variation(&mut self, parent: NodeId, variation: &Variation) {
+        let mut current = parent;
+
+        for (index, pgn_move) in variation.moves.iter().enumerate() {
+            let id = self.push_node(current, pgn_move.ply);
+            if let Some(node) = self.node_mut(id) {
+                if index == 0 {
+                    node.starting_comments.clone_from(&variation.comments);
+                }
+                node.comments.clone_from(&pgn_move.comments);
+                node.nags.clone_from(&pgn_move.nags);
+            }
+
+            // The alternatives to a move start from the same position as the move itself
+            for alternative in &pgn_move.variations {
+                self.push_variation(current, alternative);
+            }
+
+            current = id;
+        }
+    }
+
+    fn node_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+        self.nodes.get_mut(id.0).and_then(Option::as_mut)
+    }
+
+    /// Returns `true` if the node `ancestor` is `descendant` or lies on the way from the root to it.
+    fn is_ancestor(&self, ancestor: NodeId, descendant: NodeId) -> bool {
+        let mut current = Some(descendant);
+        while let Some(id) = current {
+            if id == ancestor {
+                return true;
+            }
+            current = self.node(id).and_then(Node::parent);
+        }
+        false
+    }
+}
+
+impl Default for GameTree {
+    /// Creates a tree without moves, starting from the standard starting position.
+    fn default() -> Self {
+        Self::new(Game::from_starting_position())
+    }
+}
+
+/// Builds a tree from a PGN game, keeping its tags, annotations and variations.
+///
+/// The cursor is placed at the root.
+impl From<&PgnGame> for GameTree {
+    fn from(pgn_game: &PgnGame) -> Self {
+        let mut root = Node::new(None, None);
+        root.starting_comments
+            .clone_from(&pgn_game.mainline.comments);
+
+        let mut tree = Self {
+            tags: pgn_game.tags.clone(),
+            starting_position: pgn_game.starting_position.clone(),
+            nodes: vec![Some(root)],
+            cursor: ROOT,
+        };
+
+        let mainline = Variation {
+            comments: Vec::new(),
+            moves: pgn_game.mainline.moves.clone(),
+        };
+        tree.push_variation(ROOT, &mainline);
+        tree.set_tag("Result", pgn_game.result());
+
+        tree
+    }
+}
+
+/// Formats the tree as a PGN game, with the alternatives as nested variations.
+impl std::fmt::Display for GameTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_pgn_game())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{pgn::PgnGame, tree::GameTree};
+
+    const ANNOTATED: &str = "{Game comment} 1. e4 e5 $1 {Solid} (1... c5 2. Nf3 (2. c3 {Alapin}) 2... d6) \
+                             (1... e6) 2. Nf3 Nc6 *";
+
+    #[test]
+    fn test_pgn_round_trip_keeps_variations() {
+        let pgn_game = PgnGame::parse(ANNOTATED).unwrap();
+        let tree = GameTree::from(&pgn_game);
+
+        assert_eq!(tree.mainline().len(), 4);
+        assert_eq!(tree.to_pgn_game().mainline(), pgn_game.mainline());
+        assert!(tree.to_string().replace('\n', " ").ends_with(
+            "{Game comment} 1. e4 e5 $1 {Solid} (1... c5 2. Nf3 (2. c3 {Alapin}) 2... d6) (1... e6) 2. Nf3 Nc6 *"
+        ));
+
+        let reparsed = PgnGame::parse(&tree.to_string()).unwrap();
+        assert_eq!(reparsed.mainline(), pgn_game.mainline());
+        assert_eq!(reparsed.game().to_fen(), pgn_game.game().to_fen());
+    }
+
+    #[test]
+    fn test_editing_and_navigation() {
+        let mut tree = GameTree::from(&PgnGame::parse(ANNOTATED).unwrap());
+        let mainline = tree.mainline();
+        let (e4, e5) = (mainline[0], mainline[1]);
+
+        // Playing an existing move reuses its node, and illegal moves are refused
+        assert!(tree.go_to(e4));
+        let e5_ply = tree.game().parse_san("e5").unwrap();
+        assert_eq!(tree.play(e5_ply), Some(e5));
+        assert_eq!(tree.add_move(tree.root(), e5_ply), None);
+
+        // The Sicilian becomes the mainline, the old mainline its first alternative
+        let c5 = tree.node(e4).unwrap().children()[1];
+        let nf3 = tree.node(c5).unwrap().children()[0];
+        let d6 = tree.node(nf3).unwrap().children()[0];
+        tree.promote_to_mainline(d6);
+        assert_eq!(tree.mainline(), [e4, c5, nf3, d6]);
+        assert_eq!(
+            tree.node(e4).unwrap().children(),
+            [c5, e5, tree.node(e4).unwrap().children()[2]]
+        );
+
+        // Deleting the line under the cursor moves the cursor to the parent
+        assert!(tree.go_to(d6));
+        assert!(tree.delete(c5));
+        assert_eq!(tree.cursor(), e4);
+        assert!(tree.node(d6).is_none());
+        assert_eq!(tree.mainline(), [e4, e5, mainline[2], mainline[3]]);
+        assert!(!tree.delete(tree.root()));
+
+        assert!(tree.go_forward());
+        assert!(tree.go_forward());
+        assert_eq!(
+            tree.game().to_fen(),
+            "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2"
+        );
+        assert!(tree.go_back());
+        assert_eq!(tree.cursor(), e5);
+    }
+}
@@ -0,0 +1,19 @@
+//! # Game Tree
+//!
+//! A [`Game`](crate::state::Game) only remembers the line that was actually played.
+//! This module provides a [`GameTree`], which keeps every line explored from the
+//! starting position: the mainline, analysis side-lines, moves replaced after a
+//! takeback, and the variations of imported PGN games.
+//!
+//! * [`Node`]: A move of the tree, with its comments and annotation glyphs.
+//! * [`NodeId`]: A stable handle to a node.
+//! * [`GameTree`]: The tree itself, with a cursor to navigate it.
+
+/// The nodes of the tree and the handles to them.
+mod node;
+
+/// The tree of moves, its editing operations and its cursor.
+mod game_tree;
+
+pub use game_tree::GameTree;
+pub use node::{Node, NodeId};
@@ -0,0 +1,76 @@
+//! # Game Tree Nodes
+//!
+//! The nodes of a [`GameTree`](crate::tree::GameTree) live in an arena and refer to
+//! each other through [`NodeId`]s, which stay valid until their node is deleted.
+
+use crate::moves::Ply;
+
+/// A handle to a node of a [`GameTree`](crate::tree::GameTree).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(pub(crate) usize);
+
+/// A node of a [`GameTree`](crate::tree::GameTree).
+///
+/// Every node but the root holds the move that leads to it from its parent. The first
+/// child of a node is the main continuation, and the others are its alternatives.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Node {
+    pub(crate) ply: Option<Ply>,
+    pub(crate) parent: Option<NodeId>,
+    pub(crate) children: Vec<NodeId>,
+    pub(crate) starting_comments: Vec<String>,
+    pub(crate) comments: Vec<String>,
+    pub(crate) nags: Vec<u8>,
+}
+
+impl Node {
+    /// Creates a node reached by playing `ply` from `parent`.
+    pub(crate) const fn new(ply: Option<Ply>, parent: Option<NodeId>) -> Self {
+        Self {
+            ply,
+            parent,
+            children: Vec::new(),
+            starting_comments: Vec::new(),
+            comments: Vec::new(),
+            nags: Vec::new(),
+        }
+    }
+
+    /// Returns the move leading to this node, or `None` for the root.
+    #[must_use]
+    pub const fn ply(&self) -> Option<Ply> {
+        self.ply
+    }
+
+    /// Returns the node this one was reached from, or `None` for the root.
+    #[must_use]
+    pub const fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    /// Returns the continuations of this node, the main one first.
+    #[must_use]
+    pub fn children(&self) -> &[NodeId] {
+        &self.children
+    }
+
+    /// Returns the comments written before the move, when it starts a variation.
+    ///
+    /// For the root, these are the comments on the game as a whole.
+    #[must_use]
+    pub fn starting_comments(&self) -> &[String] {
+        &self.starting_comments
+    }
+
+    /// Returns the comments written after the move.
+    #[must_use]
+    pub fn comments(&self) -> &[String] {
+        &self.comments
+    }
+
+    /// Returns the Numeric Annotation Glyphs of the move.
+    #[must_use]
+    pub fn nags(&self) -> &[u8] {
+        &self.nags
+    }
+}
@@ -1,8 +1,13 @@
 pub const TRANSPOSITION_TABLE_INITIAL_SIZE: usize = 100_000;
+/// The size of the transposition table in megabytes, unless the GUI asks for another one.
+pub const DEFAULT_HASH_SIZE_MB: usize = 32;
 
 pub const STARTING_DEPTH: usize = 1;
 pub const MAX_DEPTH: usize = 50;
 
+// How many nodes are visited between two checks of the wall clock
+pub const NODES_BETWEEN_TIME_CHECKS: u64 = 2048;
+
 pub const SCORING_PROMOTING_PAWNS_BONUS: isize = 800;
 
 pub const CHECKMATE_SCORE: isize = 1_000_000;
@@ -1,23 +1,15 @@
 use bonsai_chess::prelude::*;
 
 use crate::{
-    config::{CHECKMATE_SCORE, DRAW_SCORE},
+    config::DRAW_SCORE,
     evaluation::{KNIGHT_TABLE, PAWN_TABLE, flip_square, get_piece_value},
 };
 
 #[must_use]
 pub fn evaluate_position(state: &Game) -> isize {
-    if let Some(outcome) = state.outcome() {
-        return match outcome {
-            Outcome::Win { winner, .. } => {
-                if winner == state.turn() {
-                    CHECKMATE_SCORE
-                } else {
-                    -CHECKMATE_SCORE
-                }
-            }
-            Outcome::Draw { .. } => DRAW_SCORE,
-        };
+    // Checkmate and Stalemate are left to the search, which finds no legal move there
+    if !state.has_mating_material(Side::White) && !state.has_mating_material(Side::Black) {
+        return DRAW_SCORE;
     }
 
     if state.can_claim_fifty_move_rule() || state.can_claim_threefold_repetition() {
@@ -69,3 +61,36 @@ pub fn evaluate_position(state: &Game) -> isize {
     }
     score
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Positions from every phase of the game, with pieces on both wings and the
+    /// centre, so that each piece-square table is read on many squares.
+    const SYMMETRY_CORPUS: [&str; 8] = [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "2r3k1/5ppp/p7/1p1N4/8/1P3P2/P5PP/3R2K1 b - - 0 30",
+        "8/8/4k3/3n4/8/2N1P3/4K3/8 w - - 0 50",
+    ];
+
+    #[test]
+    fn test_evaluation_is_color_symmetric() {
+        for fen in SYMMETRY_CORPUS {
+            let position = Game::try_from_fen(fen).expect("test positions must be valid FENs");
+            let flipped = position.color_flipped();
+
+            assert_eq!(
+                evaluate_position(&position),
+                evaluate_position(&flipped),
+                "{fen} and its colour-flipped image {} are scored differently",
+                flipped.to_fen()
+            );
+        }
+    }
+}
@@ -1,54 +1,26 @@
-use bonsai_chess::prelude::*;
+use std::sync::atomic::AtomicBool;
 
-use crate::{
-    config::{MAX_DEPTH, STARTING_DEPTH},
-    openings::search_opening_book,
-    search::alpha_beta,
-    transposition_table::TranspositionTable,
-};
+use bonsai_chess::prelude::*;
 
 mod config;
 mod evaluation;
 mod openings;
 mod search;
+mod searcher;
 mod transposition_table;
 
-#[must_use]
-pub fn best_move(mut state: Game, total_depth: usize) -> Option<Ply> {
-    // 1. Check Opening Book first (Placeholder logic)
-    if let Some(book_move) = search_opening_book(&state) {
-        return Some(book_move);
-    }
+pub use config::DEFAULT_HASH_SIZE_MB;
+pub use search::{Score, SearchInfo, SearchLimits};
+pub use searcher::Searcher;
 
-    // 2. Search using Iterative Deepening
-    let mut best_ply = None;
-    let mut current_depth = STARTING_DEPTH;
-    let mut tt = TranspositionTable::new();
-
-    // Continue deepening as long as we have time
-    loop {
-        let mut depth_best_ply = None;
-        alpha_beta(
-            &mut state,
-            current_depth,
-            isize::MIN + 1,
-            isize::MAX - 1,
-            &mut depth_best_ply,
-            &mut tt,
-        );
-
-        if depth_best_ply.is_some() {
-            best_ply = depth_best_ply;
-        }
-
-        current_depth += 1;
+#[must_use]
+pub fn best_move(state: Game, total_depth: usize) -> Option<Ply> {
+    let limits = SearchLimits {
+        depth: Some(total_depth),
+        ..SearchLimits::default()
+    };
 
-        // Check if we've exceeded the allocated time
-        if current_depth > total_depth || current_depth > MAX_DEPTH {
-            break;
-        }
-    }
-    best_ply
+    Searcher::new().search(state, limits, &AtomicBool::new(false), |_| {})
 }
 
 #[cfg(test)]
@@ -57,9 +29,7 @@ mod tests {
 
     /// Helper to setup board from FEN
     fn get_board(fen: &str) -> Game {
-        // Assuming BoardFrontend implements FromStr or has a similar method.
-        // If not, replace this with: BoardFrontend::new(fen) or similar.
-        Game::from_fen(fen)
+        Game::try_from_fen(fen).expect("test positions must be valid FENs")
     }
 
     #[test]
@@ -89,9 +59,9 @@ mod tests {
     #[test]
     fn test_mate_in_one_black() {
         // Black to move and checkmate.
-        // FEN: 7k/8/8/8/8/5n2/7r/7K b - - 0 1
+        // FEN: 7k/8/8/8/8/5n2/6r1/7K b - - 0 1
         // Solution: ...Rh2# (Arabian Mate pattern variant)
-        let fen = "7k/8/8/8/8/5n2/7r/7K b - - 0 1";
+        let fen = "7k/8/8/8/8/5n2/6r1/7K b - - 0 1";
         let state = get_board(fen);
 
         let best = best_move(state, 6);
@@ -1,10 +1,10 @@
 use crate::{
     config::{CHECKMATE_SCORE, DRAW_SCORE},
-    evaluation::{evaluate_position, score_move},
+    evaluation::score_move,
 };
 use crate::{
-    search::quiescence,
-    transposition_table::{Entry, NodeType, TranspositionTable},
+    search::{SearchContext, quiescence},
+    transposition_table::{Entry, NodeType},
 };
 use bonsai_chess::prelude::*;
 
@@ -14,13 +14,18 @@ pub fn alpha_beta(
     mut alpha: isize,
     mut beta: isize,
     best_move_found: &mut Option<Ply>,
-    tt: &mut TranspositionTable, // Added parameter
+    context: &mut SearchContext,
 ) -> isize {
-    let snapshot = state.create_snapshot();
+    // The score is meaningless once aborted, the caller discards it
+    if context.visit() {
+        return 0;
+    }
+
+    let hash = state.hash();
     let mut hash_move = None;
 
     // 1. Transposition Table Lookup
-    if let Some(entry) = tt.get(&snapshot) {
+    if let Some(entry) = context.tt.get(hash) {
         // Save the move to use for sorting later (The Hash Move)
         hash_move = entry.best_move;
 
@@ -44,28 +49,20 @@ pub fn alpha_beta(
         }
     }
 
-    // Handle terminal states and depth exhaustion
-    if let Some(outcome) = state.outcome() {
-        return match outcome {
-            Outcome::Win { winner, .. } => {
-                let score = CHECKMATE_SCORE + depth.cast_signed();
-                if winner == state.turn() {
-                    score
-                } else {
-                    -score
-                }
-            }
-            Outcome::Draw { .. } => DRAW_SCORE,
-        };
-    }
-
+    // Handle depth exhaustion and terminal states
     if depth == 0 {
-        return quiescence(state, alpha, beta);
+        return quiescence(state, alpha, beta, context);
     }
 
-    let mut moves = state.get_legal_moves();
+    let mut moves = MoveList::new();
+    state.generate_moves_into(&mut moves);
     if moves.is_empty() {
-        return evaluate_position(state);
+        // Checkmate is scored higher the sooner it happens
+        return if state.is_in_check() {
+            -(CHECKMATE_SCORE + depth.cast_signed())
+        } else {
+            DRAW_SCORE
+        };
     }
 
     // 2. Move Ordering
@@ -82,10 +79,14 @@ pub fn alpha_beta(
     let mut best_move = None;
     let mut best_score = isize::MIN;
 
-    for ply in moves {
-        state.make_move(&ply);
-        let score = -alpha_beta(state, depth - 1, -beta, -alpha, &mut None, tt);
-        state.undo_last_move();
+    for &ply in &moves {
+        state.make_move_unchecked(&ply);
+        let score = -alpha_beta(state, depth - 1, -beta, -alpha, &mut None, context);
+        state.unmake_move();
+
+        if context.aborted() {
+            return 0;
+        }
 
         if score > best_score {
             best_score = score;
@@ -107,8 +108,8 @@ pub fn alpha_beta(
         NodeType::Exact
     };
 
-    tt.insert(
-        snapshot,
+    context.tt.insert(
+        hash,
         Entry {
             score: best_score,
             depth,
@@ -0,0 +1,70 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Instant,
+};
+
+use crate::{
+    config::NODES_BETWEEN_TIME_CHECKS, search::SearchLimits,
+    transposition_table::TranspositionTable,
+};
+
+/// Mutable state shared by every node of a single search.
+///
+/// Besides holding the transposition table, it counts visited nodes and decides
+/// when the search has to unwind because a limit was hit or it was told to stop.
+pub struct SearchContext<'a> {
+    pub tt: &'a mut TranspositionTable,
+    nodes: u64,
+    limits: SearchLimits,
+    stop: &'a AtomicBool,
+    aborted: bool,
+}
+
+impl<'a> SearchContext<'a> {
+    pub const fn new(
+        tt: &'a mut TranspositionTable,
+        limits: SearchLimits,
+        stop: &'a AtomicBool,
+    ) -> Self {
+        Self {
+            tt,
+            nodes: 0,
+            limits,
+            stop,
+            aborted: false,
+        }
+    }
+
+    /// Counts a newly visited node, returning `true` if the search must unwind.
+    pub fn visit(&mut self) -> bool {
+        if self.aborted {
+            return true;
+        }
+
+        self.nodes += 1;
+
+        let out_of_nodes = self.limits.nodes.is_some_and(|limit| self.nodes >= limit);
+
+        // Reading the clock is comparatively expensive, so only do it every so often
+        let out_of_time = self.nodes.is_multiple_of(NODES_BETWEEN_TIME_CHECKS)
+            && self
+                .limits
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline);
+
+        if out_of_nodes || out_of_time || self.stop.load(Ordering::Relaxed) {
+            self.aborted = true;
+        }
+
+        self.aborted
+    }
+
+    /// Returns `true` once the search has been aborted, its results must then be discarded.
+    pub const fn aborted(&self) -> bool {
+        self.aborted
+    }
+
+    pub const fn nodes(&self) -> u64 {
+        self.nodes
+    }
+}
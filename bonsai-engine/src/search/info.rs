@@ -0,0 +1,53 @@
+use bonsai_chess::prelude::Ply;
+
+use crate::config::CHECKMATE_SCORE;
+
+/// The evaluation of a position from the point of view of the side to move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Score {
+    /// A heuristic evaluation in centipawns.
+    Centipawns(isize),
+
+    /// A forced mate in the given number of full moves, negative if the side to move gets mated.
+    Mate(isize),
+}
+
+impl Score {
+    /// Translates a raw search score found by an iteration of depth `depth`.
+    ///
+    /// Mate scores are stored as `CHECKMATE_SCORE` plus the depth that was left
+    /// when the mate was found, which lets us recover how many plies away it is.
+    #[must_use]
+    pub const fn from_search(score: isize, depth: usize) -> Self {
+        if score.abs() < CHECKMATE_SCORE {
+            return Self::Centipawns(score);
+        }
+
+        let remaining_depth = score.abs() - CHECKMATE_SCORE;
+        let plies = depth.cast_signed() - remaining_depth;
+        let plies = if plies < 1 { 1 } else { plies };
+        let moves = (plies + 1) / 2;
+
+        if score > 0 {
+            Self::Mate(moves)
+        } else {
+            Self::Mate(-moves)
+        }
+    }
+}
+
+/// A progress report emitted every time an iteration of the search completes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchInfo {
+    /// The depth of the completed iteration.
+    pub depth: usize,
+
+    /// The score of the best move found.
+    pub score: Score,
+
+    /// The number of nodes visited since the search started.
+    pub nodes: u64,
+
+    /// The principal variation, starting with the best move.
+    pub principal_variation: Vec<Ply>,
+}
@@ -0,0 +1,17 @@
+use std::time::Instant;
+
+/// Bounds placed on a single search.
+///
+/// Every limit is optional. A search without any limits keeps deepening until
+/// `MAX_DEPTH` is reached or it is stopped from the outside.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchLimits {
+    /// The deepest iteration that will be started.
+    pub depth: Option<usize>,
+
+    /// The number of nodes after which the search is aborted.
+    pub nodes: Option<u64>,
+
+    /// The moment at which the search is aborted.
+    pub deadline: Option<Instant>,
+}
@@ -1,5 +1,11 @@
 mod alpha_beta;
+mod context;
+mod info;
+mod limits;
 mod quiescence;
 
 pub use alpha_beta::alpha_beta;
+pub use context::SearchContext;
+pub use info::{Score, SearchInfo};
+pub use limits::SearchLimits;
 pub use quiescence::quiescence;
@@ -1,8 +1,33 @@
-use crate::evaluation::{evaluate_position, score_move};
+use crate::{
+    config::{CHECKMATE_SCORE, DRAW_SCORE},
+    evaluation::{evaluate_position, score_move},
+    search::SearchContext,
+};
 use bonsai_chess::prelude::*;
 
 // Quiescence Search Function
-pub fn quiescence(state: &mut Game, mut alpha: isize, beta: isize) -> isize {
+pub fn quiescence(
+    state: &mut Game,
+    mut alpha: isize,
+    beta: isize,
+    context: &mut SearchContext,
+) -> isize {
+    if context.visit() {
+        return 0;
+    }
+
+    let mut moves = MoveList::new();
+    state.generate_moves_into(&mut moves);
+
+    // Checkmate or Stalemate
+    if moves.is_empty() {
+        return if state.is_in_check() {
+            -CHECKMATE_SCORE
+        } else {
+            DRAW_SCORE
+        };
+    }
+
     let stand_pat = evaluate_position(state);
 
     // Beta cutoff (Standing pat is good enough)
@@ -15,18 +40,21 @@ pub fn quiescence(state: &mut Game, mut alpha: isize, beta: isize) -> isize {
         alpha = stand_pat;
     }
 
-    let mut moves = state.get_legal_moves();
-
-    // OPTIMIZATION: Only consider capturing moves
-    moves.retain(|m| m.piece_captured().is_some());
+    // OPTIMIZATION: Only consider capturing moves that do not lose material
+    let board = state.backend();
+    moves.retain(|m| m.piece_captured().is_some() && board.see_ge(m, 0));
 
     // Sort captures by MVV-LVA
     moves.sort_by_cached_key(|m| -score_move(m));
 
-    for ply in moves {
-        state.make_move(&ply);
-        let score = -quiescence(state, -beta, -alpha);
-        state.undo_last_move();
+    for &ply in &moves {
+        state.make_move_unchecked(&ply);
+        let score = -quiescence(state, -beta, -alpha, context);
+        state.unmake_move();
+
+        if context.aborted() {
+            return 0;
+        }
 
         if score >= beta {
             return beta;
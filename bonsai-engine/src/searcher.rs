@@ -0,0 +1,117 @@
+use std::sync::atomic::AtomicBool;
+
+use bonsai_chess::prelude::*;
+
+use crate::{
+    config::{MAX_DEPTH, STARTING_DEPTH},
+    openings::search_opening_book,
+    search::{Score, SearchContext, SearchInfo, SearchLimits, alpha_beta},
+    transposition_table::TranspositionTable,
+};
+
+/// A reusable search instance.
+///
+/// Keeping a `Searcher` alive between moves of the same game lets later searches
+/// profit from the positions already stored in its transposition table.
+pub struct Searcher {
+    tt: TranspositionTable,
+}
+
+impl Default for Searcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Searcher {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            tt: TranspositionTable::new(),
+        }
+    }
+
+    /// Resizes the transposition table to roughly `megabytes` of memory, emptying it.
+    pub fn set_hash_size(&mut self, megabytes: usize) {
+        self.tt.resize(megabytes);
+    }
+
+    /// Forgets everything learned by previous searches.
+    pub fn clear(&mut self) {
+        self.tt.clear();
+    }
+
+    /// Searches for the best move using iterative deepening.
+    ///
+    /// The search runs until one of the `limits` is hit or `stop` is raised.
+    /// `report` is called after every completed iteration. The returned move is
+    /// always taken from the deepest iteration that finished, and a legal move is
+    /// returned even if the search was stopped before completing the first one.
+    pub fn search(
+        &mut self,
+        mut state: Game,
+        limits: SearchLimits,
+        stop: &AtomicBool,
+        mut report: impl FnMut(&SearchInfo),
+    ) -> Option<Ply> {
+        // 1. Check Opening Book first (Placeholder logic)
+        if let Some(book_move) = search_opening_book(&state) {
+            return Some(book_move);
+        }
+
+        // 2. Search using Iterative Deepening
+        let mut best_ply = None;
+        let max_depth = limits.depth.unwrap_or(MAX_DEPTH).min(MAX_DEPTH);
+        let mut context = SearchContext::new(&mut self.tt, limits, stop);
+
+        for current_depth in STARTING_DEPTH..=max_depth {
+            let mut depth_best_ply = None;
+            let score = alpha_beta(
+                &mut state,
+                current_depth,
+                isize::MIN + 1,
+                isize::MAX - 1,
+                &mut depth_best_ply,
+                &mut context,
+            );
+
+            // An interrupted iteration is incomplete, keep the previous answer
+            if context.aborted() || depth_best_ply.is_none() {
+                break;
+            }
+
+            best_ply = depth_best_ply;
+
+            report(&SearchInfo {
+                depth: current_depth,
+                score: Score::from_search(score, current_depth),
+                nodes: context.nodes(),
+                principal_variation: principal_variation(&state, context.tt, current_depth),
+            });
+        }
+
+        best_ply.or_else(|| state.get_legal_moves().first().copied())
+    }
+}
+
+/// Walks the best moves stored in the transposition table, starting from `state`.
+fn principal_variation(state: &Game, tt: &TranspositionTable, depth: usize) -> Vec<Ply> {
+    let mut line = Vec::with_capacity(depth);
+    let mut replay = state.clone();
+
+    while line.len() < depth {
+        let Some(ply) = tt.get(replay.hash()).and_then(|entry| entry.best_move) else {
+            break;
+        };
+
+        // Guard against stale entries from a colliding position
+        if !replay.get_legal_moves().contains(&ply) {
+            break;
+        }
+
+        replay.make_move(&ply);
+        line.push(ply);
+    }
+
+    line
+}
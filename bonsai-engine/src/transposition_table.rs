@@ -1,7 +1,7 @@
-use bonsai_chess::prelude::{Ply, PositionSnapshot};
+use bonsai_chess::prelude::Ply;
 use std::collections::HashMap;
 
-use crate::config::TRANSPOSITION_TABLE_INITIAL_SIZE;
+use crate::config::{DEFAULT_HASH_SIZE_MB, TRANSPOSITION_TABLE_INITIAL_SIZE};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum NodeType {
@@ -19,28 +19,44 @@ pub struct Entry {
 }
 
 pub struct TranspositionTable {
-    table: HashMap<PositionSnapshot, Entry>,
+    /// Keyed by the position's Zobrist hash, see `Game::hash`.
+    table: HashMap<u64, Entry>,
+    max_entries: usize,
 }
 
 impl TranspositionTable {
     pub fn new() -> Self {
-        Self {
-            table: HashMap::with_capacity(TRANSPOSITION_TABLE_INITIAL_SIZE),
-        }
+        let mut tt = Self {
+            table: HashMap::new(),
+            max_entries: 0,
+        };
+        tt.resize(DEFAULT_HASH_SIZE_MB);
+        tt
+    }
+
+    /// Drops every entry and bounds the table to roughly `megabytes` of memory.
+    pub fn resize(&mut self, megabytes: usize) {
+        let entry_size = size_of::<(u64, Entry)>();
+        self.max_entries = (megabytes * 1024 * 1024 / entry_size).max(1);
+        self.table = HashMap::with_capacity(TRANSPOSITION_TABLE_INITIAL_SIZE.min(self.max_entries));
+    }
+
+    pub fn clear(&mut self) {
+        self.table.clear();
     }
 
-    pub fn get(&self, snapshot: &PositionSnapshot) -> Option<&Entry> {
-        self.table.get(snapshot)
+    pub fn get(&self, hash: u64) -> Option<&Entry> {
+        self.table.get(&hash)
     }
 
-    pub fn insert(&mut self, snapshot: PositionSnapshot, entry: Entry) {
+    pub fn insert(&mut self, hash: u64, entry: Entry) {
         // Simple replacement strategy: replace if the new search was deeper
-        if let Some(existing) = self.table.get(&snapshot) {
+        if let Some(existing) = self.table.get(&hash) {
             if entry.depth >= existing.depth {
-                self.table.insert(snapshot, entry);
+                self.table.insert(hash, entry);
             }
-        } else {
-            self.table.insert(snapshot, entry);
+        } else if self.table.len() < self.max_entries {
+            self.table.insert(hash, entry);
         }
     }
 }
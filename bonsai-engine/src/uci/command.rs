@@ -0,0 +1,228 @@
+//! # UCI Commands
+//!
+//! Parses the lines a GUI sends to the engine into strongly typed [`Command`]s.
+//! Unknown tokens are skipped, as required by the protocol.
+
+use std::time::Duration;
+
+/// The starting position as sent by `position startpos`.
+pub const STARTPOS_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// A single command sent by the GUI to the engine.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Switch to UCI mode, answered with the engine identity and its options.
+    Uci,
+    /// Synchronisation ping, answered with `readyok`.
+    IsReady,
+    /// Changes the value of one of the options advertised after `uci`.
+    SetOption { name: String, value: Option<String> },
+    /// The next search will belong to a different game.
+    UciNewGame,
+    /// Sets up a position, given as a FEN followed by moves in coordinate notation.
+    Position { fen: String, moves: Vec<String> },
+    /// Starts searching the current position.
+    Go(GoParameters),
+    /// Stops the current search as soon as possible.
+    Stop,
+    /// Exits the program.
+    Quit,
+}
+
+/// The arguments of a `go` command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GoParameters {
+    pub white_time: Option<Duration>,
+    pub black_time: Option<Duration>,
+    pub white_increment: Option<Duration>,
+    pub black_increment: Option<Duration>,
+    pub moves_to_go: Option<u32>,
+    pub depth: Option<usize>,
+    pub nodes: Option<u64>,
+    pub move_time: Option<Duration>,
+    pub infinite: bool,
+}
+
+impl Command {
+    /// Parses a line of input, returning `None` for empty or unrecognised commands.
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut tokens = line.split_whitespace();
+
+        // Unknown leading tokens must be ignored, the command may follow them
+        loop {
+            let command = match tokens.next()? {
+                "uci" => Self::Uci,
+                "isready" => Self::IsReady,
+                "setoption" => Self::parse_set_option(tokens)?,
+                "ucinewgame" => Self::UciNewGame,
+                "position" => Self::parse_position(tokens)?,
+                "go" => Self::Go(GoParameters::parse(tokens)),
+                "stop" => Self::Stop,
+                "quit" => Self::Quit,
+                _ => continue,
+            };
+            return Some(command);
+        }
+    }
+
+    /// `setoption name <id> [value <x>]`, where both id and value may contain spaces.
+    fn parse_set_option<'a>(tokens: impl Iterator<Item = &'a str>) -> Option<Self> {
+        let mut name = Vec::new();
+        let mut value: Option<Vec<&str>> = None;
+        let mut reading_name = false;
+
+        for token in tokens {
+            match token {
+                "name" if value.is_none() => reading_name = true,
+                "value" if reading_name => {
+                    reading_name = false;
+                    value = Some(Vec::new());
+                }
+                _ if reading_name => name.push(token),
+                _ => {
+                    if let Some(value) = value.as_mut() {
+                        value.push(token);
+                    }
+                }
+            }
+        }
+
+        if name.is_empty() {
+            return None;
+        }
+
+        Some(Self::SetOption {
+            name: name.join(" "),
+            value: value.map(|value| value.join(" ")),
+        })
+    }
+
+    /// `position [fen <fenstring> | startpos] [moves <move1> ... <movei>]`
+    fn parse_position<'a>(mut tokens: impl Iterator<Item = &'a str>) -> Option<Self> {
+        let fen = match tokens.next()? {
+            "startpos" => {
+                // Skip up to the optional "moves" keyword
+                if tokens.next().is_some_and(|token| token != "moves") {
+                    return None;
+                }
+                STARTPOS_FEN.to_string()
+            }
+            "fen" => {
+                let mut fields = Vec::with_capacity(6);
+                for token in tokens.by_ref() {
+                    if token == "moves" {
+                        break;
+                    }
+                    fields.push(token);
+                }
+                fields.join(" ")
+            }
+            _ => return None,
+        };
+
+        Some(Self::Position {
+            fen,
+            moves: tokens.map(str::to_string).collect(),
+        })
+    }
+}
+
+impl GoParameters {
+    fn parse<'a>(mut tokens: impl Iterator<Item = &'a str>) -> Self {
+        let mut parameters = Self::default();
+
+        let milliseconds = |token: Option<&str>| {
+            token
+                .and_then(|t| t.parse::<i64>().ok())
+                // Some GUIs send negative times when a player is about to flag
+                .map(|ms| Duration::from_millis(ms.max(0).unsigned_abs()))
+        };
+
+        while let Some(token) = tokens.next() {
+            match token {
+                "wtime" => parameters.white_time = milliseconds(tokens.next()),
+                "btime" => parameters.black_time = milliseconds(tokens.next()),
+                "winc" => parameters.white_increment = milliseconds(tokens.next()),
+                "binc" => parameters.black_increment = milliseconds(tokens.next()),
+                "movetime" => parameters.move_time = milliseconds(tokens.next()),
+                "movestogo" => parameters.moves_to_go = tokens.next().and_then(|t| t.parse().ok()),
+                "depth" => parameters.depth = tokens.next().and_then(|t| t.parse().ok()),
+                "nodes" => parameters.nodes = tokens.next().and_then(|t| t.parse().ok()),
+                "infinite" => parameters.infinite = true,
+                _ => {}
+            }
+        }
+
+        parameters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_simple_commands() {
+        assert_eq!(Command::parse("uci"), Some(Command::Uci));
+        assert_eq!(Command::parse("  isready "), Some(Command::IsReady));
+        assert_eq!(Command::parse("joho quit"), Some(Command::Quit));
+        assert_eq!(Command::parse(""), None);
+        assert_eq!(Command::parse("nonsense"), None);
+    }
+
+    #[test]
+    fn test_parse_position() {
+        assert_eq!(
+            Command::parse("position startpos moves e2e4 e7e5"),
+            Some(Command::Position {
+                fen: STARTPOS_FEN.to_string(),
+                moves: vec!["e2e4".to_string(), "e7e5".to_string()],
+            })
+        );
+
+        assert_eq!(
+            Command::parse("position fen 7k/8/8/8/8/8/8/K7 w - - 0 1"),
+            Some(Command::Position {
+                fen: "7k/8/8/8/8/8/8/K7 w - - 0 1".to_string(),
+                moves: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_set_option() {
+        assert_eq!(
+            Command::parse("setoption name Clear Hash"),
+            Some(Command::SetOption {
+                name: "Clear Hash".to_string(),
+                value: None,
+            })
+        );
+
+        assert_eq!(
+            Command::parse("setoption name Hash value 64"),
+            Some(Command::SetOption {
+                name: "Hash".to_string(),
+                value: Some("64".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_go() {
+        let Some(Command::Go(parameters)) =
+            Command::parse("go wtime 1500 btime -5 winc 10 movestogo 20 depth 6 nodes 5000")
+        else {
+            panic!("Expected a go command");
+        };
+
+        assert_eq!(parameters.white_time, Some(Duration::from_millis(1500)));
+        assert_eq!(parameters.black_time, Some(Duration::ZERO));
+        assert_eq!(parameters.white_increment, Some(Duration::from_millis(10)));
+        assert_eq!(parameters.black_increment, None);
+        assert_eq!(parameters.moves_to_go, Some(20));
+        assert_eq!(parameters.depth, Some(6));
+        assert_eq!(parameters.nodes, Some(5000));
+        assert!(!parameters.infinite);
+    }
+}
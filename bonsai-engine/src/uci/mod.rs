@@ -0,0 +1,240 @@
+//! # UCI Front End
+//!
+//! Implements the engine side of the [Universal Chess Interface](https://www.wbec-ridderkerk.nl/html/UCIProtocol.html).
+//!
+//! The main thread keeps reading commands from standard input while searches run
+//! on a worker thread, so `stop`, `isready` and `quit` are answered immediately.
+
+/// Parsing of the commands sent by the GUI.
+mod command;
+
+/// Conversion of clock information into search limits.
+mod time_management;
+
+use std::{
+    io::BufRead,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use bonsai_chess::prelude::*;
+use bonsai_engine::{DEFAULT_HASH_SIZE_MB, Score, SearchInfo, Searcher};
+
+use crate::uci::{
+    command::{Command, GoParameters},
+    time_management::search_limits,
+};
+
+/// The bounds of the transposition table size accepted from the GUI, in megabytes.
+const MIN_HASH_SIZE_MB: usize = 1;
+const MAX_HASH_SIZE_MB: usize = 4096;
+
+/// A search running on a worker thread, handing the searcher back when done.
+struct RunningSearch {
+    handle: JoinHandle<Searcher>,
+    stop: Arc<AtomicBool>,
+}
+
+/// The state kept between commands.
+struct Session {
+    game: Game,
+    searcher: Option<Searcher>,
+    search: Option<RunningSearch>,
+}
+
+/// Reads commands from standard input until `quit` is received or the input closes.
+pub fn run() {
+    let mut session = Session::new();
+    let stdin = std::io::stdin();
+
+    for line in stdin.lock().lines() {
+        let Ok(line) = line else {
+            break;
+        };
+
+        let Some(command) = Command::parse(&line) else {
+            continue;
+        };
+
+        if command == Command::Quit {
+            break;
+        }
+
+        session.handle(command);
+    }
+
+    session.stop_search();
+}
+
+impl Session {
+    fn new() -> Self {
+        let mut searcher = Searcher::new();
+        searcher.set_hash_size(DEFAULT_HASH_SIZE_MB);
+
+        Self {
+            game: Game::from_starting_position(),
+            searcher: Some(searcher),
+            search: None,
+        }
+    }
+
+    fn handle(&mut self, command: Command) {
+        match command {
+            Command::Uci => {
+                println!("id name bonsai {}", env!("CARGO_PKG_VERSION"));
+                println!("id author niblit");
+                println!(
+                    "option name Hash type spin default {DEFAULT_HASH_SIZE_MB} min {MIN_HASH_SIZE_MB} max {MAX_HASH_SIZE_MB}"
+                );
+                println!("option name Clear Hash type button");
+                println!("uciok");
+            }
+            Command::IsReady => println!("readyok"),
+            Command::SetOption { name, value } => self.set_option(&name, value.as_deref()),
+            Command::UciNewGame => {
+                self.searcher().clear();
+                self.game = Game::from_starting_position();
+            }
+            Command::Position { fen, moves } => self.set_position(&fen, &moves),
+            Command::Go(parameters) => self.go(parameters),
+            Command::Stop | Command::Quit => self.stop_search(),
+        }
+    }
+
+    /// Returns the searcher, stopping the running search first if there is one.
+    fn searcher(&mut self) -> &mut Searcher {
+        self.stop_search();
+        self.searcher.get_or_insert_with(Searcher::new)
+    }
+
+    /// Stops the running search, if any, and blocks until it has handed its searcher back.
+    ///
+    /// The flag must be set before joining: a `go infinite` search only returns once
+    /// told to stop, so joining it first would block the input loop forever.
+    fn stop_search(&mut self) {
+        if let Some(search) = self.search.take() {
+            search.stop.store(true, Ordering::Relaxed);
+
+            if let Ok(searcher) = search.handle.join() {
+                self.searcher = Some(searcher);
+            }
+        }
+    }
+
+    fn set_option(&mut self, name: &str, value: Option<&str>) {
+        match name.to_ascii_lowercase().as_str() {
+            "hash" => {
+                if let Some(megabytes) = value.and_then(|v| v.parse::<usize>().ok()) {
+                    let megabytes = megabytes.clamp(MIN_HASH_SIZE_MB, MAX_HASH_SIZE_MB);
+                    self.searcher().set_hash_size(megabytes);
+                }
+            }
+            "clear hash" => self.searcher().clear(),
+            _ => println!("info string unknown option {name}"),
+        }
+    }
+
+    /// Sets up the position to search, keeping the previous one if the FEN or any of the
+    /// moves is invalid, so that a bad command never leaves a half-played game behind.
+    fn set_position(&mut self, fen: &str, moves: &[String]) {
+        let mut game = match Game::try_from_fen(fen) {
+            Ok(game) => game,
+            Err(error) => {
+                println!("info string invalid fen {error}, keeping the previous position");
+                return;
+            }
+        };
+
+        for text in moves {
+            match game.parse_uci_move(text) {
+                Ok(ply) => game.make_move(&ply),
+                Err(error) => {
+                    println!("info string {error}, keeping the previous position");
+                    return;
+                }
+            }
+        }
+
+        self.game = game;
+    }
+
+    fn go(&mut self, parameters: GoParameters) {
+        let start = Instant::now();
+        let limits = search_limits(&parameters, self.game.turn(), start);
+
+        self.stop_search();
+        let mut searcher = self.searcher.take().unwrap_or_default();
+        let game = self.game.clone();
+        let stop = Arc::new(AtomicBool::new(false));
+        let worker_stop = Arc::clone(&stop);
+
+        let handle = std::thread::spawn(move || {
+            let best = searcher.search(game, limits, &worker_stop, |info| {
+                print_info(info, start.elapsed());
+            });
+
+            // In infinite mode the best move may only be sent once told to stop
+            if parameters.infinite {
+                while !worker_stop.load(Ordering::Relaxed) {
+                    std::thread::sleep(Duration::from_millis(5));
+                }
+            }
+
+            match best {
+                Some(ply) => println!("bestmove {}", ply.to_uci()),
+                None => println!("bestmove 0000"),
+            }
+
+            searcher
+        });
+
+        self.search = Some(RunningSearch { handle, stop });
+    }
+}
+
+fn print_info(info: &SearchInfo, elapsed: Duration) {
+    let score = match info.score {
+        Score::Centipawns(centipawns) => format!("cp {centipawns}"),
+        Score::Mate(moves) => format!("mate {moves}"),
+    };
+
+    let milliseconds = elapsed.as_millis().max(1);
+    let nps = u128::from(info.nodes) * 1000 / milliseconds;
+
+    let pv = info
+        .principal_variation
+        .iter()
+        .map(Ply::to_uci)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    println!(
+        "info depth {} score {score} nodes {} nps {nps} time {milliseconds} pv {pv}",
+        info.depth, info.nodes
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_position_keeps_the_previous_one() {
+        let mut session = Session::new();
+        let moves = ["e2e4".to_string(), "e7e5".to_string()];
+        session.set_position(command::STARTPOS_FEN, &moves);
+        let before = session.game.clone();
+
+        // An illegal move in the middle of the list must not leave a half-played game
+        let illegal = ["d2d4".to_string(), "e1e3".to_string()];
+        session.set_position(command::STARTPOS_FEN, &illegal);
+        assert_eq!(session.game, before);
+
+        session.set_position("not a fen", &[]);
+        assert_eq!(session.game, before);
+    }
+}
@@ -0,0 +1,96 @@
+//! # Time Management
+//!
+//! Turns the clock information of a `go` command into concrete [`SearchLimits`].
+
+use std::time::{Duration, Instant};
+
+use bonsai_chess::prelude::Side;
+use bonsai_engine::SearchLimits;
+
+use crate::uci::command::GoParameters;
+
+/// How many moves we assume are left in the game when the GUI does not say.
+const DEFAULT_MOVES_TO_GO: u32 = 30;
+
+/// Time kept in reserve to absorb communication and scheduling delays.
+const MOVE_OVERHEAD: Duration = Duration::from_millis(30);
+
+/// Computes the limits of a search that started at `start` with `turn` to move.
+pub fn search_limits(parameters: &GoParameters, turn: Side, start: Instant) -> SearchLimits {
+    let mut limits = SearchLimits {
+        depth: parameters.depth,
+        nodes: parameters.nodes,
+        deadline: None,
+    };
+
+    if parameters.infinite {
+        return limits;
+    }
+
+    let (time_left, increment) = match turn {
+        Side::White => (parameters.white_time, parameters.white_increment),
+        Side::Black => (parameters.black_time, parameters.black_increment),
+    };
+
+    let budget = parameters.move_time.or_else(|| {
+        time_left.map(|time_left| {
+            let moves_to_go = parameters.moves_to_go.unwrap_or(DEFAULT_MOVES_TO_GO).max(1);
+            let share = time_left / moves_to_go + increment.unwrap_or_default() / 2;
+
+            // Never plan to use more than what is actually on the clock
+            share.min(time_left)
+        })
+    });
+
+    // The overhead is taken once, and never eats more than half of a small budget, so
+    // that even `go movetime 20` leaves time to finish the first iterations
+    limits.deadline =
+        budget.map(|budget| start + budget.saturating_sub(MOVE_OVERHEAD.min(budget / 2)));
+    limits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_budgets_leave_time_to_search() {
+        let start = Instant::now();
+
+        for milliseconds in [1, 20, 100, 1000] {
+            let move_time = Duration::from_millis(milliseconds);
+            let parameters = GoParameters {
+                move_time: Some(move_time),
+                ..GoParameters::default()
+            };
+
+            let deadline = search_limits(&parameters, Side::White, start)
+                .deadline
+                .unwrap();
+            assert!(deadline > start);
+            assert!(deadline <= start + move_time);
+        }
+    }
+
+    #[test]
+    fn test_clock_share_keeps_the_overhead_once() {
+        let start = Instant::now();
+        let parameters = GoParameters {
+            white_time: Some(Duration::from_secs(30)),
+            moves_to_go: Some(10),
+            ..GoParameters::default()
+        };
+
+        let deadline = search_limits(&parameters, Side::White, start)
+            .deadline
+            .unwrap();
+        // A tenth of the clock, less the overhead
+        assert_eq!(deadline, start + Duration::from_millis(3000 - 30));
+
+        // Black has no clock, so there is no deadline
+        assert_eq!(
+            search_limits(&parameters, Side::Black, start).deadline,
+            None
+        );
+    }
+}
@@ -22,6 +22,7 @@ pub fn GameOver(
             WinReason::Resign => "Opponent Resigned",
             WinReason::WinOnTime => "on Time",
             WinReason::Forfeit => "by Forfeit",
+            WinReason::VariantWin => "by Variant Rules",
         },
         Outcome::Draw { reason } => match reason {
             DrawReason::Stalemate => "Stalemate",
@@ -31,6 +32,7 @@ pub fn GameOver(
             DrawReason::DrawByAgreement => "Agreed Draw",
             DrawReason::DrawOnTime => "Time vs Insufficient Material",
             DrawReason::Forfeit => "Forfeit",
+            DrawReason::VariantDraw => "Variant Rules",
         },
     };
 